@@ -1,31 +1,147 @@
 use std::env::args;
 use std::fs::File;
+use std::io;
 use std::io::BufReader;
 use std::io::Read;
+use std::io::Write;
 use std::process::exit;
-use garmin_fit::fit_file_raw::FitFileRaw;
+use garmin_fit::fit_file_raw::{FitDecoderRaw, FitFileRaw, MessageRaw, TypedMessageRaw};
+use garmin_fit::ndjson::NdjsonLine;
+
+const USAGE: &str = "Usage: fit-to-json [--types] [--ndjson [--index] [--offset]] <FILE>";
 
 fn main() {
-    let args: Vec<String> = args().collect();
-    if args.len() != 2 {
-        eprintln!("You must provide exactly one argument (the file to parse)");
-        eprintln!("Exit 1");
-        exit(1);
+    let args: Vec<String> = args().skip(1).collect();
+    let (flags, files): (Vec<&String>, Vec<&String>) =
+        args.iter().partition(|arg| arg.starts_with("--"));
+    let ndjson = flags.iter().any(|flag| *flag == "--ndjson");
+    let index = flags.iter().any(|flag| *flag == "--index");
+    let offset = flags.iter().any(|flag| *flag == "--offset");
+    let types = flags.iter().any(|flag| *flag == "--types");
+    let known_flags = flags
+        .iter()
+        .all(|flag| ["--ndjson", "--index", "--offset", "--types"].contains(&flag.as_str()));
+    if files.len() != 1 || !known_flags || ((index || offset) && !ndjson) {
+        eprintln!("You must provide exactly one argument (the FIT or CSV file to parse)");
+        eprintln!("{}", USAGE);
+        eprintln!("Exit 2");
+        exit(2);
     }
-    let file_name = &args[1];
+    let file_name = files[0];
     let file = File::open(file_name);
     if file.is_err() {
         eprintln!("File {} cannot be read.", file_name);
-        eprintln!("Exit 2");
-        exit(2);
+        eprintln!("Exit 3");
+        exit(3);
     }
 
     let f = file.unwrap();
     let mut reader = BufReader::new(f);
+    // CSV files in the layout of the FIT SDK's FitCSVTool are read into the same messages
+    let is_csv = file_name.to_lowercase().ends_with(".csv");
+    if ndjson && !is_csv {
+        stream_ndjson(reader, index, offset, types, file_name);
+        return;
+    }
     let mut buffer = Vec::new();
 
-    reader.read_to_end(&mut buffer).unwrap();
+    if reader.read_to_end(&mut buffer).is_err() {
+        eprintln!("File {} cannot be read.", file_name);
+        eprintln!("Exit 3");
+        exit(3);
+    }
 
-    let fit_file = FitFileRaw::from(&buffer);
-    println!("{}", serde_json::to_string(&fit_file.messages).unwrap());
+    let parsed = if is_csv {
+        match String::from_utf8(buffer) {
+            Ok(csv) => FitFileRaw::from_fit_csv(&csv).map(|fit_file| vec![fit_file]),
+            Err(_) => {
+                eprintln!("File {} is not a valid CSV file: not UTF-8", file_name);
+                eprintln!("Exit 4");
+                exit(4);
+            }
+        }
+    } else {
+        FitFileRaw::from_chained(&buffer)
+    };
+    let fit_files = match parsed {
+        Ok(fit_files) => fit_files,
+        Err(error) => {
+            let format = if is_csv { "CSV" } else { "FIT" };
+            eprintln!(
+                "File {} is not a valid {} file: {}",
+                file_name, format, error
+            );
+            eprintln!("Exit 4");
+            exit(4);
+        }
+    };
+    let messages: Vec<_> = fit_files
+        .into_iter()
+        .flat_map(|fit_file| fit_file.messages)
+        .collect();
+    if ndjson {
+        // the rows of a CSV file have no byte offset in a FIT file
+        let mut stdout = io::stdout().lock();
+        for (message_index, message) in messages.iter().enumerate() {
+            let message_index = index.then_some(message_index);
+            if write_line(&mut stdout, message, types, message_index, None).is_err() {
+                return;
+            }
+        }
+    } else if types {
+        let typed: Vec<_> = messages.iter().map(TypedMessageRaw).collect();
+        println!("{}", serde_json::to_string(&typed).unwrap());
+    } else {
+        println!("{}", serde_json::to_string(&messages).unwrap());
+    }
+}
+
+/// prints the messages as newline-delimited JSON while they are decoded
+fn stream_ndjson<R: Read>(reader: R, index: bool, offset: bool, types: bool, file_name: &str) {
+    let mut decoder = FitDecoderRaw::new(reader);
+    let mut stdout = io::stdout().lock();
+    let mut message_index = 0;
+    while let Some(message) = decoder.next() {
+        let message = match message {
+            Ok(message) => message,
+            Err(error) => {
+                eprintln!("File {} is not a valid FIT file: {}", file_name, error);
+                eprintln!("Exit 4");
+                exit(4);
+            }
+        };
+        let index = index.then_some(message_index);
+        let offset = offset.then_some(decoder.message_position());
+        // stop quietly when the reader of the output is gone, e.g. `head`
+        if write_line(&mut stdout, &message, types, index, offset).is_err() {
+            return;
+        }
+        message_index += 1;
+    }
+}
+
+/// writes the message as line of newline-delimited JSON, with base types and sizes if `types` is set
+fn write_line<W: Write>(
+    writer: &mut W,
+    message: &MessageRaw,
+    types: bool,
+    index: Option<usize>,
+    offset: Option<usize>,
+) -> io::Result<()> {
+    if types {
+        let message = TypedMessageRaw(message);
+        let line = NdjsonLine {
+            message: &message,
+            index,
+            offset,
+        };
+        line.write_to(writer)
+    } else {
+        let line = NdjsonLine {
+            message,
+            index,
+            offset,
+        };
+        line.write_to(writer)
+    }
 }
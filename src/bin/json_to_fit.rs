@@ -0,0 +1,45 @@
+use std::env::args;
+use std::fs;
+use std::process::exit;
+
+use garmin_fit::fit_encoder::{json_to_fit, FitEncoderConfig};
+
+const USAGE: &str = "Usage: json-to-fit [--big-endian] <FILE> <OUTPUT>";
+
+fn main() {
+    let args: Vec<String> = args().skip(1).collect();
+    let (flags, files): (Vec<&String>, Vec<&String>) =
+        args.iter().partition(|arg| arg.starts_with("--"));
+    let big_endian = flags.iter().any(|flag| *flag == "--big-endian");
+    let known_flags = flags.iter().all(|flag| *flag == "--big-endian");
+    if files.len() != 2 || !known_flags {
+        eprintln!("You must provide exactly two arguments (the JSON file to read and the FIT file to write)");
+        eprintln!("{}", USAGE);
+        eprintln!("Exit 2");
+        exit(2);
+    }
+    let (file_name, output_name) = (files[0], files[1]);
+    let Ok(json) = fs::read_to_string(file_name) else {
+        eprintln!("File {} cannot be read.", file_name);
+        eprintln!("Exit 3");
+        exit(3);
+    };
+
+    let config = FitEncoderConfig {
+        big_endian,
+        ..FitEncoderConfig::default()
+    };
+    let fit = match json_to_fit(&json, config) {
+        Ok(fit) => fit,
+        Err(error) => {
+            eprintln!("File {} cannot be converted: {}", file_name, error);
+            eprintln!("Exit 4");
+            exit(4);
+        }
+    };
+    if fs::write(output_name, fit).is_err() {
+        eprintln!("File {} cannot be written.", output_name);
+        eprintln!("Exit 5");
+        exit(5);
+    }
+}
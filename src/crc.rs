@@ -0,0 +1,126 @@
+use serde::Serialize;
+
+use crate::error::{read_bytes, FitError};
+
+const CRC_TABLE: [u16; 16] = [
+    0x0000, 0xCC01, 0xD801, 0x1400, 0xF001, 0x3C00, 0x2800, 0xE401, 0xA001, 0x6C00, 0x7800, 0xB401,
+    0x5000, 0x9C01, 0x8801, 0x4400,
+];
+
+/// Calculates the FIT CRC-16 of the given bytes
+pub fn crc16(data: &[u8]) -> u16 {
+    crc16_update(0, data)
+}
+
+/// Continues the FIT CRC-16 calculation of `crc` with the given bytes
+pub fn crc16_update(crc: u16, data: &[u8]) -> u16 {
+    let mut crc = crc;
+    for byte in data {
+        // lower nibble first
+        let tmp = CRC_TABLE[(crc & 0xF) as usize];
+        crc = (crc >> 4) & 0x0FFF;
+        crc = crc ^ tmp ^ CRC_TABLE[(byte & 0xF) as usize];
+        // then the upper nibble
+        let tmp = CRC_TABLE[(crc & 0xF) as usize];
+        crc = (crc >> 4) & 0x0FFF;
+        crc = crc ^ tmp ^ CRC_TABLE[((byte >> 4) & 0xF) as usize];
+    }
+    crc
+}
+
+/// Result of a single CRC check
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct CrcCheck {
+    /// CRC stored in the file
+    pub expected: u16,
+    /// CRC calculated from the file content
+    pub computed: u16,
+}
+
+impl CrcCheck {
+    pub fn is_valid(&self) -> bool {
+        self.expected == self.computed
+    }
+
+    /// Checks the CRC of a 14 byte header.
+    /// Returns `None` for the legacy 12 byte header or if the header CRC is set to 0x0000,
+    /// which means that it was not computed by the writer.
+    pub fn header(header_info: &[u8]) -> Option<CrcCheck> {
+        if header_info.len() < 14 || header_info[0] < 14 {
+            return None;
+        }
+        let expected = u16::from_le_bytes([header_info[12], header_info[13]]);
+        if expected == 0 {
+            return None;
+        }
+        Some(CrcCheck {
+            expected,
+            computed: crc16(&header_info[0..12]),
+        })
+    }
+
+    /// Checks the CRC that follows the content (header and data records) in the buffer.
+    pub fn file(buffer: &[u8], content_end: usize) -> Result<CrcCheck, FitError> {
+        let crc_bytes = read_bytes(buffer, content_end, 2, "file CRC")?;
+        Ok(CrcCheck {
+            expected: u16::from_le_bytes([crc_bytes[0], crc_bytes[1]]),
+            computed: crc16(&buffer[0..content_end]),
+        })
+    }
+}
+
+/// Results of the header and file CRC checks
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct CrcReport {
+    /// `None` if the header has no CRC
+    pub header: Option<CrcCheck>,
+    /// `None` if only the header was parsed
+    pub file: Option<CrcCheck>,
+}
+
+impl CrcReport {
+    pub fn is_valid(&self) -> bool {
+        self.header.as_ref().map_or(true, CrcCheck::is_valid)
+            && self.file.as_ref().map_or(true, CrcCheck::is_valid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_check_value_of_crc_16_arc() {
+        // the FIT CRC is CRC-16/ARC, whose check value for "123456789" is 0xBB3D
+        assert_eq!(crc16(b"123456789"), 0xBB3D);
+    }
+
+    #[test]
+    fn continues_calculation_over_chunks() {
+        assert_eq!(crc16_update(crc16(b"1234"), b"56789"), 0xBB3D);
+    }
+
+    #[test]
+    fn checks_header_crc() {
+        // 14 byte header: protocol 2.0, profile 21.32, 500 bytes of data, ".FIT", CRC 0xA6A7
+        let header = [
+            0x0E, 0x20, 0x54, 0x08, 0xF4, 0x01, 0x00, 0x00, 0x2E, 0x46, 0x49, 0x54, 0xA7, 0xA6,
+        ];
+        let check = CrcCheck::header(&header).unwrap();
+        assert_eq!(check.computed, 0xA6A7);
+        assert!(check.is_valid());
+
+        let mut corrupted = header;
+        corrupted[4] = 0xF5;
+        assert!(!CrcCheck::header(&corrupted).unwrap().is_valid());
+    }
+
+    #[test]
+    fn ignores_header_without_crc() {
+        let header = [
+            0x0E, 0x20, 0x54, 0x08, 0xF4, 0x01, 0x00, 0x00, 0x2E, 0x46, 0x49, 0x54, 0x00, 0x00,
+        ];
+        assert_eq!(CrcCheck::header(&header), None);
+        assert_eq!(CrcCheck::header(&header[0..12]), None);
+    }
+}
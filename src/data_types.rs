@@ -2,7 +2,11 @@ use std::fmt::Debug;
 
 use serde::{Serialize, Serializer};
 
+/// 2^31 semicircles are 180 degrees
+const SEMICIRCLES_PER_180_DEGREES: f64 = 2147483648.0;
+
 #[derive(PartialEq, Clone, Debug)]
+#[allow(clippy::enum_variant_names)]
 pub enum Value {
     EnumValue(Vec<u8>),
     NumberValueS8(i8),
@@ -31,9 +35,123 @@ pub enum Value {
 
 impl Value {
     pub fn is_invalid(&self) -> bool {
-        match *self {
-            Value::Invalid => true,
-            _ => false,
+        matches!(*self, Value::Invalid)
+    }
+
+    /// numeric value as `f64`, `None` for arrays, strings, enums and invalid values
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::NumberValueS8(value) => Some(f64::from(*value)),
+            Value::NumberValueU8(value) => Some(f64::from(*value)),
+            Value::NumberValueS16(value) => Some(f64::from(*value)),
+            Value::NumberValueU16(value) => Some(f64::from(*value)),
+            Value::NumberValueS32(value) => Some(f64::from(*value)),
+            Value::NumberValueU32(value) => Some(f64::from(*value)),
+            Value::NumberValueS64(value) => Some(*value as f64),
+            Value::NumberValueU64(value) => Some(*value as f64),
+            Value::NumberValueF32(value) => Some(f64::from(*value)),
+            Value::NumberValueF64(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// numeric array as `f64` values, `None` for all other values
+    pub fn as_f64_vec(&self) -> Option<Vec<f64>> {
+        match self {
+            Value::NumberValueVecS8(values) => {
+                Some(values.iter().copied().map(f64::from).collect())
+            }
+            Value::NumberValueVecU8(values) => {
+                Some(values.iter().copied().map(f64::from).collect())
+            }
+            Value::NumberValueVecS16(values) => {
+                Some(values.iter().copied().map(f64::from).collect())
+            }
+            Value::NumberValueVecU16(values) => {
+                Some(values.iter().copied().map(f64::from).collect())
+            }
+            Value::NumberValueVecS32(values) => {
+                Some(values.iter().copied().map(f64::from).collect())
+            }
+            Value::NumberValueVecU32(values) => {
+                Some(values.iter().copied().map(f64::from).collect())
+            }
+            Value::NumberValueVecS64(values) => Some(values.iter().map(|&v| v as f64).collect()),
+            Value::NumberValueVecU64(values) => Some(values.iter().map(|&v| v as f64).collect()),
+            Value::NumberValueVecF32(values) => {
+                Some(values.iter().copied().map(f64::from).collect())
+            }
+            Value::NumberValueVecF64(values) => Some(values.clone()),
+            _ => None,
+        }
+    }
+
+    /// the value as array, a single number becomes an array of one element
+    pub fn into_array(self) -> Value {
+        match self {
+            Value::NumberValueS8(value) => Value::NumberValueVecS8(vec![value]),
+            Value::NumberValueU8(value) => Value::NumberValueVecU8(vec![value]),
+            Value::NumberValueS16(value) => Value::NumberValueVecS16(vec![value]),
+            Value::NumberValueU16(value) => Value::NumberValueVecU16(vec![value]),
+            Value::NumberValueS32(value) => Value::NumberValueVecS32(vec![value]),
+            Value::NumberValueU32(value) => Value::NumberValueVecU32(vec![value]),
+            Value::NumberValueS64(value) => Value::NumberValueVecS64(vec![value]),
+            Value::NumberValueU64(value) => Value::NumberValueVecU64(vec![value]),
+            Value::NumberValueF32(value) => Value::NumberValueVecF32(vec![value]),
+            Value::NumberValueF64(value) => Value::NumberValueVecF64(vec![value]),
+            value => value,
+        }
+    }
+
+    /// converts a position in semicircles into decimal degrees (`semicircles * 180 / 2^31`),
+    /// values that are not numeric are returned unchanged
+    pub fn semicircles_to_degrees(&self) -> Value {
+        let convert = |semicircles: f64| semicircles * 180.0 / SEMICIRCLES_PER_180_DEGREES;
+        if let Some(semicircles) = self.as_f64() {
+            Value::NumberValueF64(convert(semicircles))
+        } else if let Some(semicircles) = self.as_f64_vec() {
+            Value::NumberValueVecF64(semicircles.into_iter().map(convert).collect())
+        } else {
+            self.clone()
+        }
+    }
+
+    /// numeric elements of the value, a single number is one element
+    fn elements(&self) -> Vec<Number> {
+        fn integers<T: Copy + Into<i128>>(values: &[T]) -> Vec<Number> {
+            values
+                .iter()
+                .map(|&value| Number::Integer(value.into()))
+                .collect()
+        }
+        fn decimals<T: Copy + Into<f64>>(values: &[T]) -> Vec<Number> {
+            values
+                .iter()
+                .map(|&value| Number::Decimal(value.into()))
+                .collect()
+        }
+        match self {
+            Value::EnumValue(values) | Value::NumberValueVecU8(values) => integers(values),
+            Value::NumberValueS8(value) => integers(&[*value]),
+            Value::NumberValueU8(value) => integers(&[*value]),
+            Value::NumberValueS16(value) => integers(&[*value]),
+            Value::NumberValueU16(value) => integers(&[*value]),
+            Value::NumberValueS32(value) => integers(&[*value]),
+            Value::NumberValueU32(value) => integers(&[*value]),
+            Value::NumberValueS64(value) => integers(&[*value]),
+            Value::NumberValueU64(value) => integers(&[*value]),
+            Value::NumberValueF32(value) => decimals(&[*value]),
+            Value::NumberValueF64(value) => decimals(&[*value]),
+            Value::NumberValueVecS8(values) => integers(values),
+            Value::NumberValueVecS16(values) => integers(values),
+            Value::NumberValueVecU16(values) => integers(values),
+            Value::NumberValueVecS32(values) => integers(values),
+            Value::NumberValueVecU32(values) => integers(values),
+            Value::NumberValueVecS64(values) => integers(values),
+            Value::NumberValueVecU64(values) => integers(values),
+            Value::NumberValueVecF32(values) => decimals(values),
+            Value::NumberValueVecF64(values) => decimals(values),
+            Value::StringValue(_) | Value::Invalid => vec![],
         }
     }
 
@@ -43,25 +161,25 @@ impl Value {
     {
         match value_type {
             Value::EnumValue(value) => serializer.serialize_some(value),
-            Value::NumberValueU64(value) => serializer.serialize_u64(value.clone()),
-            Value::NumberValueU16(value) => serializer.serialize_u16(value.clone()),
+            Value::NumberValueU64(value) => serializer.serialize_u64(*value),
+            Value::NumberValueU16(value) => serializer.serialize_u16(*value),
             Value::NumberValueVecU16(value) => serializer.serialize_some(value),
-            Value::NumberValueU32(value) => serializer.serialize_u32(value.clone()),
+            Value::NumberValueU32(value) => serializer.serialize_u32(*value),
             Value::NumberValueVecU32(value) => serializer.serialize_some(value),
             Value::NumberValueVecU64(value) => serializer.serialize_some(value),
             Value::NumberValueVecS64(value) => serializer.serialize_some(value),
-            Value::NumberValueU8(value) => serializer.serialize_u8(value.clone()),
+            Value::NumberValueU8(value) => serializer.serialize_u8(*value),
             Value::NumberValueVecU8(value) => serializer.serialize_some(value),
-            Value::NumberValueS64(value) => serializer.serialize_i64(value.clone()),
-            Value::NumberValueS16(value) => serializer.serialize_i16(value.clone()),
+            Value::NumberValueS64(value) => serializer.serialize_i64(*value),
+            Value::NumberValueS16(value) => serializer.serialize_i16(*value),
             Value::NumberValueVecS16(value) => serializer.serialize_some(value),
-            Value::NumberValueS32(value) => serializer.serialize_i32(value.clone()),
+            Value::NumberValueS32(value) => serializer.serialize_i32(*value),
             Value::NumberValueVecS32(value) => serializer.serialize_some(value),
-            Value::NumberValueS8(value) => serializer.serialize_i8(value.clone()),
+            Value::NumberValueS8(value) => serializer.serialize_i8(*value),
             Value::NumberValueVecS8(value) => serializer.serialize_some(value),
             Value::StringValue(value) => serializer.serialize_str(value.as_str()),
-            Value::NumberValueF32(value) => serializer.serialize_f32(value.clone()),
-            Value::NumberValueF64(value) => serializer.serialize_f64(value.clone()),
+            Value::NumberValueF32(value) => serializer.serialize_f32(*value),
+            Value::NumberValueF64(value) => serializer.serialize_f64(*value),
             Value::NumberValueVecF32(value) => serializer.serialize_some(value),
             Value::NumberValueVecF64(value) => serializer.serialize_some(value),
             Value::Invalid => serializer.serialize_str("invalid value"),
@@ -74,10 +192,16 @@ impl Serialize for Value {
     where
         S: Serializer,
     {
-        Value::serialize_intern(&self, serializer)
+        Value::serialize_intern(self, serializer)
     }
 }
 
+/// element of a numeric value
+enum Number {
+    Integer(i128),
+    Decimal(f64),
+}
+
 #[derive(Debug)]
 pub struct BaseType {
     pub read_size: usize,
@@ -107,13 +231,13 @@ impl BaseType {
         SINT64, 8, 142, 0x7FFFFFFFFFFFFFFF, i64, NumberValueS64, NumberValueVecS64
         UINT8, 1, 2, 0xFF, u8, NumberValueU8, NumberValueVecU8
         UINT16, 2, 132, 0xFFFF, u16, NumberValueU16, NumberValueVecU16
-        UINT32, 4, 134, 0xFFFFFFF, u32, NumberValueU32, NumberValueVecU32
+        UINT32, 4, 134, 0xFFFFFFFF, u32, NumberValueU32, NumberValueVecU32
         UINT64, 8, 143, 0xFFFFFFFFFFFFFFFF, u64, NumberValueU64, NumberValueVecU64
         UINT8Z, 1, 10, 0x00, u8, NumberValueU8, NumberValueVecU8
         UINT16Z, 2, 139, 0x0000, u16, NumberValueU16, NumberValueVecU16
         UINT32Z, 4, 140, 0x00000000, u32, NumberValueU32, NumberValueVecU32
         UINT64Z, 8, 144, 0x0000000000000000, u64, NumberValueU64, NumberValueVecU64
-        FLOAT32, 4, 136, 0xFFFFFFF, f32, NumberValueF32, NumberValueVecF32
+        FLOAT32, 4, 136, 0xFFFFFFFF, f32, NumberValueF32, NumberValueVecF32
         FLOAT64, 8, 137, 0xFFFFFFFFFFFFFFFF, f64, NumberValueF64, NumberValueVecF64
     }
     // Null terminated string encoded in UTF-8 format
@@ -122,10 +246,8 @@ impl BaseType {
         type_number: 7,
         invalid_value: 0x00,
         read: |me, data, _| {
-            let size = data.len();
             let mut value = String::new();
-            for i in 0..size {
-                let raw_value = data[i];
+            for &raw_value in data {
                 if raw_value != 0 && raw_value != me.invalid_value as u8 {
                     value.push(raw_value as char);
                 }
@@ -154,12 +276,179 @@ impl BaseType {
         BaseType::FLOAT64,
     ];
 
-    pub fn parse(value: &u8) -> BaseType {
-        for base_type in BaseType::ALL_TYPES {
-            if base_type.type_number.eq(value) {
-                return base_type;
+    pub fn parse(value: &u8) -> Option<BaseType> {
+        BaseType::ALL_TYPES
+            .into_iter()
+            .find(|base_type| base_type.type_number.eq(value))
+    }
+
+    /// name of the base type as in the FIT profile, e.g. `uint16`
+    pub fn name(&self) -> &'static str {
+        match self.type_number {
+            0 => "enum",
+            1 => "sint8",
+            2 => "uint8",
+            7 => "string",
+            10 => "uint8z",
+            13 => "byte",
+            131 => "sint16",
+            132 => "uint16",
+            133 => "sint32",
+            134 => "uint32",
+            136 => "float32",
+            137 => "float64",
+            139 => "uint16z",
+            140 => "uint32z",
+            142 => "sint64",
+            143 => "uint64",
+            144 => "uint64z",
+            _ => "unknown",
+        }
+    }
+
+    /// resolves a base type by its name as in the FIT profile, see [BaseType::name]
+    pub fn parse_name(name: &str) -> Option<BaseType> {
+        BaseType::ALL_TYPES
+            .into_iter()
+            .find(|base_type| base_type.name() == name)
+    }
+
+    /// base type that is read into the kind of value, `None` for invalid values
+    pub fn of(value: &Value) -> Option<BaseType> {
+        match value {
+            Value::EnumValue(_) => Some(BaseType::ENUM),
+            Value::NumberValueS8(_) | Value::NumberValueVecS8(_) => Some(BaseType::SINT8),
+            Value::NumberValueU8(_) | Value::NumberValueVecU8(_) => Some(BaseType::UINT8),
+            Value::NumberValueS16(_) | Value::NumberValueVecS16(_) => Some(BaseType::SINT16),
+            Value::NumberValueU16(_) | Value::NumberValueVecU16(_) => Some(BaseType::UINT16),
+            Value::NumberValueS32(_) | Value::NumberValueVecS32(_) => Some(BaseType::SINT32),
+            Value::NumberValueU32(_) | Value::NumberValueVecU32(_) => Some(BaseType::UINT32),
+            Value::StringValue(_) => Some(BaseType::STRING),
+            Value::NumberValueS64(_) | Value::NumberValueVecS64(_) => Some(BaseType::SINT64),
+            Value::NumberValueU64(_) | Value::NumberValueVecU64(_) => Some(BaseType::UINT64),
+            Value::NumberValueF32(_) | Value::NumberValueVecF32(_) => Some(BaseType::FLOAT32),
+            Value::NumberValueF64(_) | Value::NumberValueVecF64(_) => Some(BaseType::FLOAT64),
+            Value::Invalid => None,
+        }
+    }
+
+    fn is_signed(&self) -> bool {
+        matches!(self.type_number, 1 | 131 | 133 | 142)
+    }
+
+    pub(crate) fn is_float(&self) -> bool {
+        matches!(self.type_number, 136 | 137)
+    }
+
+    /// bytes of an invalid element, with all bits set except for signed types (maximum) and `z` types (zero)
+    fn invalid_bytes(&self, endianness: u8) -> Vec<u8> {
+        self.to_bytes(self.invalid_value, endianness)
+    }
+
+    /// the lowest `read_size` bytes of the bits
+    fn to_bytes(&self, bits: u64, endianness: u8) -> Vec<u8> {
+        if endianness == 0 {
+            bits.to_le_bytes()[..self.read_size].to_vec()
+        } else {
+            bits.to_be_bytes()[8 - self.read_size..].to_vec()
+        }
+    }
+
+    /// Encodes a value as stored in a FIT file, the inverse of `read`.
+    ///
+    /// Numbers are converted into the base type if they fit, strings are terminated with `0`.
+    /// `size` in bytes pads strings with `0` and arrays with invalid elements,
+    /// without `size` the value defines the size. [Value::Invalid] is written as invalid elements.
+    pub fn encode(
+        &self,
+        value: &Value,
+        size: Option<u8>,
+        endianness: u8,
+    ) -> Result<Vec<u8>, String> {
+        let mut bytes = vec![];
+        match value {
+            Value::Invalid => {}
+            Value::StringValue(text) => {
+                if self.type_number != BaseType::STRING.type_number {
+                    return Err(format!(
+                        "string {:?} for base type {}",
+                        text, self.type_number
+                    ));
+                }
+                bytes.extend(text.as_bytes());
+                // the terminating zero can be left out if the string fills the field
+                if size.map_or(true, |size| bytes.len() < size as usize) {
+                    bytes.push(0);
+                }
+            }
+            value => {
+                if self.type_number == BaseType::STRING.type_number {
+                    return Err(format!("{:?} for a string", value));
+                }
+                for element in value.elements() {
+                    bytes.extend(self.encode_element(element, endianness)?);
+                }
+            }
+        }
+        let size = match size {
+            Some(size) => size as usize,
+            None if bytes.is_empty() => self.read_size,
+            None => bytes.len(),
+        };
+        if size % self.read_size != 0 {
+            return Err(format!(
+                "size {} does not match base type {}",
+                size, self.type_number
+            ));
+        }
+        if bytes.len() > size {
+            return Err(format!(
+                "{:?} needs {} bytes, more than the size {}",
+                value,
+                bytes.len(),
+                size
+            ));
+        }
+        while bytes.len() < size {
+            bytes.extend(self.invalid_bytes(endianness));
+        }
+        Ok(bytes)
+    }
+
+    fn encode_element(&self, element: Number, endianness: u8) -> Result<Vec<u8>, String> {
+        if self.is_float() {
+            let float = match element {
+                Number::Integer(integer) => integer as f64,
+                Number::Decimal(float) => float,
+            };
+            return Ok(if self.read_size == 4 {
+                self.to_bytes(u64::from((float as f32).to_bits()), endianness)
+            } else {
+                self.to_bytes(float.to_bits(), endianness)
+            });
+        }
+        let integer = match element {
+            Number::Integer(integer) => integer,
+            Number::Decimal(float) if float.fract() == 0.0 => float as i128,
+            Number::Decimal(float) => {
+                return Err(format!(
+                    "{} is no integer for base type {}",
+                    float, self.type_number
+                ))
             }
+        };
+        let bits = self.read_size as u32 * 8;
+        let (min, max) = if self.is_signed() {
+            (-(1i128 << (bits - 1)), (1i128 << (bits - 1)) - 1)
+        } else {
+            (0, (1i128 << bits) - 1)
+        };
+        if integer < min || integer > max {
+            return Err(format!(
+                "{} does not fit base type {}",
+                integer, self.type_number
+            ));
         }
-        panic!("Unknown type {}", value)
+        Ok(self.to_bytes(integer as u64, endianness))
     }
 }
@@ -0,0 +1,115 @@
+/// Seconds between the UNIX epoch and the FIT epoch 1989-12-31T00:00:00Z
+pub const FIT_EPOCH_OFFSET: u32 = 631065600;
+
+/// Values of `date_time` and `local_date_time` below this limit are no absolute times
+/// but seconds since the device was powered on (system time), e.g. before the device had a GPS fix.
+/// Those values are kept as numbers.
+pub const SYSTEM_TIME_LIMIT: u32 = 0x10000000;
+
+/// Formats a `date_time` value as RFC 3339 string in UTC, e.g. `2024-02-29T10:50:54Z`.
+/// Returns `None` for system time values, see [SYSTEM_TIME_LIMIT].
+pub fn date_time_to_rfc3339(date_time: u32) -> Option<String> {
+    format_fit_time(date_time).map(|formatted| format!("{}Z", formatted))
+}
+
+/// Formats a `local_date_time` value as RFC 3339 string without offset, e.g. `2024-02-29T11:50:54`.
+/// Returns `None` for system time values, see [SYSTEM_TIME_LIMIT].
+pub fn local_date_time_to_rfc3339(local_date_time: u32) -> Option<String> {
+    format_fit_time(local_date_time)
+}
+
+/// seconds since the UNIX epoch, `None` for system time values
+pub fn to_unix_timestamp(date_time: u32) -> Option<u64> {
+    if date_time < SYSTEM_TIME_LIMIT {
+        return None;
+    }
+    Some(u64::from(date_time) + u64::from(FIT_EPOCH_OFFSET))
+}
+
+fn format_fit_time(fit_time: u32) -> Option<String> {
+    let unix_timestamp = to_unix_timestamp(fit_time)?;
+    let (year, month, day) = civil_from_days(unix_timestamp / 86400);
+    let seconds_of_day = unix_timestamp % 86400;
+    Some(format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        seconds_of_day / 3600,
+        seconds_of_day % 3600 / 60,
+        seconds_of_day % 60
+    ))
+}
+
+/// converts days since the UNIX epoch into year, month and day of the proleptic Gregorian calendar
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    // shift the epoch to 0000-03-01, so that the leap day is the last day of the year
+    let days = days + 719468;
+    let era = days / 146097;
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    };
+    let year = year_of_era + era * 400;
+    (if month <= 2 { year + 1 } else { year }, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn starts_at_the_fit_epoch() {
+        assert_eq!(
+            civil_from_days(u64::from(FIT_EPOCH_OFFSET) / 86400),
+            (1989, 12, 31)
+        );
+        assert_eq!(
+            to_unix_timestamp(SYSTEM_TIME_LIMIT),
+            Some(u64::from(SYSTEM_TIME_LIMIT) + 631065600)
+        );
+    }
+
+    #[test]
+    fn formats_leap_days() {
+        assert_eq!(
+            date_time_to_rfc3339(1078138254),
+            Some("2024-02-29T10:50:54Z".to_string())
+        );
+        // 2000 is a leap year, 2100 is none
+        assert_eq!(
+            date_time_to_rfc3339(320716800),
+            Some("2000-02-29T00:00:00Z".to_string())
+        );
+        assert_eq!(
+            date_time_to_rfc3339(3476476800 - 1),
+            Some("2100-02-28T23:59:59Z".to_string())
+        );
+    }
+
+    #[test]
+    fn keeps_system_times_below_the_limit() {
+        assert_eq!(to_unix_timestamp(SYSTEM_TIME_LIMIT - 1), None);
+        assert_eq!(date_time_to_rfc3339(SYSTEM_TIME_LIMIT - 1), None);
+        assert_eq!(local_date_time_to_rfc3339(0), None);
+        assert_eq!(
+            date_time_to_rfc3339(SYSTEM_TIME_LIMIT),
+            Some("1998-07-03T21:24:16Z".to_string())
+        );
+    }
+
+    #[test]
+    fn formats_local_date_times_without_offset() {
+        assert_eq!(
+            local_date_time_to_rfc3339(1078138254 + 3600),
+            Some("2024-02-29T11:50:54".to_string())
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::io;
+
+/// Errors that can occur while parsing a FIT file.
+/// Every error carries the byte offset in the parsed buffer where the problem was detected.
+#[derive(Debug)]
+pub enum FitError {
+    /// the buffer ends before the data announced by the header or a definition
+    UnexpectedEndOfFile { offset: usize, context: String },
+    /// the file header is malformed or does not describe a FIT file
+    InvalidHeader { offset: usize, context: String },
+    /// a field definition references a base type that is not defined by the FIT protocol
+    UnknownBaseType { offset: usize, base_type: u8 },
+    /// a data message references a local message type that was not defined before
+    UndefinedLocalMessage {
+        offset: usize,
+        local_message_number: u8,
+    },
+    /// a `field_description` message misses fields or has unexpected values
+    InvalidFieldDescription { offset: usize, context: String },
+    /// a row of a CSV file in the layout of the FIT SDK's `FitCSVTool` cannot be read,
+    /// the offset is the position of the row in the CSV file
+    InvalidCsv { offset: usize, context: String },
+    /// the JSON of raw messages cannot be read, the offset is the position of the JSON value
+    /// that contains the message
+    InvalidJson { offset: usize, context: String },
+    /// a message cannot be encoded, the offset is the position in the encoded file
+    InvalidMessage { offset: usize, context: String },
+    /// reading from the underlying reader failed
+    Io { offset: usize, error: io::Error },
+}
+
+impl FitError {
+    /// byte offset in the parsed buffer
+    pub fn offset(&self) -> usize {
+        match self {
+            FitError::UnexpectedEndOfFile { offset, .. }
+            | FitError::InvalidHeader { offset, .. }
+            | FitError::UnknownBaseType { offset, .. }
+            | FitError::UndefinedLocalMessage { offset, .. }
+            | FitError::InvalidFieldDescription { offset, .. }
+            | FitError::InvalidCsv { offset, .. }
+            | FitError::InvalidJson { offset, .. }
+            | FitError::InvalidMessage { offset, .. }
+            | FitError::Io { offset, .. } => *offset,
+        }
+    }
+
+    /// moves the offset by `base`, used to report offsets of chained FIT files relative to the whole buffer
+    pub(crate) fn shifted(mut self, base: usize) -> FitError {
+        match &mut self {
+            FitError::UnexpectedEndOfFile { offset, .. }
+            | FitError::InvalidHeader { offset, .. }
+            | FitError::UnknownBaseType { offset, .. }
+            | FitError::UndefinedLocalMessage { offset, .. }
+            | FitError::InvalidFieldDescription { offset, .. }
+            | FitError::InvalidCsv { offset, .. }
+            | FitError::InvalidJson { offset, .. }
+            | FitError::InvalidMessage { offset, .. }
+            | FitError::Io { offset, .. } => *offset += base,
+        }
+        self
+    }
+}
+
+impl Display for FitError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            FitError::UnexpectedEndOfFile { offset, context } => {
+                write!(
+                    f,
+                    "unexpected end of file at byte {} reading {}",
+                    offset, context
+                )
+            }
+            FitError::InvalidHeader { offset, context } => {
+                write!(f, "invalid header at byte {}: {}", offset, context)
+            }
+            FitError::UnknownBaseType { offset, base_type } => {
+                write!(f, "unknown base type {} at byte {}", base_type, offset)
+            }
+            FitError::UndefinedLocalMessage {
+                offset,
+                local_message_number,
+            } => write!(
+                f,
+                "data message at byte {} references undefined local message {}",
+                offset, local_message_number
+            ),
+            FitError::InvalidFieldDescription { offset, context } => {
+                write!(
+                    f,
+                    "invalid field description at byte {}: {}",
+                    offset, context
+                )
+            }
+            FitError::InvalidCsv { offset, context } => {
+                write!(f, "invalid CSV row at byte {}: {}", offset, context)
+            }
+            FitError::InvalidJson { offset, context } => {
+                write!(f, "invalid JSON at byte {}: {}", offset, context)
+            }
+            FitError::InvalidMessage { offset, context } => {
+                write!(
+                    f,
+                    "message at byte {} cannot be encoded: {}",
+                    offset, context
+                )
+            }
+            FitError::Io { offset, error } => write!(f, "I/O error at byte {}: {}", offset, error),
+        }
+    }
+}
+
+impl Error for FitError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FitError::Io { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// returns `length` bytes starting at `position` or an [FitError::UnexpectedEndOfFile]
+pub(crate) fn read_bytes<'a>(
+    buffer: &'a [u8],
+    position: usize,
+    length: usize,
+    context: &str,
+) -> Result<&'a [u8], FitError> {
+    buffer
+        .get(position..position + length)
+        .ok_or_else(|| FitError::UnexpectedEndOfFile {
+            offset: position,
+            context: context.to_string(),
+        })
+}
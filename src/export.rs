@@ -0,0 +1,117 @@
+pub mod csv;
+pub mod fit_csv;
+pub mod geojson;
+pub mod gpx;
+pub mod tcx;
+
+use crate::data_types::Value;
+use crate::fit_file::fields::{Field, ValueField};
+use crate::fit_file::message::Messages;
+use crate::fit_file::FitFile;
+
+// Exporters expect the values with scale and offset of the profile applied, as decoded by default.
+// Positions are accepted in semicircles as well as in degrees.
+
+/// data of all messages of a message type, in the order of the file
+fn messages_of(fit_file: &FitFile, message_number: u16) -> impl Iterator<Item = &Messages> {
+    fit_file
+        .messages
+        .iter()
+        .filter(move |message| message.message_type.number == message_number)
+        .map(|message| &message.data)
+}
+
+/// index of the last period (session or lap) that started before the timestamp, `0` if there is none
+fn period_index(start_times: &[Option<u32>], timestamp: u32) -> usize {
+    start_times
+        .iter()
+        .rposition(|start_time| start_time.is_some_and(|start| start <= timestamp))
+        .unwrap_or(0)
+}
+
+/// escapes the characters that must not appear in XML text and attribute values
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            character => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+/// quotes CSV cells that contain separators, quotes or line breaks
+fn escape_csv(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+fn number(message: &Messages, field_name: &str) -> Option<f64> {
+    message.value(field_name)?.as_f64()
+}
+
+/// `date_time` value of the field as seconds since the FIT epoch
+fn date_time(message: &Messages, field_name: &str) -> Option<u32> {
+    number(message, field_name).map(|date_time| date_time as u32)
+}
+
+/// position in decimal degrees
+fn degrees(message: &Messages, field_name: &str) -> Option<f64> {
+    let field_value = message.field_value(field_name)?;
+    let in_degrees = matches!(
+        &field_value.field,
+        Field::ValueField(ValueField { scale: Some(scale), .. }) if scale.units == "degrees"
+    );
+    if in_degrees {
+        field_value.value.as_f64()
+    } else {
+        field_value.value.semicircles_to_degrees().as_f64()
+    }
+}
+
+/// name of the enum value as defined by the profile
+fn enum_name(message: &Messages, field_name: &str) -> Option<String> {
+    let field_value = message.field_value(field_name)?;
+    let Field::EnumField(enum_field) = &field_value.field else {
+        return None;
+    };
+    let number = match &field_value.value {
+        Value::EnumValue(values) => u32::from(*values.first()?),
+        value => value.as_f64()? as u32,
+    };
+    enum_field.translate(&number)
+}
+
+fn text(message: &Messages, field_name: &str) -> Option<String> {
+    match message.value(field_name)? {
+        Value::StringValue(text) if !text.is_empty() => Some(text.clone()),
+        _ => None,
+    }
+}
+
+/// FIT file of the messages as decoded by default, for the tests of the exporters
+#[cfg(test)]
+fn decode(messages: &[crate::fit_encoder::EncodeMessage]) -> FitFile {
+    use crate::fit_encoder::{encode, FitEncoderConfig};
+    use crate::fit_file::FitFileConfig;
+
+    let file = encode(messages, FitEncoderConfig::default()).unwrap();
+    FitFile::from(&file, &FitFileConfig::default()).unwrap()
+}
+
+/// message of the profile with fields by their profile name, for the tests of the exporters
+#[cfg(test)]
+fn message(name: &str, fields: &[(&str, Value)]) -> crate::fit_encoder::EncodeMessage {
+    fields.iter().fold(
+        crate::fit_encoder::EncodeMessage::by_name(name).unwrap(),
+        |message, (field, value)| message.named_field(field, value.clone()).unwrap(),
+    )
+}
@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+
+use crate::export::escape_csv;
+use crate::fit_file::message::{FieldValue, Message, OutputValue};
+use crate::fit_file::FitFile;
+
+/// Exports all messages of a message type (as named by `summary`) as CSV with one row per message.
+///
+/// The columns are the fields of all messages of the type in the order they first appear,
+/// named and translated like in the JSON output, e.g. `altitude_m` or `sport` with `Running`.
+/// Arrays get one column per element, e.g. `time_in_hr_zone_s[0]`, as many as the longest array has.
+/// Fields that a message does not contain are empty.
+pub fn to_csv(fit_file: &FitFile, message_type: &str) -> String {
+    let messages = fit_file
+        .messages
+        .iter()
+        .filter(|message| message.display_name() == message_type)
+        .collect::<Vec<&Message>>();
+    if messages.is_empty() {
+        return String::new();
+    }
+
+    let rows = messages
+        .iter()
+        .map(|message| row(message))
+        .collect::<Vec<HashMap<String, Vec<String>>>>();
+    // columns in order of appearance with the maximal number of elements
+    let mut columns: Vec<(String, usize)> = vec![];
+    for (message, row) in messages.iter().zip(&rows) {
+        for entry in &message.data.data {
+            let key = key(entry);
+            let width = row.get(&key).map_or(1, |cells| cells.len().max(1));
+            match columns.iter_mut().find(|(column, _)| *column == key) {
+                Some((_, column_width)) => *column_width = (*column_width).max(width),
+                None => columns.push((key, width)),
+            }
+        }
+    }
+
+    let mut csv = String::new();
+    let header = columns
+        .iter()
+        .flat_map(|(key, width)| match width {
+            1 => vec![key.clone()],
+            _ => (0..*width)
+                .map(|index| format!("{}[{}]", key, index))
+                .collect(),
+        })
+        .map(|name| escape_csv(&name))
+        .collect::<Vec<String>>();
+    csv.push_str(&header.join(","));
+    csv.push('\n');
+    for row in &rows {
+        let cells = columns
+            .iter()
+            .flat_map(|(key, width)| {
+                let cells = row.get(key);
+                (0..*width).map(move |index| {
+                    cells
+                        .and_then(|cells| cells.get(index))
+                        .map_or(String::new(), |cell| escape_csv(cell))
+                })
+            })
+            .collect::<Vec<String>>();
+        csv.push_str(&cells.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Exports every message type of the file as CSV, see [to_csv].
+/// Returns the message types in the order they first appear with their CSV.
+pub fn to_csv_per_message_type(fit_file: &FitFile) -> Vec<(String, String)> {
+    let mut message_types: Vec<String> = vec![];
+    for message in &fit_file.messages {
+        let message_type = message.display_name();
+        if !message_types.contains(&message_type) {
+            message_types.push(message_type);
+        }
+    }
+    message_types
+        .into_iter()
+        .map(|message_type| {
+            let csv = to_csv(fit_file, &message_type);
+            (message_type, csv)
+        })
+        .collect()
+}
+
+/// name of the column as the key of the JSON output
+fn key(entry: &FieldValue) -> String {
+    match serde_json::to_value(&entry.field) {
+        Ok(serde_json::Value::String(key)) => key,
+        _ => String::new(),
+    }
+}
+
+/// cells of the fields of a message, arrays are flattened to one cell per element
+fn row(message: &Message) -> HashMap<String, Vec<String>> {
+    message
+        .data
+        .data
+        .iter()
+        .map(|entry| {
+            let cells = match serde_json::to_value(OutputValue(entry)) {
+                Ok(serde_json::Value::Array(elements)) => elements.iter().map(cell).collect(),
+                Ok(value) => vec![cell(&value)],
+                Err(_) => vec![],
+            };
+            (key(entry), cells)
+        })
+        .collect()
+}
+
+fn cell(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => String::new(),
+        serde_json::Value::String(text) => text.clone(),
+        value => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_types::Value;
+    use crate::export::{decode, message};
+
+    #[test]
+    fn writes_one_column_per_field_and_array_element() {
+        let fit_file = decode(&[
+            message(
+                "record",
+                &[
+                    ("heart_rate", Value::NumberValueU8(120)),
+                    ("speed_1s", Value::NumberValueVecU8(vec![16, 32])),
+                ],
+            ),
+            message(
+                "record",
+                &[
+                    ("cadence", Value::NumberValueU8(80)),
+                    ("speed_1s", Value::NumberValueVecU8(vec![48, 64, 80])),
+                    ("heart_rate", Value::NumberValueU8(121)),
+                ],
+            ),
+        ]);
+        assert_eq!(
+            to_csv(&fit_file, "Record"),
+            concat!(
+                "heart_rate_bpm,speed_1s_m_per_s[0],speed_1s_m_per_s[1],speed_1s_m_per_s[2],cadence_rpm\n",
+                "120,1.0,2.0,,\n",
+                "121,3.0,4.0,5.0,80\n"
+            )
+        );
+        assert_eq!(to_csv(&fit_file, "Lap"), "");
+    }
+
+    #[test]
+    fn exports_every_message_type_with_quoted_cells() {
+        let fit_file = decode(&[
+            message("record", &[("heart_rate", Value::NumberValueU8(120))]),
+            message(
+                "course",
+                &[("name", Value::StringValue("Loop, \"north\"".to_string()))],
+            ),
+            message("record", &[("heart_rate", Value::NumberValueU8(121))]),
+        ]);
+        assert_eq!(
+            to_csv_per_message_type(&fit_file),
+            vec![
+                (
+                    "Record".to_string(),
+                    "heart_rate_bpm\n120\n121\n".to_string()
+                ),
+                (
+                    "Course".to_string(),
+                    "name\n\"Loop, \"\"north\"\"\"\n".to_string()
+                ),
+            ]
+        );
+    }
+}
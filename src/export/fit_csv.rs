@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::data_types::{BaseType, Value};
+use crate::export::escape_csv;
+use crate::fit_file::fields::Field;
+use crate::fit_file::message::{FieldValue, Message};
+use crate::fit_file::message_types::MessageDefinition;
+use crate::fit_file::FitFile;
+
+/// Exports all definitions and data messages in the layout of the FIT SDK's `FitCSVTool`.
+///
+/// Every row starts with `Type,Local Number,Message` followed by triplets of field name, value and units.
+/// A `Definition` row lists the fields and their number of elements and is written before the first
+/// data message that uses it, `Data` rows contain the decoded values:
+/// enums by their profile name in snake case, date times as seconds since the FIT epoch with the units `s`
+/// and the elements of arrays separated by `|`.
+/// Fields and message types that are unknown to the profile are named `unknown`, developer fields
+/// only appear in the data rows.
+///
+/// The file should be decoded with scale and offset applied and the positions in semicircles,
+/// like the FIT SDK does.
+pub fn to_fit_csv(fit_file: &FitFile) -> String {
+    let mut rows: Vec<Vec<String>> = vec![];
+    let mut definitions: HashMap<u8, &Arc<MessageDefinition>> = HashMap::new();
+    for message in &fit_file.messages {
+        if let Some(definition) = &message.definition {
+            let local_message_number = message.local_message_number;
+            let defined = definitions
+                .get(&local_message_number)
+                .is_some_and(|defined| Arc::ptr_eq(defined, definition));
+            if !defined {
+                rows.push(definition_row(message, definition));
+                definitions.insert(local_message_number, definition);
+            }
+        }
+        rows.push(data_row(message));
+    }
+
+    let field_count = rows
+        .iter()
+        .map(|row| (row.len() - 3) / 3)
+        .max()
+        .unwrap_or(0);
+    let mut csv = String::from("Type,Local Number,Message,");
+    for field_index in 1..=field_count {
+        csv.push_str(&format!("Field {0},Value {0},Units {0},", field_index));
+    }
+    csv.push('\n');
+    for row in rows {
+        for cell in row {
+            csv.push_str(&cell);
+            csv.push(',');
+        }
+        csv.push('\n');
+    }
+    csv
+}
+
+fn definition_row(message: &Message, definition: &MessageDefinition) -> Vec<String> {
+    let mut row = vec![
+        "Definition".to_string(),
+        message.local_message_number.to_string(),
+        definition.message_type.profile_name(),
+    ];
+    for field_definition in &definition.fields {
+        if field_definition.field == Field::DeveloperField {
+            continue;
+        }
+        let read_size = BaseType::parse(&field_definition.base_type_value_or_dev_index)
+            .map_or(1, |base_type| base_type.read_size);
+        row.push(field_name(&field_definition.field));
+        row.push((field_definition.size as usize / read_size).to_string());
+        row.push(String::new());
+    }
+    row
+}
+
+fn data_row(message: &Message) -> Vec<String> {
+    let mut row = vec![
+        "Data".to_string(),
+        message.local_message_number.to_string(),
+        message.message_type.profile_name(),
+    ];
+    for entry in &message.data.data {
+        let (value, units) = value_and_units(entry);
+        row.push(field_name(&entry.field));
+        row.push(format!("\"{}\"", value.replace('"', "\"\"")));
+        row.push(escape_csv(&units));
+    }
+    row
+}
+
+fn field_name(field: &Field) -> String {
+    field.name().unwrap_or("unknown").to_string()
+}
+
+fn value_and_units(entry: &FieldValue) -> (String, String) {
+    match &entry.field {
+        Field::EnumField(enum_field) => {
+            let numbers: Vec<u32> = match &entry.value {
+                Value::EnumValue(values) => values.iter().map(|&value| u32::from(value)).collect(),
+                value => value
+                    .as_f64()
+                    .map(|number| vec![number])
+                    .or_else(|| value.as_f64_vec())
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|number| number as u32)
+                    .collect(),
+            };
+            if enum_field.is_date_time() {
+                return (join(numbers.iter()), "s".to_string());
+            }
+            if enum_field.is_bitfield() {
+                return (join(numbers.iter()), String::new());
+            }
+            let names = numbers.iter().map(|number| {
+                enum_field
+                    .profile_name(number)
+                    .unwrap_or_else(|| number.to_string())
+            });
+            (join(names), String::new())
+        }
+        Field::ValueField(value_field) => (
+            format_value(&entry.value),
+            value_field
+                .scale
+                .as_ref()
+                .map(|scale| scale.units.clone())
+                .unwrap_or_default(),
+        ),
+        Field::Unknown(_) | Field::DeveloperField => (format_value(&entry.value), String::new()),
+    }
+}
+
+/// the value as written by `FitCSVTool`, elements of arrays are separated by `|`
+fn format_value(value: &Value) -> String {
+    match value {
+        Value::EnumValue(values) => join(values.iter()),
+        Value::NumberValueS8(value) => value.to_string(),
+        Value::NumberValueU8(value) => value.to_string(),
+        Value::NumberValueS16(value) => value.to_string(),
+        Value::NumberValueU16(value) => value.to_string(),
+        Value::NumberValueS32(value) => value.to_string(),
+        Value::NumberValueU32(value) => value.to_string(),
+        Value::StringValue(value) => value.clone(),
+        Value::NumberValueS64(value) => value.to_string(),
+        Value::NumberValueU64(value) => value.to_string(),
+        Value::NumberValueF32(value) => value.to_string(),
+        Value::NumberValueF64(value) => value.to_string(),
+        Value::NumberValueVecS8(values) => join(values.iter()),
+        Value::NumberValueVecU8(values) => join(values.iter()),
+        Value::NumberValueVecS16(values) => join(values.iter()),
+        Value::NumberValueVecU16(values) => join(values.iter()),
+        Value::NumberValueVecS32(values) => join(values.iter()),
+        Value::NumberValueVecU32(values) => join(values.iter()),
+        Value::NumberValueVecS64(values) => join(values.iter()),
+        Value::NumberValueVecU64(values) => join(values.iter()),
+        Value::NumberValueVecF32(values) => join(values.iter()),
+        Value::NumberValueVecF64(values) => join(values.iter()),
+        Value::Invalid => String::new(),
+    }
+}
+
+fn join<T: ToString>(elements: impl Iterator<Item = T>) -> String {
+    elements
+        .map(|element| element.to_string())
+        .collect::<Vec<String>>()
+        .join("|")
+}
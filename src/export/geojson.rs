@@ -0,0 +1,326 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use serde_json::{json, Map, Value};
+
+use crate::export::{date_time, degrees, messages_of, period_index};
+use crate::fit_file::message::Messages;
+use crate::fit_file::message_types::MessageType;
+use crate::fit_file::FitFile;
+
+const SESSION: u16 = 18;
+const LAP: u16 = 19;
+const RECORD: u16 = 20;
+const COURSE_POINT: u16 = 32;
+const SEGMENT_POINT: u16 = 150;
+
+/// Exports the track of an activity or course as GeoJSON `FeatureCollection`.
+///
+/// Every `Lap` becomes a `LineString` of the `Record` positions from its start time up to its timestamp,
+/// with the fields of the lap as properties. Files without laps use the sessions instead,
+/// files without both a single line of all records.
+/// `CoursePoint` and `SegmentPoint` messages and the start of every `Session` become `Point` features.
+/// Every feature has the property `message_type` with the message it was built from.
+///
+/// `max_vertices` simplifies every line to at most that many positions (but at least two),
+/// keeping the positions that deviate most from the simplified line (Douglas-Peucker).
+pub fn to_geojson(fit_file: &FitFile, max_vertices: Option<usize>) -> String {
+    let sessions = messages_of(fit_file, SESSION).collect::<Vec<&Messages>>();
+    let laps = messages_of(fit_file, LAP).collect::<Vec<&Messages>>();
+    // without laps the sessions are the laps
+    let (laps, lap_message_number) = if laps.is_empty() {
+        (sessions.clone(), SESSION)
+    } else {
+        (laps, LAP)
+    };
+    let lap_start_times = laps
+        .iter()
+        .map(|lap| date_time(lap, "start_time"))
+        .collect::<Vec<Option<u32>>>();
+
+    let mut lap_positions: Vec<Vec<[f64; 2]>> = vec![vec![]; laps.len().max(1)];
+    for record in messages_of(fit_file, RECORD) {
+        let (Some(lat), Some(lon)) = (
+            degrees(record, "position_lat"),
+            degrees(record, "position_long"),
+        ) else {
+            continue;
+        };
+        let lap_index = match date_time(record, "timestamp") {
+            Some(timestamp) => {
+                let lap_index = period_index(&lap_start_times, timestamp);
+                let lap_end = laps
+                    .get(lap_index)
+                    .and_then(|lap| date_time(lap, "timestamp"));
+                if lap_end.is_some_and(|lap_end| timestamp > lap_end) {
+                    continue;
+                }
+                lap_index
+            }
+            None if laps.len() <= 1 => 0,
+            None => continue,
+        };
+        lap_positions[lap_index].push([lon, lat]);
+    }
+
+    let mut features = vec![];
+    for (lap_index, positions) in lap_positions.into_iter().enumerate() {
+        let positions = match max_vertices {
+            Some(max_vertices) => simplify(&positions, max_vertices),
+            None => positions,
+        };
+        if positions.len() < 2 {
+            continue;
+        }
+        let (message_number, properties) = match laps.get(lap_index) {
+            Some(lap) => (lap_message_number, Some(*lap)),
+            None => (RECORD, None),
+        };
+        features.push(feature(
+            json!({"type": "LineString", "coordinates": positions}),
+            message_number,
+            properties,
+        ));
+    }
+    for session in &sessions {
+        if let Some(position) = session_start(fit_file, session) {
+            features.push(point(position, SESSION, session));
+        }
+    }
+    for message_number in [COURSE_POINT, SEGMENT_POINT] {
+        for message in messages_of(fit_file, message_number) {
+            if let (Some(lat), Some(lon)) = (
+                degrees(message, "position_lat"),
+                degrees(message, "position_long"),
+            ) {
+                features.push(point([lon, lat], message_number, message));
+            }
+        }
+    }
+
+    json!({"type": "FeatureCollection", "features": features}).to_string()
+}
+
+fn feature(geometry: Value, message_number: u16, message: Option<&Messages>) -> Value {
+    let mut properties = match message.map(serde_json::to_value) {
+        Some(Ok(Value::Object(properties))) => properties,
+        _ => Map::new(),
+    };
+    let message_type = MessageType::resolve(message_number, None);
+    properties.insert("message_type".to_string(), json!(message_type.name));
+    json!({"type": "Feature", "geometry": geometry, "properties": properties})
+}
+
+fn point(position: [f64; 2], message_number: u16, message: &Messages) -> Value {
+    feature(
+        json!({"type": "Point", "coordinates": position}),
+        message_number,
+        Some(message),
+    )
+}
+
+/// start position of the session, the first record position from its start time on if it has none
+fn session_start(fit_file: &FitFile, session: &Messages) -> Option<[f64; 2]> {
+    if let (Some(lat), Some(lon)) = (
+        degrees(session, "start_position_lat"),
+        degrees(session, "start_position_long"),
+    ) {
+        return Some([lon, lat]);
+    }
+    let start_time = date_time(session, "start_time")?;
+    messages_of(fit_file, RECORD)
+        .filter(|record| date_time(record, "timestamp").is_some_and(|time| time >= start_time))
+        .find_map(|record| {
+            Some([
+                degrees(record, "position_long")?,
+                degrees(record, "position_lat")?,
+            ])
+        })
+}
+
+/// segment of the line between two kept positions with its farthest position in between
+struct Segment {
+    start: usize,
+    end: usize,
+    farthest: usize,
+    distance: f64,
+}
+
+impl PartialEq for Segment {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Segment {}
+
+impl PartialOrd for Segment {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Segment {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance.total_cmp(&other.distance)
+    }
+}
+
+impl Segment {
+    /// `None` if there are no positions between start and end
+    fn from(positions: &[[f64; 2]], start: usize, end: usize) -> Option<Segment> {
+        (start + 1..end)
+            .map(|index| {
+                let distance = distance_to_line(positions[index], positions[start], positions[end]);
+                (index, distance)
+            })
+            .max_by(|(_, distance), (_, other)| distance.total_cmp(other))
+            .map(|(farthest, distance)| Segment {
+                start,
+                end,
+                farthest,
+                distance,
+            })
+    }
+}
+
+/// keeps the first and last position and adds the farthest positions until `max_vertices` are reached
+fn simplify(positions: &[[f64; 2]], max_vertices: usize) -> Vec<[f64; 2]> {
+    let max_vertices = max_vertices.max(2);
+    if positions.len() <= max_vertices {
+        return positions.to_vec();
+    }
+    let mut kept = vec![false; positions.len()];
+    kept[0] = true;
+    kept[positions.len() - 1] = true;
+    let mut kept_count = 2;
+    let mut segments = BinaryHeap::new();
+    segments.extend(Segment::from(positions, 0, positions.len() - 1));
+    while kept_count < max_vertices {
+        let Some(segment) = segments.pop() else {
+            break;
+        };
+        kept[segment.farthest] = true;
+        kept_count += 1;
+        segments.extend(Segment::from(positions, segment.start, segment.farthest));
+        segments.extend(Segment::from(positions, segment.farthest, segment.end));
+    }
+    positions
+        .iter()
+        .zip(kept)
+        .filter(|(_, kept)| *kept)
+        .map(|(position, _)| *position)
+        .collect()
+}
+
+/// distance of a position to the line through start and end, in degrees
+fn distance_to_line(position: [f64; 2], start: [f64; 2], end: [f64; 2]) -> f64 {
+    let [x, y] = position;
+    let [x1, y1] = start;
+    let [x2, y2] = end;
+    let length = (x2 - x1).hypot(y2 - y1);
+    if length == 0.0 {
+        return (x - x1).hypot(y - y1);
+    }
+    ((x2 - x1) * (y1 - y) - (x1 - x) * (y2 - y1)).abs() / length
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_types::Value as FitValue;
+    use crate::export::{decode, message};
+    use crate::fit_encoder::EncodeMessage;
+
+    /// 2021-09-08T01:46:40Z
+    const START: u32 = 1_000_000_000;
+
+    fn lap(start_time: u32, timestamp: u32) -> EncodeMessage {
+        message(
+            "lap",
+            &[
+                ("start_time", FitValue::NumberValueU32(start_time)),
+                ("timestamp", FitValue::NumberValueU32(timestamp)),
+            ],
+        )
+    }
+
+    fn record(timestamp: u32, lat: i32) -> EncodeMessage {
+        message(
+            "record",
+            &[
+                ("timestamp", FitValue::NumberValueU32(timestamp)),
+                ("position_lat", FitValue::NumberValueS32(lat)),
+                ("position_long", FitValue::NumberValueS32(1 << 20)),
+            ],
+        )
+    }
+
+    #[test]
+    fn assigns_records_to_the_laps_from_their_start_time_to_their_timestamp() {
+        let geojson = to_geojson(
+            &decode(&[
+                lap(START, START + 4),
+                lap(START + 10, START + 14),
+                record(START, 1 << 20),
+                record(START + 4, 2 << 20),
+                // between the laps
+                record(START + 7, 3 << 20),
+                record(START + 10, 4 << 20),
+                record(START + 12, 5 << 20),
+                record(START + 14, 6 << 20),
+            ]),
+            None,
+        );
+        let geojson: Value = serde_json::from_str(&geojson).unwrap();
+        let lines = geojson["features"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|feature| {
+                (
+                    feature["properties"]["message_type"].clone(),
+                    feature["geometry"]["coordinates"].as_array().unwrap().len(),
+                )
+            })
+            .collect::<Vec<(Value, usize)>>();
+        assert_eq!(lines, vec![(json!("Lap"), 2), (json!("Lap"), 3)]);
+    }
+
+    #[test]
+    fn simplifies_to_the_farthest_positions_keeping_first_and_last() {
+        let positions = [
+            [0.0, 0.0],
+            [1.0, 0.1],
+            [2.0, 3.0],
+            [3.0, 0.2],
+            [4.0, -1.0],
+            [5.0, 0.0],
+        ];
+        assert_eq!(
+            simplify(&positions, 3),
+            vec![[0.0, 0.0], [2.0, 3.0], [5.0, 0.0]]
+        );
+        assert_eq!(
+            simplify(&positions, 4),
+            vec![[0.0, 0.0], [2.0, 3.0], [4.0, -1.0], [5.0, 0.0]]
+        );
+        assert_eq!(simplify(&positions, 0), vec![[0.0, 0.0], [5.0, 0.0]]);
+        assert_eq!(simplify(&positions, 6), positions.to_vec());
+    }
+
+    #[test]
+    fn limits_the_lines_to_max_vertices() {
+        let records = (0..10)
+            .map(|second| record(START + second, (second as i32 % 3) << 20))
+            .collect::<Vec<EncodeMessage>>();
+        let geojson = to_geojson(&decode(&records), Some(4));
+        let geojson: Value = serde_json::from_str(&geojson).unwrap();
+        let coordinates = geojson["features"][0]["geometry"]["coordinates"]
+            .as_array()
+            .unwrap();
+        assert_eq!(coordinates.len(), 4);
+        assert_eq!(coordinates[0], json!([0.087890625, 0.0]));
+        assert_eq!(coordinates[3], json!([0.087890625, 0.0]));
+    }
+}
@@ -0,0 +1,228 @@
+use std::fmt::Write;
+
+use crate::date_time::date_time_to_rfc3339;
+use crate::export::{
+    date_time, degrees, enum_name, escape_xml, messages_of, number, period_index, text,
+};
+use crate::fit_file::message::Messages;
+use crate::fit_file::FitFile;
+
+const FILE_ID: u16 = 0;
+const SESSION: u16 = 18;
+const RECORD: u16 = 20;
+const COURSE: u16 = 31;
+const COURSE_POINT: u16 = 32;
+
+/// Exports the `Record` messages of an activity or course as GPX 1.1 track.
+///
+/// Every `Session` becomes a track segment with the records from its start time on,
+/// files without sessions (like courses) get a single segment.
+/// Heart rate, cadence and temperature are written as Garmin `TrackPointExtension`,
+/// `CoursePoint` messages become waypoints. Records without position are skipped.
+pub fn to_gpx(fit_file: &FitFile) -> String {
+    let session_start_times = messages_of(fit_file, SESSION)
+        .map(|session| date_time(session, "start_time"))
+        .collect::<Vec<Option<u32>>>();
+
+    let mut segments: Vec<Vec<&Messages>> = vec![vec![]; session_start_times.len().max(1)];
+    let mut segment = 0;
+    for record in messages_of(fit_file, RECORD) {
+        if let Some(timestamp) = date_time(record, "timestamp") {
+            segment = period_index(&session_start_times, timestamp);
+        }
+        if degrees(record, "position_lat").is_some() && degrees(record, "position_long").is_some() {
+            segments[segment].push(record);
+        }
+    }
+
+    let mut gpx = String::new();
+    writeln!(gpx, r#"<?xml version="1.0" encoding="UTF-8"?>"#).unwrap();
+    writeln!(
+        gpx,
+        concat!(
+            r#"<gpx version="1.1" creator="rusty-but-fit" xmlns="http://www.topografix.com/GPX/1/1""#,
+            r#" xmlns:gpxtpx="http://www.garmin.com/xmlschemas/TrackPointExtension/v1""#,
+            r#" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance""#,
+            r#" xsi:schemaLocation="http://www.topografix.com/GPX/1/1 http://www.topografix.com/GPX/1/1/gpx.xsd"#,
+            r#" http://www.garmin.com/xmlschemas/TrackPointExtension/v1 http://www8.garmin.com/xmlschemas/TrackPointExtensionv1.xsd">"#
+        )
+    )
+    .unwrap();
+    if let Some(time_created) = messages_of(fit_file, FILE_ID)
+        .find_map(|file_id| date_time(file_id, "time_created"))
+        .and_then(date_time_to_rfc3339)
+    {
+        writeln!(gpx, "  <metadata>").unwrap();
+        writeln!(gpx, "    <time>{}</time>", time_created).unwrap();
+        writeln!(gpx, "  </metadata>").unwrap();
+    }
+    for course_point in messages_of(fit_file, COURSE_POINT) {
+        write_waypoint(&mut gpx, course_point);
+    }
+    if segments.iter().any(|segment| !segment.is_empty()) {
+        writeln!(gpx, "  <trk>").unwrap();
+        if let Some(name) = messages_of(fit_file, COURSE).find_map(|course| text(course, "name")) {
+            writeln!(gpx, "    <name>{}</name>", escape_xml(&name)).unwrap();
+        }
+        if let Some(sport) = messages_of(fit_file, SESSION)
+            .chain(messages_of(fit_file, COURSE))
+            .find_map(|message| enum_name(message, "sport"))
+        {
+            writeln!(gpx, "    <type>{}</type>", escape_xml(&sport)).unwrap();
+        }
+        for segment in segments.iter().filter(|segment| !segment.is_empty()) {
+            writeln!(gpx, "    <trkseg>").unwrap();
+            for record in segment {
+                write_track_point(&mut gpx, record);
+            }
+            writeln!(gpx, "    </trkseg>").unwrap();
+        }
+        writeln!(gpx, "  </trk>").unwrap();
+    }
+    writeln!(gpx, "</gpx>").unwrap();
+    gpx
+}
+
+fn write_waypoint(gpx: &mut String, course_point: &Messages) {
+    let (Some(lat), Some(lon)) = (
+        degrees(course_point, "position_lat"),
+        degrees(course_point, "position_long"),
+    ) else {
+        return;
+    };
+    writeln!(gpx, r#"  <wpt lat="{}" lon="{}">"#, lat, lon).unwrap();
+    if let Some(time) = date_time(course_point, "timestamp").and_then(date_time_to_rfc3339) {
+        writeln!(gpx, "    <time>{}</time>", time).unwrap();
+    }
+    if let Some(name) = text(course_point, "name") {
+        writeln!(gpx, "    <name>{}</name>", escape_xml(&name)).unwrap();
+    }
+    if let Some(point_type) = enum_name(course_point, "type") {
+        writeln!(gpx, "    <type>{}</type>", escape_xml(&point_type)).unwrap();
+    }
+    writeln!(gpx, "  </wpt>").unwrap();
+}
+
+fn write_track_point(gpx: &mut String, record: &Messages) {
+    let lat = degrees(record, "position_lat").unwrap_or_default();
+    let lon = degrees(record, "position_long").unwrap_or_default();
+    writeln!(gpx, r#"      <trkpt lat="{}" lon="{}">"#, lat, lon).unwrap();
+    if let Some(elevation) =
+        number(record, "enhanced_altitude").or_else(|| number(record, "altitude"))
+    {
+        writeln!(gpx, "        <ele>{}</ele>", elevation).unwrap();
+    }
+    if let Some(time) = date_time(record, "timestamp").and_then(date_time_to_rfc3339) {
+        writeln!(gpx, "        <time>{}</time>", time).unwrap();
+    }
+    // the order of the elements is defined by the TrackPointExtension schema
+    let extensions = [
+        ("atemp", number(record, "temperature")),
+        ("hr", number(record, "heart_rate")),
+        ("cad", number(record, "cadence")),
+    ];
+    if extensions.iter().any(|(_, value)| value.is_some()) {
+        writeln!(gpx, "        <extensions>").unwrap();
+        writeln!(gpx, "          <gpxtpx:TrackPointExtension>").unwrap();
+        for (element, value) in extensions {
+            if let Some(value) = value {
+                writeln!(
+                    gpx,
+                    "            <gpxtpx:{0}>{1}</gpxtpx:{0}>",
+                    element, value
+                )
+                .unwrap();
+            }
+        }
+        writeln!(gpx, "          </gpxtpx:TrackPointExtension>").unwrap();
+        writeln!(gpx, "        </extensions>").unwrap();
+    }
+    writeln!(gpx, "      </trkpt>").unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_types::Value;
+    use crate::export::{decode, message};
+    use crate::fit_encoder::EncodeMessage;
+
+    /// 2021-09-08T01:46:40Z
+    const START: u32 = 1_000_000_000;
+
+    fn session(start_time: u32) -> EncodeMessage {
+        message(
+            "session",
+            &[
+                ("start_time", Value::NumberValueU32(start_time)),
+                ("sport", Value::EnumValue(vec![1])),
+            ],
+        )
+    }
+
+    fn record(timestamp: u32, position: Option<(i32, i32)>) -> EncodeMessage {
+        let record = message("record", &[("timestamp", Value::NumberValueU32(timestamp))]);
+        match position {
+            Some((lat, long)) => record
+                .named_field("position_lat", Value::NumberValueS32(lat))
+                .unwrap()
+                .named_field("position_long", Value::NumberValueS32(long))
+                .unwrap(),
+            None => record,
+        }
+    }
+
+    #[test]
+    fn writes_track_point_elements_in_schema_order() {
+        let gpx = to_gpx(&decode(&[message(
+            "record",
+            &[
+                ("timestamp", Value::NumberValueU32(START)),
+                ("position_lat", Value::NumberValueS32(1 << 28)),
+                ("position_long", Value::NumberValueS32(-(1 << 29))),
+                ("altitude", Value::NumberValueU16(2_600)),
+                ("heart_rate", Value::NumberValueU8(121)),
+                ("cadence", Value::NumberValueU8(82)),
+                ("temperature", Value::NumberValueS8(-3)),
+            ],
+        )]));
+        let segment_start = gpx.find("    <trkseg>").unwrap();
+        let segment_end = gpx.find("  </trk>").unwrap();
+        assert_eq!(
+            &gpx[segment_start..segment_end],
+            r#"    <trkseg>
+      <trkpt lat="22.5" lon="-45">
+        <ele>20</ele>
+        <time>2021-09-08T01:46:40Z</time>
+        <extensions>
+          <gpxtpx:TrackPointExtension>
+            <gpxtpx:atemp>-3</gpxtpx:atemp>
+            <gpxtpx:hr>121</gpxtpx:hr>
+            <gpxtpx:cad>82</gpxtpx:cad>
+          </gpxtpx:TrackPointExtension>
+        </extensions>
+      </trkpt>
+    </trkseg>
+"#
+        );
+    }
+
+    #[test]
+    fn assigns_records_to_the_sessions_by_their_start_time() {
+        let gpx = to_gpx(&decode(&[
+            session(START),
+            session(START + 10),
+            record(START, Some((1 << 20, 1 << 20))),
+            record(START + 5, None),
+            record(START + 9, Some((2 << 20, 1 << 20))),
+            record(START + 10, Some((3 << 20, 1 << 20))),
+        ]));
+        // track points per segment, the record without position is skipped
+        let segments = gpx
+            .split("<trkseg>")
+            .skip(1)
+            .map(|segment| segment.matches("<trkpt ").count())
+            .collect::<Vec<usize>>();
+        assert_eq!(segments, vec![2, 1]);
+    }
+}
@@ -0,0 +1,464 @@
+use std::fmt::{Display, Write};
+
+use crate::date_time::date_time_to_rfc3339;
+use crate::export::{date_time, degrees, enum_name, messages_of, number, period_index};
+use crate::fit_file::message::Messages;
+use crate::fit_file::FitFile;
+
+const SESSION: u16 = 18;
+const LAP: u16 = 19;
+const RECORD: u16 = 20;
+
+/// Exports the sessions of an activity as TCX (Training Center Database v2).
+///
+/// Every `Session` becomes an `Activity` with its `Lap` messages,
+/// every lap gets the `Record` messages from its start time on as `Track`.
+/// The sport of an activity is `Running`, `Biking` (also e-biking and indoor cycling) or `Other`.
+/// Files without laps use the session totals as single lap.
+/// Lap intensities other than `Rest` are written as `Active`, as TCX has no warmup or cooldown.
+/// Speed, run cadence and power are written as `ActivityExtension/v2`, cadence of other sports as `Cadence`.
+pub fn to_tcx(fit_file: &FitFile) -> String {
+    let sessions = messages_of(fit_file, SESSION).collect::<Vec<&Messages>>();
+    let laps = messages_of(fit_file, LAP).collect::<Vec<&Messages>>();
+    let session_start_times = sessions
+        .iter()
+        .map(|session| date_time(session, "start_time"))
+        .collect::<Vec<Option<u32>>>();
+    // without laps the sessions are the laps
+    let (laps, lap_start_times) = if laps.is_empty() {
+        (sessions.clone(), session_start_times.clone())
+    } else {
+        let lap_start_times = laps
+            .iter()
+            .map(|lap| date_time(lap, "start_time"))
+            .collect::<Vec<Option<u32>>>();
+        (laps, lap_start_times)
+    };
+
+    let mut lap_records: Vec<Vec<&Messages>> = vec![vec![]; laps.len().max(1)];
+    for record in messages_of(fit_file, RECORD) {
+        if let Some(timestamp) = date_time(record, "timestamp") {
+            lap_records[period_index(&lap_start_times, timestamp)].push(record);
+        }
+    }
+    let mut session_laps: Vec<Vec<usize>> = vec![vec![]; sessions.len().max(1)];
+    for (lap_index, records) in lap_records.iter().enumerate() {
+        let start_time = lap_start_times
+            .get(lap_index)
+            .copied()
+            .flatten()
+            .or_else(|| {
+                records
+                    .first()
+                    .and_then(|record| date_time(record, "timestamp"))
+            });
+        let session_index = start_time
+            .map(|start_time| period_index(&session_start_times, start_time))
+            .unwrap_or(0);
+        session_laps[session_index].push(lap_index);
+    }
+
+    let mut tcx = String::new();
+    writeln!(tcx, r#"<?xml version="1.0" encoding="UTF-8"?>"#).unwrap();
+    writeln!(
+        tcx,
+        concat!(
+            r#"<TrainingCenterDatabase xmlns="http://www.garmin.com/xmlschemas/TrainingCenterDatabase/v2""#,
+            r#" xmlns:ns3="http://www.garmin.com/xmlschemas/ActivityExtension/v2""#,
+            r#" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance""#,
+            r#" xsi:schemaLocation="http://www.garmin.com/xmlschemas/TrainingCenterDatabase/v2 http://www.garmin.com/xmlschemas/TrainingCenterDatabasev2.xsd">"#
+        )
+    )
+    .unwrap();
+    writeln!(tcx, "  <Activities>").unwrap();
+    for (session_index, lap_indices) in session_laps.iter().enumerate() {
+        let session = sessions.get(session_index).copied();
+        let lap_start = |lap_index: usize| {
+            lap_start_times
+                .get(lap_index)
+                .copied()
+                .flatten()
+                .or_else(|| {
+                    lap_records[lap_index]
+                        .first()
+                        .and_then(|record| date_time(record, "timestamp"))
+                })
+        };
+        let Some(id) = session
+            .and_then(|session| date_time(session, "start_time"))
+            .or_else(|| {
+                lap_indices
+                    .iter()
+                    .find_map(|&lap_index| lap_start(lap_index))
+            })
+            .and_then(date_time_to_rfc3339)
+        else {
+            continue;
+        };
+        let tcx_sport = tcx_sport(
+            session.and_then(|session| enum_name(session, "sport")),
+            session.and_then(|session| enum_name(session, "sub_sport")),
+        );
+        let running = tcx_sport == "Running";
+        writeln!(tcx, r#"    <Activity Sport="{}">"#, tcx_sport).unwrap();
+        write_element(&mut tcx, 6, "Id", id);
+        for &lap_index in lap_indices {
+            let Some(start_time) = lap_start(lap_index).and_then(date_time_to_rfc3339) else {
+                continue;
+            };
+            write_lap(
+                &mut tcx,
+                laps.get(lap_index).copied(),
+                &start_time,
+                &lap_records[lap_index],
+                running,
+            );
+        }
+        writeln!(tcx, "    </Activity>").unwrap();
+    }
+    writeln!(tcx, "  </Activities>").unwrap();
+    writeln!(tcx, "</TrainingCenterDatabase>").unwrap();
+    tcx
+}
+
+/// TCX only knows `Running` and `Biking`, every other sport is `Other`
+fn tcx_sport(sport: Option<String>, sub_sport: Option<String>) -> &'static str {
+    match (sport.as_deref(), sub_sport.as_deref()) {
+        (Some("Running"), _) => "Running",
+        (Some("Cycling" | "EBiking"), _) => "Biking",
+        (Some("FitnessEquipment"), Some("Treadmill" | "IndoorRunning")) => "Running",
+        (
+            Some("FitnessEquipment"),
+            Some("IndoorCycling" | "Spin" | "EBikeFitness" | "IndoorHandCycling"),
+        ) => "Biking",
+        _ => "Other",
+    }
+}
+
+fn write_element(tcx: &mut String, indent: usize, name: &str, value: impl Display) {
+    writeln!(tcx, "{:indent$}<{name}>{value}</{name}>", "").unwrap();
+}
+
+/// writes the lap summary and its track, the order of the elements is defined by the TCX schema
+fn write_lap(
+    tcx: &mut String,
+    lap: Option<&Messages>,
+    start_time: &str,
+    records: &[&Messages],
+    running: bool,
+) {
+    let lap_number = |field_name: &str| lap.and_then(|lap| number(lap, field_name));
+    let first_record_time = records
+        .first()
+        .and_then(|record| date_time(record, "timestamp"));
+    let last_record_time = records
+        .last()
+        .and_then(|record| date_time(record, "timestamp"));
+    let total_time = lap_number("total_timer_time").unwrap_or_else(|| {
+        f64::from(
+            last_record_time
+                .unwrap_or_default()
+                .saturating_sub(first_record_time.unwrap_or_default()),
+        )
+    });
+    let distance = lap_number("total_distance")
+        .or_else(|| records.last().and_then(|record| number(record, "distance")))
+        .unwrap_or_default();
+
+    writeln!(tcx, r#"      <Lap StartTime="{}">"#, start_time).unwrap();
+    write_element(tcx, 8, "TotalTimeSeconds", total_time);
+    write_element(tcx, 8, "DistanceMeters", distance);
+    if let Some(max_speed) = lap_number("enhanced_max_speed").or_else(|| lap_number("max_speed")) {
+        write_element(tcx, 8, "MaximumSpeed", max_speed);
+    }
+    write_element(
+        tcx,
+        8,
+        "Calories",
+        lap_number("total_calories").unwrap_or_default(),
+    );
+    if let Some(avg_heart_rate) = lap_number("avg_heart_rate") {
+        write_element(
+            tcx,
+            8,
+            "AverageHeartRateBpm",
+            heart_rate_value(avg_heart_rate),
+        );
+    }
+    if let Some(max_heart_rate) = lap_number("max_heart_rate") {
+        write_element(
+            tcx,
+            8,
+            "MaximumHeartRateBpm",
+            heart_rate_value(max_heart_rate),
+        );
+    }
+    let intensity = match lap.and_then(|lap| enum_name(lap, "intensity")).as_deref() {
+        Some("Rest") => "Resting",
+        _ => "Active",
+    };
+    write_element(tcx, 8, "Intensity", intensity);
+    if !running {
+        if let Some(avg_cadence) = lap_number("avg_cadence") {
+            write_element(tcx, 8, "Cadence", avg_cadence);
+        }
+    }
+    let trigger_method = match lap.and_then(|lap| enum_name(lap, "lap_trigger")).as_deref() {
+        Some("Time") => "Time",
+        Some("Distance") => "Distance",
+        Some(trigger) if trigger.starts_with("Position") => "Location",
+        _ => "Manual",
+    };
+    write_element(tcx, 8, "TriggerMethod", trigger_method);
+    if !records.is_empty() {
+        writeln!(tcx, "        <Track>").unwrap();
+        for record in records {
+            write_track_point(tcx, record, running);
+        }
+        writeln!(tcx, "        </Track>").unwrap();
+    }
+
+    let avg_speed = lap_number("enhanced_avg_speed").or_else(|| lap_number("avg_speed"));
+    let extensions = [
+        ("AvgSpeed", avg_speed),
+        (
+            "MaxBikeCadence",
+            lap_number("max_cadence").filter(|_| !running),
+        ),
+        (
+            "AvgRunCadence",
+            lap_number("avg_cadence").filter(|_| running),
+        ),
+        (
+            "MaxRunCadence",
+            lap_number("max_cadence").filter(|_| running),
+        ),
+        ("AvgWatts", lap_number("avg_power")),
+        ("MaxWatts", lap_number("max_power")),
+    ];
+    if extensions.iter().any(|(_, value)| value.is_some()) {
+        writeln!(tcx, "        <Extensions>").unwrap();
+        writeln!(tcx, "          <ns3:LX>").unwrap();
+        write_extensions(tcx, 12, &extensions);
+        writeln!(tcx, "          </ns3:LX>").unwrap();
+        writeln!(tcx, "        </Extensions>").unwrap();
+    }
+    writeln!(tcx, "      </Lap>").unwrap();
+}
+
+fn write_track_point(tcx: &mut String, record: &Messages, running: bool) {
+    let Some(time) = date_time(record, "timestamp").and_then(date_time_to_rfc3339) else {
+        return;
+    };
+    writeln!(tcx, "          <Trackpoint>").unwrap();
+    write_element(tcx, 12, "Time", time);
+    if let (Some(lat), Some(lon)) = (
+        degrees(record, "position_lat"),
+        degrees(record, "position_long"),
+    ) {
+        writeln!(tcx, "            <Position>").unwrap();
+        write_element(tcx, 14, "LatitudeDegrees", lat);
+        write_element(tcx, 14, "LongitudeDegrees", lon);
+        writeln!(tcx, "            </Position>").unwrap();
+    }
+    if let Some(altitude) =
+        number(record, "enhanced_altitude").or_else(|| number(record, "altitude"))
+    {
+        write_element(tcx, 12, "AltitudeMeters", altitude);
+    }
+    if let Some(distance) = number(record, "distance") {
+        write_element(tcx, 12, "DistanceMeters", distance);
+    }
+    if let Some(heart_rate) = number(record, "heart_rate") {
+        write_element(tcx, 12, "HeartRateBpm", heart_rate_value(heart_rate));
+    }
+    if !running {
+        if let Some(cadence) = number(record, "cadence") {
+            write_element(tcx, 12, "Cadence", cadence);
+        }
+    }
+    let speed = number(record, "enhanced_speed").or_else(|| number(record, "speed"));
+    let extensions = [
+        ("Speed", speed),
+        ("RunCadence", number(record, "cadence").filter(|_| running)),
+        ("Watts", number(record, "power")),
+    ];
+    if extensions.iter().any(|(_, value)| value.is_some()) {
+        writeln!(tcx, "            <Extensions>").unwrap();
+        writeln!(tcx, "              <ns3:TPX>").unwrap();
+        write_extensions(tcx, 16, &extensions);
+        writeln!(tcx, "              </ns3:TPX>").unwrap();
+        writeln!(tcx, "            </Extensions>").unwrap();
+    }
+    writeln!(tcx, "          </Trackpoint>").unwrap();
+}
+
+fn heart_rate_value(heart_rate: f64) -> String {
+    format!("<Value>{}</Value>", heart_rate)
+}
+
+fn write_extensions(tcx: &mut String, indent: usize, extensions: &[(&str, Option<f64>)]) {
+    for (name, value) in extensions {
+        if let Some(value) = value {
+            write_element(tcx, indent, &format!("ns3:{}", name), value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_types::Value;
+    use crate::export::{decode, message};
+    use crate::fit_encoder::EncodeMessage;
+
+    /// 2021-09-08T01:46:40Z
+    const START: u32 = 1_000_000_000;
+
+    fn lap(start_time: u32, timestamp: u32) -> EncodeMessage {
+        message(
+            "lap",
+            &[
+                ("start_time", Value::NumberValueU32(start_time)),
+                ("timestamp", Value::NumberValueU32(timestamp)),
+            ],
+        )
+    }
+
+    fn record(timestamp: u32) -> EncodeMessage {
+        message("record", &[("timestamp", Value::NumberValueU32(timestamp))])
+    }
+
+    #[test]
+    fn writes_lap_and_trackpoint_elements_in_schema_order() {
+        let tcx = to_tcx(&decode(&[
+            message(
+                "session",
+                &[
+                    ("start_time", Value::NumberValueU32(START)),
+                    ("sport", Value::EnumValue(vec![2])),
+                ],
+            ),
+            message(
+                "lap",
+                &[
+                    ("start_time", Value::NumberValueU32(START)),
+                    ("total_timer_time", Value::NumberValueU32(10_000)),
+                    ("total_distance", Value::NumberValueU32(2_500)),
+                    ("max_speed", Value::NumberValueU16(3_000)),
+                    ("total_calories", Value::NumberValueU16(3)),
+                    ("avg_heart_rate", Value::NumberValueU8(120)),
+                    ("max_heart_rate", Value::NumberValueU8(130)),
+                    ("intensity", Value::EnumValue(vec![2])),
+                    ("lap_trigger", Value::EnumValue(vec![2])),
+                    ("avg_cadence", Value::NumberValueU8(80)),
+                    ("avg_power", Value::NumberValueU16(200)),
+                ],
+            ),
+            message(
+                "record",
+                &[
+                    ("timestamp", Value::NumberValueU32(START)),
+                    ("position_lat", Value::NumberValueS32(1 << 28)),
+                    ("position_long", Value::NumberValueS32(1 << 29)),
+                    ("altitude", Value::NumberValueU16(2_600)),
+                    ("distance", Value::NumberValueU32(100)),
+                    ("heart_rate", Value::NumberValueU8(121)),
+                    ("cadence", Value::NumberValueU8(82)),
+                    ("speed", Value::NumberValueU16(2_500)),
+                ],
+            ),
+        ]));
+        let lap_start = tcx.find("      <Lap ").unwrap();
+        let lap_end = tcx.find("    </Activity>").unwrap();
+        assert_eq!(
+            &tcx[lap_start..lap_end],
+            r#"      <Lap StartTime="2021-09-08T01:46:40Z">
+        <TotalTimeSeconds>10</TotalTimeSeconds>
+        <DistanceMeters>25</DistanceMeters>
+        <MaximumSpeed>3</MaximumSpeed>
+        <Calories>3</Calories>
+        <AverageHeartRateBpm><Value>120</Value></AverageHeartRateBpm>
+        <MaximumHeartRateBpm><Value>130</Value></MaximumHeartRateBpm>
+        <Intensity>Active</Intensity>
+        <Cadence>80</Cadence>
+        <TriggerMethod>Distance</TriggerMethod>
+        <Track>
+          <Trackpoint>
+            <Time>2021-09-08T01:46:40Z</Time>
+            <Position>
+              <LatitudeDegrees>22.5</LatitudeDegrees>
+              <LongitudeDegrees>45</LongitudeDegrees>
+            </Position>
+            <AltitudeMeters>20</AltitudeMeters>
+            <DistanceMeters>1</DistanceMeters>
+            <HeartRateBpm><Value>121</Value></HeartRateBpm>
+            <Cadence>82</Cadence>
+            <Extensions>
+              <ns3:TPX>
+                <ns3:Speed>2.5</ns3:Speed>
+              </ns3:TPX>
+            </Extensions>
+          </Trackpoint>
+        </Track>
+        <Extensions>
+          <ns3:LX>
+            <ns3:AvgWatts>200</ns3:AvgWatts>
+          </ns3:LX>
+        </Extensions>
+      </Lap>
+"#
+        );
+    }
+
+    #[test]
+    fn assigns_laps_and_records_by_their_start_time() {
+        let session = |start_time| {
+            message(
+                "session",
+                &[("start_time", Value::NumberValueU32(start_time))],
+            )
+        };
+        let tcx = to_tcx(&decode(&[
+            session(START),
+            session(START + 10),
+            lap(START, START + 4),
+            lap(START + 5, START + 9),
+            lap(START + 10, START + 14),
+            record(START),
+            record(START + 4),
+            record(START + 5),
+            record(START + 7),
+            record(START + 9),
+            record(START + 12),
+        ]));
+        // track points per lap per activity
+        let activities = tcx
+            .split("<Activity ")
+            .skip(1)
+            .map(|activity| {
+                activity
+                    .split("<Lap ")
+                    .skip(1)
+                    .map(|lap| lap.matches("<Trackpoint>").count())
+                    .collect::<Vec<usize>>()
+            })
+            .collect::<Vec<Vec<usize>>>();
+        assert_eq!(activities, vec![vec![2, 3], vec![1]]);
+    }
+
+    #[test]
+    fn maps_sports_to_running_biking_or_other() {
+        let sport = |sport: &str, sub_sport: &str| {
+            tcx_sport(Some(sport.to_string()), Some(sub_sport.to_string()))
+        };
+        assert_eq!(sport("Running", "Trail"), "Running");
+        assert_eq!(sport("FitnessEquipment", "Treadmill"), "Running");
+        assert_eq!(sport("Cycling", "GravelCycling"), "Biking");
+        assert_eq!(sport("EBiking", "EBikeMountain"), "Biking");
+        assert_eq!(sport("FitnessEquipment", "IndoorCycling"), "Biking");
+        assert_eq!(sport("FitnessEquipment", "Elliptical"), "Other");
+        assert_eq!(sport("Walking", "Treadmill"), "Other");
+        assert_eq!(tcx_sport(None, None), "Other");
+    }
+}
@@ -0,0 +1,598 @@
+use std::collections::HashMap;
+
+use crate::crc::crc16;
+use crate::data_types::{BaseType, Value};
+use crate::error::FitError;
+use crate::fit_file::fields::Field;
+use crate::fit_file::message_types::MessageType;
+use crate::fit_file_raw::{FitFileRaw, MessageRaw};
+
+const HEADER_LENGTH: usize = 14;
+const FIELD_DESCRIPTION: u16 = 206;
+const DEVELOPER_DATA_ID: u16 = 207;
+
+/// Configuration for writing FIT files
+pub struct FitEncoderConfig {
+    /// write multi byte values big endian instead of little endian
+    pub big_endian: bool,
+    /// protocol version byte of the header, e.g. `0x20` for 2.0 which is needed for developer fields
+    pub protocol_version: u8,
+    /// profile version of the header times 100, e.g. `2132` for 21.32
+    pub profile_version: u16,
+    /// number of local message types (1 to 16) that are used before definitions are replaced
+    pub local_message_types: u8,
+}
+
+impl Default for FitEncoderConfig {
+    fn default() -> Self {
+        FitEncoderConfig {
+            big_endian: false,
+            protocol_version: 0x20,
+            profile_version: 2132,
+            local_message_types: 16,
+        }
+    }
+}
+
+/// Field of a message to encode, the value is written as stored in a FIT file,
+/// without scale, offset or components of the profile
+#[derive(Clone, Debug)]
+pub struct EncodeField {
+    pub number: u8,
+    pub base_type: BaseType,
+    pub value: Value,
+    /// size in bytes, `None` for the size of the value
+    pub size: Option<u8>,
+}
+
+/// Developer field of a message to encode, described by a `field_description` message
+/// with the same developer data index and field number
+#[derive(Clone, Debug)]
+pub struct EncodeDeveloperField {
+    pub developer_data_index: u8,
+    pub number: u8,
+    pub base_type: BaseType,
+    pub value: Value,
+    /// size in bytes, `None` for the size of the value
+    pub size: Option<u8>,
+}
+
+/// Message to encode with numbered fields, like [MessageRaw]
+#[derive(Clone, Debug)]
+pub struct EncodeMessage {
+    pub message_number: u16,
+    pub fields: Vec<EncodeField>,
+    pub developer_fields: Vec<EncodeDeveloperField>,
+}
+
+impl EncodeMessage {
+    pub fn new(message_number: u16) -> EncodeMessage {
+        EncodeMessage {
+            message_number,
+            fields: vec![],
+            developer_fields: vec![],
+        }
+    }
+
+    /// message of a type of the profile, by its profile name like `record` or its name like `Record`
+    pub fn by_name(message_name: &str) -> Option<EncodeMessage> {
+        MessageType::resolve_by_name(message_name)
+            .map(|message_type| EncodeMessage::new(message_type.number))
+    }
+
+    /// adds a field with the base type of its value, see [BaseType::of]
+    pub fn field(self, number: u8, value: Value) -> EncodeMessage {
+        let base_type = BaseType::of(&value).unwrap_or(BaseType::BYTE);
+        self.field_with_type(number, base_type, value, None)
+    }
+
+    /// adds a field with an explicit base type and size in bytes
+    pub fn field_with_type(
+        mut self,
+        number: u8,
+        base_type: BaseType,
+        value: Value,
+        size: Option<u8>,
+    ) -> EncodeMessage {
+        self.fields.push(EncodeField {
+            number,
+            base_type,
+            value,
+            size,
+        });
+        self
+    }
+
+    /// adds a field by its profile name like `heart_rate`, subfields are written as the field they belong to
+    pub fn named_field(self, field_name: &str, value: Value) -> Result<EncodeMessage, String> {
+        let message_type = MessageType::resolve(self.message_number, None);
+        let Some((number, _)) = Field::resolve_by_name(&message_type, field_name) else {
+            return Err(format!(
+                "{} has no field {:?}",
+                message_type.profile_name(),
+                field_name
+            ));
+        };
+        Ok(self.field(number, value))
+    }
+
+    /// adds a developer field with its base type
+    pub fn developer_field(
+        mut self,
+        developer_data_index: u8,
+        number: u8,
+        base_type: BaseType,
+        value: Value,
+    ) -> EncodeMessage {
+        self.developer_fields.push(EncodeDeveloperField {
+            developer_data_index,
+            number,
+            base_type,
+            value,
+            size: None,
+        });
+        self
+    }
+
+    /// `developer_data_id` message that has to precede the field descriptions of the developer data index
+    pub fn developer_data_id(developer_data_index: u8, application_id: [u8; 16]) -> EncodeMessage {
+        EncodeMessage::new(DEVELOPER_DATA_ID)
+            .field_with_type(
+                1,
+                BaseType::BYTE,
+                Value::NumberValueVecU8(application_id.to_vec()),
+                None,
+            )
+            .field(3, Value::NumberValueU8(developer_data_index))
+    }
+
+    /// `field_description` message that has to precede the messages with the developer field,
+    /// array fields additionally need the number of elements as field 4
+    pub fn field_description(
+        developer_data_index: u8,
+        field_number: u8,
+        base_type: &BaseType,
+        field_name: &str,
+        units: &str,
+    ) -> EncodeMessage {
+        EncodeMessage::new(FIELD_DESCRIPTION)
+            .field(0, Value::NumberValueU8(developer_data_index))
+            .field(1, Value::NumberValueU8(field_number))
+            .field(2, Value::NumberValueU8(base_type.type_number))
+            .field(3, Value::StringValue(field_name.to_string()))
+            .field(8, Value::StringValue(units.to_string()))
+    }
+}
+
+impl From<&MessageRaw> for EncodeMessage {
+    /// the fields with their base types and sizes, the base types of their values if they are not known,
+    /// see [BaseType::of]
+    fn from(message: &MessageRaw) -> EncodeMessage {
+        let mut encode_message = EncodeMessage::new(message.message_number);
+        for entry in &message.fields.data {
+            let base_type = entry
+                .base_type
+                .and_then(|base_type| BaseType::parse(&base_type))
+                .or_else(|| BaseType::of(&entry.value))
+                .unwrap_or(BaseType::BYTE);
+            match entry.developer_data_index {
+                Some(developer_data_index) => {
+                    encode_message.developer_fields.push(EncodeDeveloperField {
+                        developer_data_index,
+                        number: entry.field_number,
+                        base_type,
+                        value: entry.value.clone(),
+                        size: entry.size,
+                    })
+                }
+                None => encode_message.fields.push(EncodeField {
+                    number: entry.field_number,
+                    base_type,
+                    value: entry.value.clone(),
+                    size: entry.size,
+                }),
+            }
+        }
+        encode_message
+    }
+}
+
+/// field definitions of a local message type, each as field number, size and base type or developer data index
+#[derive(PartialEq)]
+struct LocalDefinition {
+    message_number: u16,
+    fields: Vec<[u8; 3]>,
+    developer_fields: Vec<[u8; 3]>,
+}
+
+/// Writes messages into a FIT file.
+///
+/// Local message types are assigned automatically: a definition is written for every new combination
+/// of message type, fields, sizes and base types, and the least recently used local message type is
+/// redefined when all are in use. [FitEncoder::finish] adds the 14 byte header and both CRCs.
+pub struct FitEncoder {
+    config: FitEncoderConfig,
+    /// data records without the header
+    data: Vec<u8>,
+    /// definitions by local message number with the number of the message that used it last
+    local_definitions: Vec<Option<(LocalDefinition, usize)>>,
+    message_count: usize,
+    /// number of elements of the developer array fields by developer data index and field number,
+    /// from the written field descriptions
+    developer_arrays: HashMap<(u8, u8), u8>,
+}
+
+impl FitEncoder {
+    pub fn new(config: FitEncoderConfig) -> FitEncoder {
+        let local_message_types = config.local_message_types.clamp(1, 16) as usize;
+        FitEncoder {
+            config,
+            data: vec![],
+            local_definitions: (0..local_message_types).map(|_| None).collect(),
+            message_count: 0,
+            developer_arrays: HashMap::new(),
+        }
+    }
+
+    /// writes the message, preceded by a definition if its layout has no local message type
+    pub fn write(&mut self, message: &EncodeMessage) -> Result<(), FitError> {
+        let endianness = u8::from(self.config.big_endian);
+        let offset = HEADER_LENGTH + self.data.len();
+        let invalid_message = |context: String| FitError::InvalidMessage { offset, context };
+        if message.fields.len() > u8::MAX as usize
+            || message.developer_fields.len() > u8::MAX as usize
+        {
+            return Err(invalid_message(format!(
+                "message {} has more than 255 fields",
+                message.message_number
+            )));
+        }
+
+        let mut values = vec![];
+        let mut fields = vec![];
+        for field in &message.fields {
+            let bytes = encode_value(&field.base_type, &field.value, field.size, endianness)
+                .map_err(|context| {
+                    invalid_message(format!("field {}: {}", field.number, context))
+                })?;
+            fields.push([field.number, bytes.len() as u8, field.base_type.type_number]);
+            values.extend(bytes);
+        }
+        let mut developer_fields = vec![];
+        for field in &message.developer_fields {
+            // without an explicit size, arrays get the number of elements of their description
+            let field_size = field.size.or_else(|| {
+                let array = self
+                    .developer_arrays
+                    .get(&(field.developer_data_index, field.number))?;
+                let size = *array as usize * field.base_type.read_size;
+                let encoded = field.base_type.encode(&field.value, None, endianness);
+                (size <= u8::MAX as usize && encoded.ok()?.len() < size).then_some(size as u8)
+            });
+            let bytes = encode_value(&field.base_type, &field.value, field_size, endianness)
+                .map_err(|context| {
+                    invalid_message(format!(
+                        "developer field {} of developer {}: {}",
+                        field.number, field.developer_data_index, context
+                    ))
+                })?;
+            developer_fields.push([field.number, bytes.len() as u8, field.developer_data_index]);
+            values.extend(bytes);
+        }
+        let definition = LocalDefinition {
+            message_number: message.message_number,
+            fields,
+            developer_fields,
+        };
+
+        let local_message_number = match self.local_definitions.iter().position(|local| {
+            local
+                .as_ref()
+                .is_some_and(|(defined, _)| *defined == definition)
+        }) {
+            Some(local_message_number) => local_message_number,
+            None => {
+                let local_message_number = self.free_local_message_number();
+                self.write_definition(local_message_number as u8, &definition);
+                self.local_definitions[local_message_number] = Some((definition, 0));
+                local_message_number
+            }
+        };
+        if let Some((_, last_used)) = &mut self.local_definitions[local_message_number] {
+            *last_used = self.message_count;
+        }
+        self.message_count += 1;
+        self.data.push(local_message_number as u8);
+        self.data.extend(values);
+        self.update_developer_arrays(message);
+        Ok(())
+    }
+
+    /// like the decoder, a `developer_data_id` forgets the descriptions of its developer data index
+    fn update_developer_arrays(&mut self, message: &EncodeMessage) {
+        let number = |field_number: u8| {
+            message
+                .fields
+                .iter()
+                .find(|field| field.number == field_number)
+                .and_then(|field| field.value.as_f64())
+                .map(|number| number as u8)
+        };
+        match message.message_number {
+            DEVELOPER_DATA_ID => {
+                if let Some(developer_data_index) = number(3) {
+                    self.developer_arrays
+                        .retain(|(index, _), _| *index != developer_data_index);
+                }
+            }
+            FIELD_DESCRIPTION => {
+                if let (Some(developer_data_index), Some(field_number)) = (number(0), number(1)) {
+                    match number(4).filter(|array| *array > 0) {
+                        Some(array) => self
+                            .developer_arrays
+                            .insert((developer_data_index, field_number), array),
+                        None => self
+                            .developer_arrays
+                            .remove(&(developer_data_index, field_number)),
+                    };
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// an unused local message number or the least recently used one
+    fn free_local_message_number(&self) -> usize {
+        self.local_definitions
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, local)| local.as_ref().map(|(_, last_used)| *last_used + 1))
+            .map(|(local_message_number, _)| local_message_number)
+            .unwrap_or(0)
+    }
+
+    fn write_definition(&mut self, local_message_number: u8, definition: &LocalDefinition) {
+        let developer_data = !definition.developer_fields.is_empty();
+        let mut record_header = 0x40 | local_message_number;
+        if developer_data {
+            record_header |= 0x20;
+        }
+        let message_number = if self.config.big_endian {
+            definition.message_number.to_be_bytes()
+        } else {
+            definition.message_number.to_le_bytes()
+        };
+        self.data.extend([
+            record_header,
+            0, // reserved
+            u8::from(self.config.big_endian),
+            message_number[0],
+            message_number[1],
+            definition.fields.len() as u8,
+        ]);
+        self.data.extend(definition.fields.concat());
+        if developer_data {
+            self.data.push(definition.developer_fields.len() as u8);
+            self.data.extend(definition.developer_fields.concat());
+        }
+    }
+
+    /// the FIT file with header, all written messages and the file CRC
+    pub fn finish(self) -> Vec<u8> {
+        let mut file = Vec::with_capacity(HEADER_LENGTH + self.data.len() + 2);
+        file.push(HEADER_LENGTH as u8);
+        file.push(self.config.protocol_version);
+        file.extend(self.config.profile_version.to_le_bytes());
+        file.extend((self.data.len() as u32).to_le_bytes());
+        file.extend(b".FIT");
+        file.extend(crc16(&file).to_le_bytes());
+        file.extend(&self.data);
+        file.extend(crc16(&file).to_le_bytes());
+        file
+    }
+}
+
+/// the value as stored, a field can have at most 255 bytes
+fn encode_value(
+    base_type: &BaseType,
+    value: &Value,
+    size: Option<u8>,
+    endianness: u8,
+) -> Result<Vec<u8>, String> {
+    let bytes = base_type.encode(value, size, endianness)?;
+    if bytes.len() > u8::MAX as usize {
+        return Err(format!("{} bytes are more than 255", bytes.len()));
+    }
+    Ok(bytes)
+}
+
+/// Encodes the messages into a FIT file, see [FitEncoder]
+pub fn encode(messages: &[EncodeMessage], config: FitEncoderConfig) -> Result<Vec<u8>, FitError> {
+    let mut encoder = FitEncoder::new(config);
+    for message in messages {
+        encoder.write(message)?;
+    }
+    Ok(encoder.finish())
+}
+
+/// Encodes raw messages into a FIT file with the base types and sizes they were read with,
+/// see [EncodeMessage::from]
+pub fn encode_raw(messages: &[MessageRaw], config: FitEncoderConfig) -> Result<Vec<u8>, FitError> {
+    let mut encoder = FitEncoder::new(config);
+    for message in messages {
+        encoder.write(&EncodeMessage::from(message))?;
+    }
+    Ok(encoder.finish())
+}
+
+/// Converts the JSON of `fit-to-json` back into a FIT file, see [FitFileRaw::from_json]
+pub fn json_to_fit(json: &str, config: FitEncoderConfig) -> Result<Vec<u8>, FitError> {
+    encode_raw(&FitFileRaw::from_json(json)?.messages, config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fit_file::{FitFile, FitFileConfig};
+
+    fn activity() -> Vec<EncodeMessage> {
+        let mut messages = vec![
+            EncodeMessage::by_name("file_id")
+                .unwrap()
+                .field(0, Value::EnumValue(vec![4]))
+                .field(1, Value::NumberValueU16(1))
+                .field(4, Value::NumberValueU32(1_000_000_000)),
+            EncodeMessage::developer_data_id(0, [7; 16]),
+            EncodeMessage::field_description(0, 0, &BaseType::UINT16, "Power", "W"),
+        ];
+        for second in 0..3 {
+            messages.push(
+                EncodeMessage::by_name("record")
+                    .unwrap()
+                    .field(253, Value::NumberValueU32(1_000_000_000 + second))
+                    .named_field("heart_rate", Value::NumberValueU8(140 + second as u8))
+                    .unwrap()
+                    .named_field("distance", Value::NumberValueU32(250 * second))
+                    .unwrap()
+                    .developer_field(0, 0, BaseType::UINT16, Value::NumberValueU16(200)),
+            );
+        }
+        messages
+    }
+
+    #[test]
+    fn decodes_encoded_messages() {
+        let file = encode(&activity(), FitEncoderConfig::default()).unwrap();
+        let fit_file_raw = FitFileRaw::from(&file).unwrap();
+        assert!(fit_file_raw.crc.is_valid());
+        assert_eq!(fit_file_raw.messages.len(), 6);
+        let record = &fit_file_raw.messages[5];
+        assert_eq!(record.message_number, 20);
+        assert_eq!(record.fields.timestamp(), Some(1_000_000_002));
+        let values = record
+            .fields
+            .data
+            .iter()
+            .map(|entry| (entry.key(), entry.value.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            vec![
+                ("253".to_string(), Value::NumberValueU32(1_000_000_002)),
+                ("3".to_string(), Value::NumberValueU8(142)),
+                ("5".to_string(), Value::NumberValueU32(500)),
+                ("dev:0:0".to_string(), Value::NumberValueU16(200)),
+            ]
+        );
+
+        let fit_file = FitFile::from(&file, &FitFileConfig::default()).unwrap();
+        assert!(fit_file.crc.is_valid());
+        assert_eq!(fit_file.get_message_types().get("Record"), Some(&3));
+        let record = &fit_file.messages[5].data;
+        assert_eq!(record.value("heart_rate"), Some(&Value::NumberValueU8(142)));
+        // distance is stored in centimeters
+        assert_eq!(record.value("distance").and_then(Value::as_f64), Some(5.0));
+        assert_eq!(record.value("Power").and_then(Value::as_f64), Some(200.0));
+    }
+
+    #[test]
+    fn decodes_array_developer_fields_as_arrays() {
+        let messages = vec![
+            EncodeMessage::developer_data_id(0, [7; 16]),
+            EncodeMessage::field_description(0, 0, &BaseType::UINT8, "Zones", "")
+                .field(4, Value::NumberValueU8(3)),
+            // a single element is written with the size of the declared array
+            EncodeMessage::by_name("session").unwrap().developer_field(
+                0,
+                0,
+                BaseType::UINT8,
+                Value::NumberValueU8(5),
+            ),
+            EncodeMessage::by_name("session").unwrap().developer_field(
+                0,
+                0,
+                BaseType::UINT8,
+                Value::NumberValueVecU8(vec![5, 6, 7]),
+            ),
+        ];
+        let file = encode(&messages, FitEncoderConfig::default()).unwrap();
+
+        let fit_file_raw = FitFileRaw::from(&file).unwrap();
+        let sizes_and_values = fit_file_raw.messages[2..]
+            .iter()
+            .map(|message| &message.fields.data[0])
+            .map(|entry| (entry.size, entry.value.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            sizes_and_values,
+            vec![
+                (Some(3), Value::NumberValueVecU8(vec![5])),
+                (Some(3), Value::NumberValueVecU8(vec![5, 6, 7])),
+            ]
+        );
+
+        let fit_file = FitFile::from(&file, &FitFileConfig::default()).unwrap();
+        let values = fit_file.messages[2..]
+            .iter()
+            .map(|message| message.data.value("Zones").cloned())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            vec![
+                Some(Value::NumberValueVecU8(vec![5])),
+                Some(Value::NumberValueVecU8(vec![5, 6, 7])),
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_invalid_uint32_and_float32_values_as_invalid() {
+        for base_type in [BaseType::UINT32, BaseType::FLOAT32] {
+            let bytes = base_type.encode(&Value::Invalid, None, 0).unwrap();
+            assert_eq!(bytes, [0xFF; 4]);
+            assert_eq!((base_type.read)(&base_type, &bytes, &0), Value::Invalid);
+        }
+
+        let message = EncodeMessage::new(0xFF00)
+            .field_with_type(0, BaseType::UINT32, Value::Invalid, None)
+            .field_with_type(1, BaseType::FLOAT32, Value::Invalid, None)
+            .field_with_type(2, BaseType::UINT32, Value::NumberValueU32(0x0FFFFFFF), None)
+            .field_with_type(
+                3,
+                BaseType::FLOAT32,
+                Value::NumberValueVecF32(vec![1.5]),
+                Some(8),
+            )
+            .field_with_type(4, BaseType::UINT32, Value::Invalid, Some(8));
+        let file = encode(&[message], FitEncoderConfig::default()).unwrap();
+        let messages = FitFileRaw::from(&file).unwrap().messages;
+        let values = messages[0]
+            .fields
+            .data
+            .iter()
+            .map(|entry| (entry.field_number, entry.value.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            vec![
+                (2, Value::NumberValueU32(0x0FFFFFFF)),
+                (3, Value::NumberValueVecF32(vec![1.5])),
+            ]
+        );
+    }
+
+    #[test]
+    fn reencodes_decoded_raw_messages_to_the_same_file() {
+        // the second layout redefines its only local message type for every change of message type
+        for (big_endian, local_message_types) in [(false, 16), (true, 1)] {
+            let config = || FitEncoderConfig {
+                big_endian,
+                local_message_types,
+                ..FitEncoderConfig::default()
+            };
+            let file = encode(&activity(), config()).unwrap();
+            let messages = FitFileRaw::from(&file).unwrap().messages;
+            assert_eq!(encode_raw(&messages, config()).unwrap(), file);
+        }
+    }
+}
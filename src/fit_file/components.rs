@@ -0,0 +1,449 @@
+use std::collections::HashMap;
+
+use crate::data_types::Value;
+use crate::fit_file::fields::Field;
+use crate::fit_file::message::FieldValue;
+use crate::fit_file::message_types::MessageType;
+use crate::fit_file::ProfileExtension;
+
+/// A component of a stored field as defined by the FIT profile.
+/// The stored value is read as little endian bit stream, each component takes the next `bits` bits
+/// and is written to the field `target_field_number` of the same message.
+#[derive(Debug)]
+pub struct Component {
+    pub message_number: u16,
+    pub field_number: u8,
+    pub target_field_number: u8,
+    pub bits: u8,
+    pub scale: f64,
+    pub offset: f64,
+    /// the value is a rolling counter that is accumulated over the file
+    pub accumulate: bool,
+    /// field number and value of another field in the message the component depends on
+    pub reference: Option<(u8, u32)>,
+}
+
+// message, field, target field, bits, scale, offset, accumulate [, reference field, reference value]
+crate::expand_components! {
+    18,14,124,16,1000,0,false
+    18,15,125,16,1000,0,false
+    18,49,126,16,5,500,false
+    18,50,128,16,5,500,false
+    18,71,127,16,5,500,false
+    18,147,169,8,1,0,false
+    18,148,170,8,1,0,false
+    18,149,180,8,1,0,false
+    19,13,110,16,1000,0,false
+    19,14,111,16,1000,0,false
+    19,42,112,16,5,500,false
+    19,43,114,16,5,500,false
+    19,62,113,16,5,500,false
+    19,147,136,8,1,0,false
+    19,148,137,8,1,0,false
+    101,24,22,8,1,0,false
+    101,25,23,8,1,0,false
+    20,2,78,16,5,500,false
+    20,6,73,16,1000,0,false
+    20,8,6,12,100,0,false
+    20,8,5,12,16,0,true
+    20,18,19,8,1,0,true
+    20,28,29,16,1,0,true
+    20,99,108,8,1,0,false
+    21,2,3,16,1,0,false
+    21,3,7,16,1,0,false,0,33
+    21,3,8,16,1,0,false,0,33
+    21,3,11,8,1,0,false,0,42
+    21,3,12,8,1,0,false,0,42
+    21,3,9,8,1,0,false,0,42
+    21,3,10,8,1,0,false,0,42
+    21,3,11,8,1,0,false,0,43
+    21,3,12,8,1,0,false,0,43
+    21,3,9,8,1,0,false,0,43
+    21,3,10,8,1,0,false,0,43
+    21,3,21,8,1,0,false,0,75
+    21,3,22,8,1,0,false,0,75
+    21,3,23,8,10,0,false,0,75
+    21,3,24,8,10,0,false,0,75
+    285,7,8,16,1000,0,false
+    150,4,6,16,5,500,false
+    142,34,91,16,5,500,false
+    142,35,92,16,5,500,false
+    142,54,93,16,5,500,false
+    55,24,5,5,1,0,false
+    55,24,28,3,1,0,false
+    132,1,0,8,256,0,false
+    132,10,9,12,1024,0,true
+    132,10,9,12,1024,0,true
+    132,10,9,12,1024,0,true
+    132,10,9,12,1024,0,true
+    132,10,9,12,1024,0,true
+    132,10,9,12,1024,0,true
+    132,10,9,12,1024,0,true
+    132,10,9,12,1024,0,true
+    132,10,9,12,1024,0,true
+    132,10,9,12,1024,0,true
+    80,2,3,8,1,0,false
+    80,2,4,8,1,0,false
+    80,2,4,8,1,0,false
+    80,2,4,8,1,0,false
+    80,2,4,8,1,0,false
+    80,2,4,8,1,0,false
+    80,2,4,8,1,0,false
+    80,2,4,8,1,0,false
+    80,2,4,8,1,0,false
+    81,2,3,8,1,0,false
+    81,2,4,8,1,0,false
+    81,2,4,8,1,0,false
+    81,2,4,8,1,0,false
+    81,2,4,8,1,0,false
+    81,2,4,8,1,0,false
+    81,2,4,8,1,0,false
+    81,2,4,8,1,0,false
+    81,2,4,8,1,0,false
+    201,1,2,4,1,0,false
+    201,1,3,4,1,0,false
+    202,1,2,4,1,0,false
+    202,1,3,4,1,0,false
+}
+
+impl Component {
+    /// converts the raw component value into the physical value of the target field
+    fn value(&self, raw: u32) -> Value {
+        if self.scale == 1.0 && self.offset == 0.0 {
+            Value::NumberValueU32(raw)
+        } else {
+            Value::NumberValueF64((f64::from(raw) - self.offset * self.scale) / self.scale)
+        }
+    }
+}
+
+/// A field as read from a data message, kept to expand its components
+pub struct StoredField {
+    pub number: u8,
+    /// stored bytes, every element in little endian order
+    pub bytes: Vec<u8>,
+    /// size of an element in bytes
+    pub element_size: usize,
+    /// the value after applying scale and offset of the field
+    pub value: Value,
+}
+
+impl StoredField {
+    pub fn from(
+        number: u8,
+        data: &[u8],
+        element_size: usize,
+        endianness: &u8,
+        value: Value,
+    ) -> Self {
+        let mut bytes = data.to_vec();
+        if *endianness != 0 && element_size > 1 {
+            for element in bytes.chunks_mut(element_size) {
+                element.reverse();
+            }
+        }
+        StoredField {
+            number,
+            bytes,
+            element_size,
+            value,
+        }
+    }
+
+    /// the stored value as unsigned number, arrays and values larger than 32 bit are cut off
+    pub fn raw_value(&self) -> Option<u32> {
+        self.read_bits(0, (self.bytes.len() * 8).min(32) as u8)
+    }
+
+    /// reads `bits` bits starting at `bit_offset`, `None` if the field is too short
+    fn read_bits(&self, bit_offset: usize, bits: u8) -> Option<u32> {
+        if bits > 32 || bit_offset + bits as usize > self.bytes.len() * 8 {
+            return None;
+        }
+        let mut value = 0u32;
+        for i in 0..bits as usize {
+            let bit = bit_offset + i;
+            if (self.bytes[bit / 8] >> (bit % 8)) & 1 == 1 {
+                value |= 1 << i;
+            }
+        }
+        Some(value)
+    }
+}
+
+/// Rolling counters of accumulated components, per message and target field
+#[derive(Default)]
+pub struct Accumulators {
+    values: HashMap<(u16, u8), Accumulator>,
+}
+
+#[derive(Default)]
+struct Accumulator {
+    last_value: u32,
+    accumulated_value: u32,
+}
+
+impl Accumulators {
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+
+    fn accumulate(&mut self, message_number: u16, field_number: u8, value: u32, bits: u8) -> u32 {
+        let mask = if bits >= 32 {
+            u32::MAX
+        } else {
+            (1 << bits) - 1
+        };
+        let accumulator = self
+            .values
+            .entry((message_number, field_number))
+            .or_default();
+        accumulator.accumulated_value = accumulator
+            .accumulated_value
+            .wrapping_add(value.wrapping_sub(accumulator.last_value) & mask);
+        accumulator.last_value = value;
+        accumulator.accumulated_value
+    }
+
+    /// restarts the counter with a value of the target field itself
+    fn set(&mut self, message_number: u16, field_number: u8, value: u32) {
+        self.values.insert(
+            (message_number, field_number),
+            Accumulator {
+                last_value: value,
+                accumulated_value: value,
+            },
+        );
+    }
+}
+
+/// Expands the components of the stored fields into the fields they are unpacking to.
+/// Fields that are part of the message themselves are not overwritten by a component.
+pub fn expand(
+    message_type: &MessageType,
+    stored_fields: &[StoredField],
+    accumulators: &mut Accumulators,
+    include_invalid_values: bool,
+    extension: Option<&ProfileExtension>,
+) -> Vec<FieldValue> {
+    let message_number = message_type.number;
+    let contains = |field_number: u8| {
+        stored_fields
+            .iter()
+            .any(|field| field.number == field_number && !field.value.is_invalid())
+    };
+    // the fields that are written directly restart their counters
+    for component in COMPONENTS
+        .iter()
+        .filter(|component| component.message_number == message_number && component.accumulate)
+    {
+        if let Some(physical_value) = stored_fields
+            .iter()
+            .find(|field| field.number == component.target_field_number)
+            .and_then(|field| field.value.as_f64())
+        {
+            let value = (physical_value + component.offset) * component.scale;
+            accumulators.set(message_number, component.target_field_number, value as u32);
+        }
+    }
+
+    let mut expanded: Vec<FieldValue> = vec![];
+    for stored_field in stored_fields {
+        let first_expanded = expanded.len();
+        let mut bit_offset = 0;
+        for component in COMPONENTS.iter().filter(|component| {
+            component.message_number == message_number
+                && component.field_number == stored_field.number
+        }) {
+            if let Some((reference_field_number, reference_value)) = component.reference {
+                let matches = stored_fields
+                    .iter()
+                    .find(|field| field.number == reference_field_number)
+                    .and_then(StoredField::raw_value)
+                    .is_some_and(|value| value == reference_value);
+                if !matches {
+                    continue;
+                }
+            }
+            let Some(raw) = stored_field.read_bits(bit_offset, component.bits) else {
+                break;
+            };
+            bit_offset += component.bits as usize;
+            if contains(component.target_field_number) {
+                continue;
+            }
+            let invalid = raw == u32::MAX >> (32 - component.bits);
+            if invalid && !include_invalid_values {
+                continue;
+            }
+            let value = if invalid {
+                Value::Invalid
+            } else if component.accumulate {
+                let accumulated = accumulators.accumulate(
+                    message_number,
+                    component.target_field_number,
+                    raw,
+                    component.bits,
+                );
+                component.value(accumulated)
+            } else {
+                component.value(raw)
+            };
+            let field =
+                Field::resolve_field(message_type, component.target_field_number, extension);
+            if field.is_unknown() {
+                continue;
+            }
+            // components that unpack into the same field fill an array, e.g. the `event_timestamp`s of `Hr`
+            match expanded[first_expanded..]
+                .iter_mut()
+                .find(|field_value| field_value.field == field)
+            {
+                Some(field_value) => field_value.value = append(&field_value.value, &value),
+                None => expanded.push(FieldValue { field, value }),
+            }
+        }
+    }
+    expanded
+}
+
+/// adds the value of a component to the values of the components before, invalid values are left out
+fn append(values: &Value, value: &Value) -> Value {
+    match (values, value) {
+        (_, Value::Invalid) => values.clone(),
+        (Value::Invalid, _) => value.clone(),
+        (Value::NumberValueU32(first), Value::NumberValueU32(value)) => {
+            Value::NumberValueVecU32(vec![*first, *value])
+        }
+        (Value::NumberValueVecU32(values), Value::NumberValueU32(value)) => {
+            let mut values = values.clone();
+            values.push(*value);
+            Value::NumberValueVecU32(values)
+        }
+        _ => {
+            let mut values = values
+                .as_f64()
+                .map(|first| vec![first])
+                .or_else(|| values.as_f64_vec())
+                .unwrap_or_default();
+            values.extend(value.as_f64());
+            Value::NumberValueVecF64(values)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stored_field(number: u8, bytes: &[u8]) -> StoredField {
+        StoredField::from(
+            number,
+            bytes,
+            1,
+            &0,
+            Value::NumberValueVecU8(bytes.to_vec()),
+        )
+    }
+
+    /// names and values of the expanded fields
+    fn expanded(
+        message_number: u16,
+        stored_fields: &[StoredField],
+        accumulators: &mut Accumulators,
+    ) -> Vec<(String, Value)> {
+        let message_type = MessageType::resolve(message_number, None);
+        expand(&message_type, stored_fields, accumulators, false, None)
+            .into_iter()
+            .map(|field_value| {
+                (
+                    field_value.field.name().unwrap().to_string(),
+                    field_value.value,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn accumulates_the_distance_of_compressed_speed_distance_over_rollovers() {
+        // 12 bits of speed in cm/s, 12 bits of distance in 1/16 m
+        let compressed_speed_distance = |speed: u32, distance: u32| {
+            let bits = speed | distance << 12;
+            stored_field(8, &bits.to_le_bytes()[..3])
+        };
+        let mut accumulators = Accumulators::default();
+        assert_eq!(
+            expanded(
+                20,
+                &[compressed_speed_distance(250, 4000)],
+                &mut accumulators
+            ),
+            vec![
+                ("speed".to_string(), Value::NumberValueF64(2.5)),
+                ("distance".to_string(), Value::NumberValueF64(250.0)),
+            ]
+        );
+        // the distance counter rolled over at 4096
+        assert_eq!(
+            expanded(
+                20,
+                &[compressed_speed_distance(300, 100)],
+                &mut accumulators
+            ),
+            vec![
+                ("speed".to_string(), Value::NumberValueF64(3.0)),
+                ("distance".to_string(), Value::NumberValueF64(262.25)),
+            ]
+        );
+    }
+
+    #[test]
+    fn fills_an_array_with_the_components_of_event_timestamp_12() {
+        // ten 12 bit timestamps in 1/1024 s, rolling over after the first one
+        let timestamps: Vec<u32> = (0..10).map(|i| 4000 + 100 * i).collect();
+        let mut bits: u128 = 0;
+        for (i, timestamp) in timestamps.iter().enumerate() {
+            bits |= u128::from(timestamp % 4096) << (12 * i);
+        }
+        let event_timestamp_12 = stored_field(10, &bits.to_le_bytes()[..15]);
+
+        let expected = timestamps
+            .iter()
+            .map(|&timestamp| f64::from(timestamp) / 1024.0)
+            .collect();
+        assert_eq!(
+            expanded(132, &[event_timestamp_12], &mut Accumulators::default()),
+            vec![(
+                "event_timestamp".to_string(),
+                Value::NumberValueVecF64(expected)
+            )]
+        );
+    }
+
+    #[test]
+    fn expands_the_components_selected_by_the_reference_field() {
+        let event = |event: u8| [stored_field(0, &[event]), stored_field(3, &[5, 28, 2, 50])];
+        let number = |name: &str, value: u32| (name.to_string(), Value::NumberValueU32(value));
+        let gears = vec![
+            number("rear_gear_num", 5),
+            number("rear_gear", 28),
+            number("front_gear_num", 2),
+            number("front_gear", 50),
+        ];
+        let mut accumulators = Accumulators::default();
+
+        // front_gear_change and rear_gear_change
+        assert_eq!(expanded(21, &event(42), &mut accumulators), gears);
+        assert_eq!(expanded(21, &event(43), &mut accumulators), gears);
+        // sport_point
+        assert_eq!(
+            expanded(21, &event(33), &mut accumulators),
+            vec![
+                number("score", 28 << 8 | 5),
+                number("opponent_score", 50 << 8 | 2)
+            ]
+        );
+        // timer events have no components
+        assert_eq!(expanded(21, &event(0), &mut accumulators), vec![]);
+    }
+}
@@ -0,0 +1,315 @@
+use std::collections::HashMap;
+use std::io::Read;
+use std::sync::Arc;
+
+use crate::crc::CrcReport;
+use crate::data_types::Value;
+use crate::error::FitError;
+use crate::fit_file::components::Accumulators;
+use crate::fit_file::fields::{DeveloperField, Field};
+use crate::fit_file::message::{FieldValue, Header, Message};
+use crate::fit_file::message_types::{FieldDefinition, MessageDefinition, MessageType};
+use crate::fit_file::FitFileConfig;
+use crate::fit_file_raw::DefinitionRecordRaw;
+use crate::record_header::{RecordHeader, TIMESTAMP_FIELD_NUMBER};
+use crate::record_reader::{next_chained_message, DataRecord, Record, RecordDecoder, RecordReader};
+
+/// Streaming decoder that reads the messages of a FIT file one at a time from any [Read].
+///
+/// Only the local message definitions and developer fields are kept as state,
+/// so the memory usage does not grow with the size of the file.
+/// Chained FIT files are decoded one after another when used as [Iterator],
+/// use [FitDecoder::next_file] and [FitDecoder::read_message] to process them separately.
+pub struct FitDecoder<'a, R: Read> {
+    records: RecordReader<R>,
+    pub(crate) config: &'a FitFileConfig,
+    /// absolute position of the record header of the last message
+    message_position: usize,
+    /// header of the current FIT file
+    header: Option<Header>,
+    /// the definitions of the local message numbers with the fields of the profile
+    local_message_types: HashMap<u8, Arc<MessageDefinition>>,
+    accumulators: Accumulators,
+}
+
+impl<'a, R: Read> FitDecoder<'a, R> {
+    pub fn new(reader: R, config: &'a FitFileConfig) -> FitDecoder<'a, R> {
+        FitDecoder {
+            records: RecordReader::new(reader),
+            config,
+            message_position: 0,
+            header: None,
+            local_message_types: HashMap::new(),
+            accumulators: Accumulators::default(),
+        }
+    }
+
+    /// header of the current FIT file, `None` before [FitDecoder::next_file] was called
+    pub fn header(&self) -> Option<&Header> {
+        self.header.as_ref()
+    }
+
+    /// CRC results of the current FIT file, the file CRC is available after its last message
+    pub fn crc(&self) -> &CrcReport {
+        self.records.crc()
+    }
+
+    /// developer fields described by the `field_description` messages of the current FIT file so far
+    pub fn developer_fields(&self) -> &[DeveloperField] {
+        self.records.developer_fields()
+    }
+
+    /// absolute byte position in the stream
+    pub fn position(&self) -> usize {
+        self.records.position()
+    }
+
+    /// absolute byte position of the record header of the last message that was read
+    pub fn message_position(&self) -> usize {
+        self.message_position
+    }
+
+    /// Skips the rest of the current FIT file and reads the header of the next one.
+    /// Returns `false` if the stream ends before another FIT file starts.
+    pub fn next_file(&mut self) -> Result<bool, FitError> {
+        let Some(header) = self.records.next_file(|header_info| {
+            let header = Header::read_header(header_info)?;
+            let content_size = header.content_size();
+            Ok((header, content_size))
+        })?
+        else {
+            return Ok(false);
+        };
+        if self.config.debug {
+            println!("{:?}", header);
+        }
+        self.header = Some(header);
+        self.local_message_types.clear();
+        self.accumulators.clear();
+        Ok(true)
+    }
+
+    /// Reads the next message of the current FIT file.
+    /// Returns `None` after the last message, when the file CRC was read.
+    pub fn read_message(&mut self) -> Result<Option<Message>, FitError> {
+        while let Some(record) = self.records.read_record()? {
+            let message = match record {
+                Record::Definition(definition) => {
+                    self.read_definition(&definition)?;
+                    None
+                }
+                Record::Data(record) => self.read_data(&record)?,
+            };
+            if message.is_some() {
+                return Ok(message);
+            }
+        }
+        if let (true, Some(file_crc)) = (self.config.debug, &self.crc().file) {
+            println!(
+                "CRC expected: {:#06x}, computed: {:#06x}",
+                file_crc.expected, file_crc.computed
+            );
+        }
+        Ok(None)
+    }
+
+    /// converts a data record, returns only messages that should be reported
+    fn read_data(&mut self, record: &DataRecord) -> Result<Option<Message>, FitError> {
+        let local_message_number = record.local_message_number();
+        // the reader only returns data records of defined local message numbers
+        let definition_message = self.local_message_types.get(&local_message_number).unwrap();
+        let (mut message, _) = definition_message
+            .read_message(
+                &0,
+                &record.data,
+                self.config,
+                &record.definition.architecture,
+                self.records.developer_fields(),
+                &mut self.accumulators,
+            )
+            .map_err(|error| error.shifted(record.data_position()))?;
+        message.local_message_number = local_message_number;
+        message.definition = Some(Arc::clone(definition_message));
+        if let Some(timestamp) = record.compressed_timestamp {
+            // the compressed header replaces the timestamp field of the message
+            let mut field = Field::resolve_field(
+                &message.message_type,
+                TIMESTAMP_FIELD_NUMBER,
+                self.config.profile_extension.as_deref(),
+            );
+            if self.config.numeric_timestamps {
+                field = field.without_date_time();
+            }
+            if !field.is_unknown() || self.config.include_unknown_fields {
+                message.data.data.insert(
+                    0,
+                    FieldValue {
+                        field,
+                        value: Value::NumberValueU32(timestamp),
+                    },
+                );
+            }
+        }
+        self.records.update_developer_data(record)?;
+        if !message.is_unknown() || self.config.include_unknown_message_types {
+            self.message_position = record.record_position;
+            Ok(Some(message))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// resolves the message type and fields of a definition with the profile
+    fn read_definition(&mut self, definition: &DefinitionRecordRaw) -> Result<(), FitError> {
+        let record_position = self.records.record_position();
+        definition.check_base_types(record_position)?;
+        let extension = self.config.profile_extension.as_deref();
+        let message_type = MessageType::resolve(definition.message_number, extension);
+        let fields = definition
+            .fields
+            .iter()
+            .map(|field_definition| FieldDefinition {
+                field: if field_definition.is_dev_field {
+                    Field::DeveloperField
+                } else {
+                    Field::resolve_field(&message_type, field_definition.number, extension)
+                },
+                number: field_definition.number,
+                size: field_definition.size,
+                base_type_value_or_dev_index: field_definition.base_type_value_or_dev_index,
+            })
+            .collect();
+        let local_message_number =
+            RecordHeader::parse(definition.record_header).local_message_number;
+        self.local_message_types.insert(
+            local_message_number,
+            Arc::new(MessageDefinition {
+                message_type,
+                fields,
+            }),
+        );
+        Ok(())
+    }
+}
+
+impl<R: Read> RecordDecoder for FitDecoder<'_, R> {
+    type Reader = R;
+    type Message = Message;
+
+    fn record_reader(&mut self) -> &mut RecordReader<R> {
+        &mut self.records
+    }
+
+    fn next_file(&mut self) -> Result<bool, FitError> {
+        FitDecoder::next_file(self)
+    }
+
+    fn read_message(&mut self) -> Result<Option<Message>, FitError> {
+        FitDecoder::read_message(self)
+    }
+}
+
+impl<R: Read> Iterator for FitDecoder<'_, R> {
+    type Item = Result<Message, FitError>;
+
+    /// returns the messages of all chained FIT files in the stream
+    fn next(&mut self) -> Option<Self::Item> {
+        next_chained_message(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_types::BaseType;
+    use crate::fit_encoder::{encode, EncodeMessage, FitEncoderConfig};
+    use crate::fit_file::FitFile;
+
+    #[test]
+    fn forgets_field_descriptions_of_a_redeclared_developer_data_index() {
+        let record = EncodeMessage::new(20).developer_field(
+            0,
+            0,
+            BaseType::UINT16,
+            Value::NumberValueU16(250),
+        );
+        let messages = [
+            EncodeMessage::developer_data_id(0, [1; 16]),
+            EncodeMessage::field_description(0, 0, &BaseType::UINT16, "Power", "W"),
+            record.clone(),
+            EncodeMessage::developer_data_id(0, [2; 16]),
+            record,
+        ];
+        let file = encode(&messages, FitEncoderConfig::default()).unwrap();
+        let config = FitFileConfig::default();
+        let mut decoder = FitDecoder::new(file.as_slice(), &config);
+        assert!(decoder.next_file().unwrap());
+
+        let mut powers = vec![];
+        while let Some(message) = decoder.read_message().unwrap() {
+            if message.message_type.number == 20 {
+                powers.push(message.data.value("Power").cloned());
+                assert_eq!(
+                    decoder.developer_fields().len(),
+                    usize::from(powers.len() == 1)
+                );
+            }
+        }
+        assert_eq!(powers, vec![Some(Value::NumberValueU16(250)), None]);
+    }
+
+    #[test]
+    fn decodes_bitfields_into_their_flags() {
+        let messages = [
+            EncodeMessage::by_name("capabilities")
+                .unwrap()
+                .field_with_type(
+                    1,
+                    BaseType::UINT8Z,
+                    Value::NumberValueVecU8(vec![0x06, 0x01]),
+                    None,
+                ),
+            EncodeMessage::by_name("file_capabilities")
+                .unwrap()
+                .field_with_type(1, BaseType::UINT8Z, Value::NumberValueU8(0x0A), None),
+            EncodeMessage::by_name("device_settings")
+                .unwrap()
+                .field(90, Value::NumberValueU32(0x03)),
+        ];
+        let file = encode(&messages, FitEncoderConfig::default()).unwrap();
+
+        let fit_file = FitFile::from(&file, &FitFileConfig::default()).unwrap();
+        assert_eq!(
+            serde_json::to_string(&fit_file.messages).unwrap(),
+            r#"[{"message_type":"Capabilities","message":{"sports":["Running","Cycling","Tennis"]}},{"message_type":"File capabilities","message":{"flags":["Read","Erase"]}},{"message_type":"Device settings","message":{"auto_activity_detect":["Running","Cycling"]}}]"#
+        );
+    }
+
+    #[test]
+    fn selects_subfields_by_their_reference_fields() {
+        let file_id = |manufacturer| {
+            EncodeMessage::by_name("file_id")
+                .unwrap()
+                .field(2, Value::NumberValueU16(3121))
+                .field(1, Value::NumberValueU16(manufacturer))
+        };
+        let messages = [
+            // the reference field may follow the field with subfields
+            file_id(1),
+            // development is not a reference value of garmin_product
+            file_id(255),
+            EncodeMessage::by_name("event")
+                .unwrap()
+                .field(0, Value::EnumValue(vec![0]))
+                .field(1, Value::EnumValue(vec![0]))
+                .field(3, Value::NumberValueU32(1)),
+        ];
+        let file = encode(&messages, FitEncoderConfig::default()).unwrap();
+
+        let fit_file = FitFile::from(&file, &FitFileConfig::default()).unwrap();
+        assert_eq!(
+            serde_json::to_string(&fit_file.messages).unwrap(),
+            r#"[{"message_type":"File Id","message":{"garmin_product":"EdgeFivethree0","manufacturer":"Garmin"}},{"message_type":"File Id","message":{"product":3121,"manufacturer":"Development"}},{"message_type":"Event","message":{"event":"Timer","event_type":"Start","timer_trigger":"Auto"}}]"#
+        );
+    }
+}
@@ -1,16 +1,290 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
 use std::hash::{Hash, Hasher};
 use std::string::ToString;
+use std::sync::Arc;
 
 use serde::{Serialize, Serializer};
+use crate::data_types::{BaseType, Value};
+use crate::date_time;
+use crate::fit_file::key_value_enum::snake_case;
+use crate::fit_file::message::Messages;
 use crate::fit_file::types::*;
-use crate::fit_file::MessageType;
+use crate::fit_file::{MessageType, ProfileExtension};
+use crate::fit_file_raw::MessageRaw;
 
 pub struct DeveloperField {
     pub developer_data_index: u8,
     pub field_definition_number: u8,
     pub fit_base_type_id: u8,
     pub field_name: String,
+    /// units of the values, e.g. `Watts`
+    pub units: Option<String>,
+    pub scale: Option<f64>,
+    pub offset: Option<f64>,
+    /// number of elements if the values are arrays
+    pub array: Option<u8>,
+    /// message number of the profile field the developer field provides, e.g. 20 for `record`
+    pub native_mesg_num: Option<u16>,
+    /// field number of the profile field the developer field provides, e.g. 7 for `power`
+    pub native_field_num: Option<u8>,
+    /// the `developer_data_id` message of the application that wrote the field
+    pub developer_data_id: Option<DeveloperDataId>,
+}
+
+impl DeveloperField {
+    /// creates the developer field from a `field_description` message
+    pub fn from(field_description: &Messages) -> Result<DeveloperField, String> {
+        let value = |field_name: &str| {
+            field_description
+                .value(field_name)
+                .ok_or(format!("missing {}", field_name))
+        };
+        let Value::NumberValueU8(developer_data_index) = value("developer_data_index")? else {
+            return Err(format!(
+                "expected u8 for developer_data_index, got {:?}",
+                value("developer_data_index")?
+            ));
+        };
+        let Value::NumberValueU8(field_definition_number) = value("field_definition_number")?
+        else {
+            return Err(format!(
+                "expected u8 for field_definition_number, got {:?}",
+                value("field_definition_number")?
+            ));
+        };
+        let Value::StringValue(field_name) = value("field_name")? else {
+            return Err(format!(
+                "expected string for field_name, got {:?}",
+                value("field_name")?
+            ));
+        };
+        let Value::NumberValueU8(fit_base_type_id) = value("fit_base_type_id")? else {
+            return Err(format!(
+                "expected u8 for fit_base_type_id, got {:?}",
+                value("fit_base_type_id")?
+            ));
+        };
+        let number = |field_name: &str| field_description.value(field_name).and_then(as_number);
+        Ok(DeveloperField {
+            field_name: field_name.clone(),
+            field_definition_number: *field_definition_number,
+            developer_data_index: *developer_data_index,
+            fit_base_type_id: *fit_base_type_id,
+            units: match field_description.value("units") {
+                Some(Value::StringValue(units)) if !units.is_empty() => Some(units.clone()),
+                _ => None,
+            },
+            scale: number("scale"),
+            offset: number("offset"),
+            array: number("array").map(|array| array as u8),
+            native_mesg_num: number("native_mesg_num").map(|number| number as u16),
+            native_field_num: number("native_field_num").map(|number| number as u8),
+            developer_data_id: None,
+        })
+    }
+
+    /// creates the developer field from a raw `field_description` message
+    pub fn from_raw(field_description: &MessageRaw) -> Result<DeveloperField, String> {
+        let value = |field_number: u8, field_name: &str| {
+            field_description
+                .fields
+                .data
+                .iter()
+                .find(|entry| {
+                    entry.field_number == field_number && entry.developer_data_index.is_none()
+                })
+                .map(|entry| &entry.value)
+                .ok_or(format!("missing {}", field_name))
+        };
+        let number = |field_number: u8, field_name: &str| match value(field_number, field_name)? {
+            Value::NumberValueU8(number) => Ok(*number),
+            value => Err(format!("expected u8 for {}, got {:?}", field_name, value)),
+        };
+        let Value::StringValue(field_name) = value(3, "field_name")? else {
+            return Err(format!(
+                "expected string for field_name, got {:?}",
+                value(3, "field_name")?
+            ));
+        };
+        let optional_number = |field_number: u8| value(field_number, "").ok().and_then(as_number);
+        Ok(DeveloperField {
+            field_name: field_name.clone(),
+            field_definition_number: number(1, "field_definition_number")?,
+            developer_data_index: number(0, "developer_data_index")?,
+            fit_base_type_id: number(2, "fit_base_type_id")?,
+            units: match value(8, "units") {
+                Ok(Value::StringValue(units)) if !units.is_empty() => Some(units.clone()),
+                _ => None,
+            },
+            scale: optional_number(6),
+            offset: optional_number(7),
+            array: optional_number(4).map(|array| array as u8),
+            native_mesg_num: optional_number(14).map(|number| number as u16),
+            native_field_num: optional_number(15).map(|number| number as u8),
+            developer_data_id: None,
+        })
+    }
+
+    /// `true` if the description declares the values as arrays
+    pub fn is_array(&self) -> bool {
+        self.array.is_some_and(|array| array > 0)
+    }
+
+    /// the value as read, as array of elements if the description declares an array,
+    /// so that a single element is an array as well
+    pub fn elements(&self, value: Value) -> Value {
+        if self.is_array() {
+            value.into_array()
+        } else {
+            value
+        }
+    }
+
+    /// scale, offset and units of the values, `None` if the description defines none of them
+    pub fn scale(&self) -> Option<Scale> {
+        if self.scale.is_none() && self.offset.is_none() && self.units.is_none() {
+            return None;
+        }
+        Some(Scale::from(
+            self.scale.filter(|&scale| scale != 0.0).unwrap_or(1.0),
+            self.offset.unwrap_or(0.0),
+            self.units.clone().unwrap_or_default(),
+        ))
+    }
+
+    /// The profile field the developer field provides, e.g. `power` of `record` for the power of a Stryd.
+    /// Without `native_mesg_num` it is a field of the message the developer field is part of.
+    /// The profile extension resolves the message types and fields it adds.
+    pub fn native_field(
+        &self,
+        message_type: &MessageType,
+        extension: Option<&ProfileExtension>,
+    ) -> Option<(MessageType, Field)> {
+        let native_field_num = self.native_field_num?;
+        let native_message_type = match self.native_mesg_num {
+            Some(native_mesg_num) => MessageType::resolve(native_mesg_num, extension),
+            None => message_type.clone(),
+        };
+        let field = Field::resolve_field(&native_message_type, native_field_num, extension);
+        (!field.is_unknown()).then_some((native_message_type, field))
+    }
+
+    /// name of the profile field the developer field provides,
+    /// prefixed by the message type if it belongs to another message, e.g. `power` or `Record.power`
+    pub fn native_field_name(
+        &self,
+        message_type: &MessageType,
+        extension: Option<&ProfileExtension>,
+    ) -> Option<String> {
+        let (native_message_type, field) = self.native_field(message_type, extension)?;
+        let name = field.name()?;
+        if native_message_type == *message_type {
+            Some(name.to_string())
+        } else {
+            Some(format!("{}.{}", native_message_type.name, name))
+        }
+    }
+
+    /// identifier of the application that wrote the field, see `DeveloperDataId::app_id`
+    pub fn app_id(&self) -> String {
+        match &self.developer_data_id {
+            Some(developer_data_id) => developer_data_id.app_id(),
+            None => format!("developer_data_index:{}", self.developer_data_index),
+        }
+    }
+}
+
+/// The application that writes developer fields, e.g. a Connect IQ data field, from a `developer_data_id` message.
+#[derive(Clone)]
+pub struct DeveloperDataId {
+    pub developer_data_index: u8,
+    /// 16 bytes, the UUID of a Connect IQ app
+    pub application_id: Option<Vec<u8>>,
+    pub manufacturer_id: Option<u16>,
+    pub developer_id: Option<Vec<u8>>,
+    pub application_version: Option<u32>,
+}
+
+impl DeveloperDataId {
+    /// creates the application from a `developer_data_id` message
+    pub fn from(developer_data_id: &Messages) -> Result<DeveloperDataId, String> {
+        let value = |field_name: &str| developer_data_id.value(field_name);
+        Ok(DeveloperDataId {
+            developer_data_index: value("developer_data_index")
+                .and_then(as_number)
+                .ok_or("missing developer_data_index")? as u8,
+            application_id: value("application_id").and_then(as_bytes),
+            manufacturer_id: value("manufacturer_id")
+                .and_then(as_number)
+                .map(|number| number as u16),
+            developer_id: value("developer_id").and_then(as_bytes),
+            application_version: value("application_version")
+                .and_then(as_number)
+                .map(|number| number as u32),
+        })
+    }
+
+    /// creates the application from a raw `developer_data_id` message
+    pub fn from_raw(developer_data_id: &MessageRaw) -> Result<DeveloperDataId, String> {
+        let value = |field_number: u8| {
+            developer_data_id
+                .fields
+                .data
+                .iter()
+                .find(|entry| {
+                    entry.field_number == field_number && entry.developer_data_index.is_none()
+                })
+                .map(|entry| &entry.value)
+        };
+        Ok(DeveloperDataId {
+            developer_data_index: value(3)
+                .and_then(as_number)
+                .ok_or("missing developer_data_index")? as u8,
+            application_id: value(1).and_then(as_bytes),
+            manufacturer_id: value(2).and_then(as_number).map(|number| number as u16),
+            developer_id: value(0).and_then(as_bytes),
+            application_version: value(4).and_then(as_number).map(|number| number as u32),
+        })
+    }
+
+    /// The `application_id` as UUID, e.g. `0c7a1ea5-6dc7-4a36-9c5b-2f3d5c1f6a20`,
+    /// without one the `developer_id` and without both `developer_data_index:<index>`.
+    pub fn app_id(&self) -> String {
+        match (&self.application_id, &self.developer_id) {
+            (Some(id), _) | (None, Some(id)) => uuid(id),
+            (None, None) => format!("developer_data_index:{}", self.developer_data_index),
+        }
+    }
+}
+
+/// 16 bytes in the hyphenated form of a UUID, other lengths as plain hex
+fn uuid(bytes: &[u8]) -> String {
+    let mut uuid = String::new();
+    for (i, byte) in bytes.iter().enumerate() {
+        if bytes.len() == 16 && [4, 6, 8, 10].contains(&i) {
+            uuid.push('-');
+        }
+        uuid.push_str(&format!("{:02x}", byte));
+    }
+    uuid
+}
+
+/// a single number, also a single element of an enum array
+fn as_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::EnumValue(values) if values.len() == 1 => Some(f64::from(values[0])),
+        value => value.as_f64(),
+    }
+}
+
+/// the bytes of a byte array like `application_id`
+fn as_bytes(value: &Value) -> Option<Vec<u8>> {
+    match value {
+        Value::EnumValue(bytes) | Value::NumberValueVecU8(bytes) => Some(bytes.clone()),
+        _ => None,
+    }
 }
 
 impl Clone for DeveloperField {
@@ -20,10 +294,18 @@ impl Clone for DeveloperField {
             field_definition_number: self.field_definition_number,
             fit_base_type_id: self.fit_base_type_id,
             field_name: self.field_name.clone(),
+            units: self.units.clone(),
+            scale: self.scale,
+            offset: self.offset,
+            array: self.array,
+            native_mesg_num: self.native_mesg_num,
+            native_field_num: self.native_field_num,
+            developer_data_id: self.developer_data_id.clone(),
         }
     }
 }
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
+#[allow(clippy::enum_variant_names)]
 pub enum Field {
     Unknown(UnknownField),
     EnumField(EnumField),
@@ -38,7 +320,7 @@ impl Serialize for Field {
         match self {
             Field::Unknown(value) => serializer.serialize_some(value),
             Field::EnumField(value) => serializer.serialize_str(&value.name),
-            Field::ValueField(value) => serializer.serialize_str(&value.name),
+            Field::ValueField(value) => serializer.serialize_str(&value.key()),
             Field::DeveloperField => serializer.serialize_str("dev field"),
         }
     }
@@ -73,17 +355,126 @@ impl Serialize for UnknownField {
         serializer.serialize_str(serialized.as_str())
     }
 }
+/// returns the names of the flags that are set in the elements of a bitfield
+pub type TranslateFlags = fn(&[u32]) -> Vec<String>;
+
 #[derive(Clone, Debug)]
 pub struct EnumField {
     pub name: String,
-    pub translate_enum: fn(&u32) -> String,
+    /// name of the profile type, e.g. `Sport` or `DateTime`
+    pub type_name: Cow<'static, str>,
+    /// name of the enum value, `None` if the value is not defined by the profile
+    pub translate_enum: fn(&u32) -> Option<String>,
+    /// names of the flags set in all elements, `None` if the field is no bitfield
+    pub translate_flags: Option<TranslateFlags>,
+    /// name of the enum value in the FIT profile, e.g. `edge_530` for `EdgeFivethree0`
+    pub profile_name_enum: fn(&u32) -> Option<String>,
+    /// value of a name returned by `translate_enum` or `profile_name_enum`
+    pub parse_enum: fn(&str) -> Option<u32>,
+    /// the table of a [ProfileExtension](crate::fit_file::ProfileExtension) for the type,
+    /// its values are translated before the ones of the profile
+    pub extension_values: Option<Arc<HashMap<u32, String>>>,
 }
 
 impl EnumField {
-    const fn from(name: String, translate_enum: fn(&u32) -> String) -> Self {
+    const fn from(
+        name: String,
+        type_name: &'static str,
+        translate_enum: fn(&u32) -> Option<String>,
+        profile_name_enum: fn(&u32) -> Option<String>,
+        parse_enum: fn(&str) -> Option<u32>,
+    ) -> Self {
         EnumField {
             name,
+            type_name: Cow::Borrowed(type_name),
             translate_enum,
+            translate_flags: None,
+            profile_name_enum,
+            parse_enum,
+            extension_values: None,
+        }
+    }
+
+    const fn bitfield(
+        name: String,
+        type_name: &'static str,
+        translate_flags: TranslateFlags,
+    ) -> Self {
+        EnumField {
+            name,
+            type_name: Cow::Borrowed(type_name),
+            translate_enum: |_| None,
+            translate_flags: Some(translate_flags),
+            profile_name_enum: |_| None,
+            parse_enum: |_| None,
+            extension_values: None,
+        }
+    }
+
+    /// enum field of a [ProfileExtension](crate::fit_file::ProfileExtension), translated by its table of the type
+    pub(crate) fn extension(
+        name: String,
+        type_name: String,
+        extension_values: Option<Arc<HashMap<u32, String>>>,
+    ) -> Self {
+        EnumField {
+            name,
+            type_name: Cow::Owned(type_name),
+            translate_enum: |_| None,
+            translate_flags: None,
+            profile_name_enum: |_| None,
+            parse_enum: |_| None,
+            extension_values,
+        }
+    }
+
+    /// name of the value, by the table of the [ProfileExtension](crate::fit_file::ProfileExtension) before the profile type
+    pub fn translate(&self, value: &u32) -> Option<String> {
+        self.extension_values
+            .as_ref()
+            .and_then(|values| values.get(value).cloned())
+            .or_else(|| (self.translate_enum)(value))
+    }
+
+    /// name of the value in the snake case of the FIT profile, like the FIT SDK writes it,
+    /// by the table of the [ProfileExtension](crate::fit_file::ProfileExtension) before the profile type
+    pub fn profile_name(&self, value: &u32) -> Option<String> {
+        self.extension_values
+            .as_ref()
+            .and_then(|values| values.get(value).map(|name| snake_case(name)))
+            .or_else(|| (self.profile_name_enum)(value))
+    }
+
+    /// value of a name, by the table of the [ProfileExtension](crate::fit_file::ProfileExtension) before the profile type
+    pub fn parse(&self, name: &str) -> Option<u32> {
+        self.extension_values
+            .as_ref()
+            .and_then(|values| {
+                values
+                    .iter()
+                    .find(|(_, value_name)| {
+                        value_name.as_str() == name || snake_case(value_name) == name
+                    })
+                    .map(|(&value, _)| value)
+            })
+            .or_else(|| (self.parse_enum)(name))
+    }
+
+    pub fn is_bitfield(&self) -> bool {
+        self.translate_flags.is_some()
+    }
+
+    /// `date_time` and `local_date_time` fields are seconds since the FIT epoch, see [crate::date_time]
+    pub fn is_date_time(&self) -> bool {
+        matches!(self.type_name.as_ref(), "DateTime" | "LocalDateTime")
+    }
+
+    /// the value as RFC 3339 timestamp, `None` if the field is no date time or the value is a system time
+    pub fn format_date_time(&self, value: u32) -> Option<String> {
+        match self.type_name.as_ref() {
+            "DateTime" => date_time::date_time_to_rfc3339(value),
+            "LocalDateTime" => date_time::local_date_time_to_rfc3339(value),
+            _ => None,
         }
     }
 }
@@ -108,9 +499,93 @@ impl Serialize for EnumField {
     }
 }
 
+/// Scale, offset and units of a field as defined by the FIT profile.
+/// The physical value is `stored value / scale - offset`.
+#[derive(Clone, Debug)]
+pub struct Scale {
+    pub scale: f64,
+    pub offset: f64,
+    pub units: String,
+}
+
+impl Scale {
+    pub fn from(scale: f64, offset: f64, units: String) -> Self {
+        Scale {
+            scale,
+            offset,
+            units,
+        }
+    }
+
+    /// converts a stored value into its physical value, values that are not numeric are returned unchanged
+    pub fn apply(&self, value: &Value) -> Value {
+        if self.scale == 1.0 && self.offset == 0.0 {
+            return value.clone();
+        }
+        // subtracting the scaled offset first avoids rounding noise like 123.39999999999998
+        let convert = |stored: f64| (stored - self.offset * self.scale) / self.scale;
+        if let Some(stored) = value.as_f64() {
+            Value::NumberValueF64(convert(stored))
+        } else if let Some(stored) = value.as_f64_vec() {
+            Value::NumberValueVecF64(stored.into_iter().map(convert).collect())
+        } else {
+            value.clone()
+        }
+    }
+
+    /// converts a physical value back into the stored value, the inverse of [Scale::apply],
+    /// rounded for base types that are no floats
+    pub fn unapply(&self, value: &Value, base_type: &BaseType) -> Value {
+        if self.scale == 1.0 && self.offset == 0.0 {
+            return value.clone();
+        }
+        let convert = |physical: f64| {
+            let stored = physical * self.scale + self.offset * self.scale;
+            if base_type.is_float() {
+                stored
+            } else {
+                stored.round()
+            }
+        };
+        if let Some(physical) = value.as_f64() {
+            Value::NumberValueF64(convert(physical))
+        } else if let Some(physical) = value.as_f64_vec() {
+            Value::NumberValueVecF64(physical.into_iter().map(convert).collect())
+        } else {
+            value.clone()
+        }
+    }
+
+    pub fn is_semicircles(&self) -> bool {
+        self.units == "semicircles"
+    }
+
+    /// units as part of a JSON key, e.g. `m_per_s` for `m/s`, `None` for fields without units
+    pub fn units_key(&self) -> Option<String> {
+        if self.units.is_empty() {
+            return None;
+        }
+        let mut key = String::new();
+        for character in self.units.to_lowercase().chars() {
+            match character {
+                '/' => key.push_str("_per_"),
+                '%' => key.push_str("percent"),
+                '^' => {}
+                character if character.is_ascii_alphanumeric() => key.push(character),
+                _ => key.push('_'),
+            }
+        }
+        Some(key)
+    }
+}
+
 #[derive(Clone)]
 pub struct ValueField {
     pub name: String,
+    /// `None` if the profile defines neither scale, offset nor units or raw values are requested
+    pub scale: Option<Scale>,
+    /// the profile field a developer field provides, see [DeveloperField::native_field_name]
+    pub native_field: Option<String>,
 }
 
 impl Hash for ValueField {
@@ -138,21 +613,79 @@ impl Serialize for ValueField {
     where
         S: Serializer,
     {
-        serializer.serialize_str(&self.name)
+        serializer.serialize_str(&self.key())
     }
 }
 
 impl ValueField {
-    const fn from(name: String) -> Self {
-        ValueField { name }
+    pub const fn from(name: String, scale: Option<Scale>) -> Self {
+        ValueField {
+            name,
+            scale,
+            native_field: None,
+        }
+    }
+
+    /// name with the units as suffix, e.g. `altitude_m`, names like `cycles` already contain their units
+    pub fn key(&self) -> String {
+        match self.scale.as_ref().and_then(Scale::units_key) {
+            Some(units) if self.name != units && !self.name.ends_with(&format!("_{}", units)) => {
+                format!("{}_{}", self.name, units)
+            }
+            _ => self.name.clone(),
+        }
     }
 }
 
 impl Field {
     pub fn is_unknown(&self) -> bool {
+        matches!(self, Field::Unknown(_))
+    }
+
+    /// profile name of the field, `None` for unknown and developer fields
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            Field::EnumField(enum_field) => Some(&enum_field.name),
+            Field::ValueField(value_field) => Some(&value_field.name),
+            Field::Unknown(_) | Field::DeveloperField => None,
+        }
+    }
+
+    /// the field of the [ProfileExtension](crate::fit_file::ProfileExtension), if any, or of the FIT profile
+    pub fn resolve_field(
+        message_type: &MessageType,
+        field_number: u8,
+        extension: Option<&ProfileExtension>,
+    ) -> Field {
+        let Some(extension) = extension else {
+            return Field::resolve_profile_field(message_type, field_number);
+        };
+        extension
+            .field(message_type.number, field_number)
+            .unwrap_or_else(|| {
+                extension.extend(Field::resolve_profile_field(message_type, field_number))
+            })
+    }
+
+    /// resolves a field of the FIT profile by its name together with its number,
+    /// subfields come with the number of the field they belong to
+    pub fn resolve_by_name(message_type: &MessageType, field_name: &str) -> Option<(u8, Field)> {
+        (0..u8::MAX)
+            .map(|field_number| {
+                let field = Field::resolve_profile_field(message_type, field_number);
+                (field_number, field)
+            })
+            .find(|(_, field)| field.name() == Some(field_name))
+            .or_else(|| Field::resolve_subfield_by_name(message_type.number, field_name))
+    }
+
+    /// `date_time` and `local_date_time` fields become plain value fields that keep the seconds since the FIT epoch
+    pub fn without_date_time(self) -> Field {
         match self {
-            Field::Unknown(_) => true,
-            _ => false,
+            Field::EnumField(enum_field) if enum_field.is_date_time() => {
+                Field::ValueField(ValueField::from(enum_field.name, None))
+            }
+            field => field,
         }
     }
 
@@ -171,7 +704,6 @@ impl Field {
     35,3,"version"
     35,5,"part_number"
     106,1,"product"
-    1,0,"languages"
     37,2,"directory"
     37,3,"max_count"
     37,4,"max_size"
@@ -192,16 +724,16 @@ impl Field {
     2,86,"ble_auto_upload_enabled"
     2,94,"number_of_screens"
     3,0,"friendly_name"
-    3,2,"age"
-    3,3,"height"
-    3,4,"weight"
-    3,8,"resting_heart_rate"
-    3,9,"default_max_running_heart_rate"
-    3,10,"default_max_biking_heart_rate"
-    3,11,"default_max_heart_rate"
+    3,2,"age",1,0,"years"
+    3,3,"height",100,0,"m"
+    3,4,"weight",10,0,"kg"
+    3,8,"resting_heart_rate",1,0,"bpm"
+    3,9,"default_max_running_heart_rate",1,0,"bpm"
+    3,10,"default_max_biking_heart_rate",1,0,"bpm"
+    3,11,"default_max_heart_rate",1,0,"bpm"
     3,23,"global_id"
-    3,31,"user_running_step_length"
-    3,32,"user_walking_step_length"
+    3,31,"user_running_step_length",1000,0,"m"
+    3,32,"user_walking_step_length",1000,0,"m"
     3,49,"dive_count"
     4,0,"enabled"
     4,1,"hrm_ant_id"
@@ -269,17 +801,17 @@ impl Field {
     216,12,"resting_heart_rate"
     216,13,"threshold_heart_rate"
     216,15,"functional_threshold_power"
-    7,1,"max_heart_rate"
-    7,2,"threshold_heart_rate"
-    7,3,"functional_threshold_power"
+    7,1,"max_heart_rate",1,0,"bpm"
+    7,2,"threshold_heart_rate",1,0,"bpm"
+    7,3,"functional_threshold_power",1,0,"watts"
     12,3,"name"
-    8,1,"high_bpm"
+    8,1,"high_bpm",1,0,"bpm"
     8,2,"name"
     53,0,"high_value"
     53,1,"name"
     131,0,"high_value"
     131,1,"name"
-    9,1,"high_value"
+    9,1,"high_value",1,0,"watts"
     9,2,"name"
     10,1,"high_bpm"
     10,2,"calories"
@@ -331,357 +863,357 @@ impl Field {
     15,7,"target_value"
     15,9,"recurrence_value"
     15,10,"enabled"
-    34,0,"total_timer_time"
+    34,0,"total_timer_time",1000,0,"s"
     34,1,"num_sessions"
     34,6,"event_group"
-    18,3,"start_position_lat"
-    18,4,"start_position_long"
-    18,7,"total_elapsed_time"
-    18,8,"total_timer_time"
-    18,9,"total_distance"
-    18,10,"total_cycles"
-    18,11,"total_calories"
-    18,13,"total_fat_calories"
-    18,14,"avg_speed"
-    18,15,"max_speed"
-    18,16,"avg_heart_rate"
-    18,17,"max_heart_rate"
-    18,18,"avg_cadence"
-    18,19,"max_cadence"
-    18,20,"avg_power"
-    18,21,"max_power"
-    18,22,"total_ascent"
-    18,23,"total_descent"
-    18,24,"total_training_effect"
+    18,3,"start_position_lat",1,0,"semicircles"
+    18,4,"start_position_long",1,0,"semicircles"
+    18,7,"total_elapsed_time",1000,0,"s"
+    18,8,"total_timer_time",1000,0,"s"
+    18,9,"total_distance",100,0,"m"
+    18,10,"total_cycles",1,0,"cycles"
+    18,11,"total_calories",1,0,"kcal"
+    18,13,"total_fat_calories",1,0,"kcal"
+    18,14,"avg_speed",1000,0,"m/s"
+    18,15,"max_speed",1000,0,"m/s"
+    18,16,"avg_heart_rate",1,0,"bpm"
+    18,17,"max_heart_rate",1,0,"bpm"
+    18,18,"avg_cadence",1,0,"rpm"
+    18,19,"max_cadence",1,0,"rpm"
+    18,20,"avg_power",1,0,"watts"
+    18,21,"max_power",1,0,"watts"
+    18,22,"total_ascent",1,0,"m"
+    18,23,"total_descent",1,0,"m"
+    18,24,"total_training_effect",10,0,""
     18,25,"first_lap_index"
     18,26,"num_laps"
     18,27,"event_group"
-    18,29,"nec_lat"
-    18,30,"nec_long"
-    18,31,"swc_lat"
-    18,32,"swc_long"
+    18,29,"nec_lat",1,0,"semicircles"
+    18,30,"nec_long",1,0,"semicircles"
+    18,31,"swc_lat",1,0,"semicircles"
+    18,32,"swc_long",1,0,"semicircles"
     18,33,"num_lengths"
-    18,34,"normalized_power"
-    18,35,"training_stress_score"
-    18,36,"intensity_factor"
-    18,38,"end_position_lat"
-    18,39,"end_position_long"
-    18,41,"avg_stroke_count"
-    18,42,"avg_stroke_distance"
-    18,44,"pool_length"
-    18,45,"threshold_power"
+    18,34,"normalized_power",1,0,"watts"
+    18,35,"training_stress_score",10,0,"tss"
+    18,36,"intensity_factor",1000,0,"if"
+    18,38,"end_position_lat",1,0,"semicircles"
+    18,39,"end_position_long",1,0,"semicircles"
+    18,41,"avg_stroke_count",10,0,"strokes/lap"
+    18,42,"avg_stroke_distance",100,0,"m"
+    18,44,"pool_length",100,0,"m"
+    18,45,"threshold_power",1,0,"watts"
     18,47,"num_active_lengths"
-    18,48,"total_work"
-    18,49,"avg_altitude"
-    18,50,"max_altitude"
-    18,51,"gps_accuracy"
-    18,52,"avg_grade"
-    18,53,"avg_pos_grade"
-    18,54,"avg_neg_grade"
-    18,55,"max_pos_grade"
-    18,56,"max_neg_grade"
-    18,57,"avg_temperature"
-    18,58,"max_temperature"
-    18,59,"total_moving_time"
-    18,60,"avg_pos_vertical_speed"
-    18,61,"avg_neg_vertical_speed"
-    18,62,"max_pos_vertical_speed"
-    18,63,"max_neg_vertical_speed"
-    18,64,"min_heart_rate"
-    18,65,"time_in_hr_zone"
-    18,66,"time_in_speed_zone"
-    18,67,"time_in_cadence_zone"
-    18,68,"time_in_power_zone"
-    18,69,"avg_lap_time"
+    18,48,"total_work",1,0,"J"
+    18,49,"avg_altitude",5,500,"m"
+    18,50,"max_altitude",5,500,"m"
+    18,51,"gps_accuracy",1,0,"m"
+    18,52,"avg_grade",100,0,"%"
+    18,53,"avg_pos_grade",100,0,"%"
+    18,54,"avg_neg_grade",100,0,"%"
+    18,55,"max_pos_grade",100,0,"%"
+    18,56,"max_neg_grade",100,0,"%"
+    18,57,"avg_temperature",1,0,"C"
+    18,58,"max_temperature",1,0,"C"
+    18,59,"total_moving_time",1000,0,"s"
+    18,60,"avg_pos_vertical_speed",1000,0,"m/s"
+    18,61,"avg_neg_vertical_speed",1000,0,"m/s"
+    18,62,"max_pos_vertical_speed",1000,0,"m/s"
+    18,63,"max_neg_vertical_speed",1000,0,"m/s"
+    18,64,"min_heart_rate",1,0,"bpm"
+    18,65,"time_in_hr_zone",1000,0,"s"
+    18,66,"time_in_speed_zone",1000,0,"s"
+    18,67,"time_in_cadence_zone",1000,0,"s"
+    18,68,"time_in_power_zone",1000,0,"s"
+    18,69,"avg_lap_time",1000,0,"s"
     18,70,"best_lap_index"
-    18,71,"min_altitude"
+    18,71,"min_altitude",5,500,"m"
     18,82,"player_score"
     18,83,"opponent_score"
     18,84,"opponent_name"
     18,85,"stroke_count"
     18,86,"zone_count"
-    18,87,"max_ball_speed"
-    18,88,"avg_ball_speed"
-    18,89,"avg_vertical_oscillation"
-    18,90,"avg_stance_time_percent"
-    18,91,"avg_stance_time"
-    18,92,"avg_fractional_cadence"
-    18,93,"max_fractional_cadence"
-    18,94,"total_fractional_cycles"
-    18,95,"avg_total_hemoglobin_conc"
-    18,96,"min_total_hemoglobin_conc"
-    18,97,"max_total_hemoglobin_conc"
-    18,98,"avg_saturated_hemoglobin_percent"
-    18,99,"min_saturated_hemoglobin_percent"
-    18,100,"max_saturated_hemoglobin_percent"
-    18,101,"avg_left_torque_effectiveness"
-    18,102,"avg_right_torque_effectiveness"
-    18,103,"avg_left_pedal_smoothness"
-    18,104,"avg_right_pedal_smoothness"
-    18,105,"avg_combined_pedal_smoothness"
+    18,87,"max_ball_speed",100,0,"m/s"
+    18,88,"avg_ball_speed",100,0,"m/s"
+    18,89,"avg_vertical_oscillation",10,0,"mm"
+    18,90,"avg_stance_time_percent",100,0,"percent"
+    18,91,"avg_stance_time",10,0,"ms"
+    18,92,"avg_fractional_cadence",128,0,"rpm"
+    18,93,"max_fractional_cadence",128,0,"rpm"
+    18,94,"total_fractional_cycles",128,0,"cycles"
+    18,95,"avg_total_hemoglobin_conc",100,0,"g/dL"
+    18,96,"min_total_hemoglobin_conc",100,0,"g/dL"
+    18,97,"max_total_hemoglobin_conc",100,0,"g/dL"
+    18,98,"avg_saturated_hemoglobin_percent",10,0,"%"
+    18,99,"min_saturated_hemoglobin_percent",10,0,"%"
+    18,100,"max_saturated_hemoglobin_percent",10,0,"%"
+    18,101,"avg_left_torque_effectiveness",2,0,"percent"
+    18,102,"avg_right_torque_effectiveness",2,0,"percent"
+    18,103,"avg_left_pedal_smoothness",2,0,"percent"
+    18,104,"avg_right_pedal_smoothness",2,0,"percent"
+    18,105,"avg_combined_pedal_smoothness",2,0,"percent"
     18,110,"sport_profile_name"
     18,111,"sport_index"
-    18,112,"time_standing"
+    18,112,"time_standing",1000,0,"s"
     18,113,"stand_count"
-    18,114,"avg_left_pco"
-    18,115,"avg_right_pco"
-    18,116,"avg_left_power_phase"
-    18,117,"avg_left_power_phase_peak"
-    18,118,"avg_right_power_phase"
-    18,119,"avg_right_power_phase_peak"
-    18,120,"avg_power_position"
-    18,121,"max_power_position"
-    18,122,"avg_cadence_position"
-    18,123,"max_cadence_position"
-    18,124,"enhanced_avg_speed"
-    18,125,"enhanced_max_speed"
-    18,126,"enhanced_avg_altitude"
-    18,127,"enhanced_min_altitude"
-    18,128,"enhanced_max_altitude"
-    18,129,"avg_lev_motor_power"
-    18,130,"max_lev_motor_power"
-    18,131,"lev_battery_consumption"
-    18,132,"avg_vertical_ratio"
-    18,133,"avg_stance_time_balance"
-    18,134,"avg_step_length"
-    18,137,"total_anaerobic_training_effect"
-    18,139,"avg_vam"
-    18,140,"avg_depth"
-    18,141,"max_depth"
-    18,142,"surface_interval"
-    18,143,"start_cns"
-    18,144,"end_cns"
-    18,145,"start_n2"
-    18,146,"end_n2"
-    18,147,"avg_respiration_rate"
-    18,148,"max_respiration_rate"
-    18,149,"min_respiration_rate"
-    18,150,"min_temperature"
-    18,155,"o2_toxicity"
+    18,114,"avg_left_pco",1,0,"mm"
+    18,115,"avg_right_pco",1,0,"mm"
+    18,116,"avg_left_power_phase",0.7111111,0,"degrees"
+    18,117,"avg_left_power_phase_peak",0.7111111,0,"degrees"
+    18,118,"avg_right_power_phase",0.7111111,0,"degrees"
+    18,119,"avg_right_power_phase_peak",0.7111111,0,"degrees"
+    18,120,"avg_power_position",1,0,"watts"
+    18,121,"max_power_position",1,0,"watts"
+    18,122,"avg_cadence_position",1,0,"rpm"
+    18,123,"max_cadence_position",1,0,"rpm"
+    18,124,"enhanced_avg_speed",1000,0,"m/s"
+    18,125,"enhanced_max_speed",1000,0,"m/s"
+    18,126,"enhanced_avg_altitude",5,500,"m"
+    18,127,"enhanced_min_altitude",5,500,"m"
+    18,128,"enhanced_max_altitude",5,500,"m"
+    18,129,"avg_lev_motor_power",1,0,"watts"
+    18,130,"max_lev_motor_power",1,0,"watts"
+    18,131,"lev_battery_consumption",2,0,"percent"
+    18,132,"avg_vertical_ratio",100,0,"percent"
+    18,133,"avg_stance_time_balance",100,0,"percent"
+    18,134,"avg_step_length",10,0,"mm"
+    18,137,"total_anaerobic_training_effect",10,0,""
+    18,139,"avg_vam",1000,0,"m/s"
+    18,140,"avg_depth",1000,0,"m"
+    18,141,"max_depth",1000,0,"m"
+    18,142,"surface_interval",1,0,"s"
+    18,143,"start_cns",1,0,"percent"
+    18,144,"end_cns",1,0,"percent"
+    18,145,"start_n2",1,0,"percent"
+    18,146,"end_n2",1,0,"percent"
+    18,147,"avg_respiration_rate",1,0,"breaths/min"
+    18,148,"max_respiration_rate",1,0,"breaths/min"
+    18,149,"min_respiration_rate",1,0,"breaths/min"
+    18,150,"min_temperature",1,0,"C"
+    18,155,"o2_toxicity",1,0,"OTUs"
     18,156,"dive_number"
     18,168,"training_load_peak"
-    18,169,"enhanced_avg_respiration_rate"
-    18,170,"enhanced_max_respiration_rate"
-    18,180,"enhanced_min_respiration_rate"
+    18,169,"enhanced_avg_respiration_rate",100,0,"breaths/min"
+    18,170,"enhanced_max_respiration_rate",100,0,"breaths/min"
+    18,180,"enhanced_min_respiration_rate",100,0,"breaths/min"
     18,181,"total_grit"
     18,182,"total_flow"
     18,183,"jump_count"
     18,186,"avg_grit"
     18,187,"avg_flow"
-    18,194,"avg_spo2"
-    18,195,"avg_stress"
-    18,197,"sdrr_hrv"
-    18,198,"rmssd_hrv"
-    18,199,"total_fractional_ascent"
-    18,200,"total_fractional_descent"
-    18,208,"avg_core_temperature"
-    18,209,"min_core_temperature"
-    18,210,"max_core_temperature"
-    19,3,"start_position_lat"
-    19,4,"start_position_long"
-    19,5,"end_position_lat"
-    19,6,"end_position_long"
-    19,7,"total_elapsed_time"
-    19,8,"total_timer_time"
-    19,9,"total_distance"
-    19,10,"total_cycles"
-    19,11,"total_calories"
-    19,12,"total_fat_calories"
-    19,13,"avg_speed"
-    19,14,"max_speed"
-    19,15,"avg_heart_rate"
-    19,16,"max_heart_rate"
-    19,17,"avg_cadence"
-    19,18,"max_cadence"
-    19,19,"avg_power"
-    19,20,"max_power"
-    19,21,"total_ascent"
-    19,22,"total_descent"
+    18,194,"avg_spo2",1,0,"percent"
+    18,195,"avg_stress",1,0,"percent"
+    18,197,"sdrr_hrv",1,0,"mS"
+    18,198,"rmssd_hrv",1,0,"mS"
+    18,199,"total_fractional_ascent",100,0,"m"
+    18,200,"total_fractional_descent",100,0,"m"
+    18,208,"avg_core_temperature",100,0,"C"
+    18,209,"min_core_temperature",100,0,"C"
+    18,210,"max_core_temperature",100,0,"C"
+    19,3,"start_position_lat",1,0,"semicircles"
+    19,4,"start_position_long",1,0,"semicircles"
+    19,5,"end_position_lat",1,0,"semicircles"
+    19,6,"end_position_long",1,0,"semicircles"
+    19,7,"total_elapsed_time",1000,0,"s"
+    19,8,"total_timer_time",1000,0,"s"
+    19,9,"total_distance",100,0,"m"
+    19,10,"total_cycles",1,0,"cycles"
+    19,11,"total_calories",1,0,"kcal"
+    19,12,"total_fat_calories",1,0,"kcal"
+    19,13,"avg_speed",1000,0,"m/s"
+    19,14,"max_speed",1000,0,"m/s"
+    19,15,"avg_heart_rate",1,0,"bpm"
+    19,16,"max_heart_rate",1,0,"bpm"
+    19,17,"avg_cadence",1,0,"rpm"
+    19,18,"max_cadence",1,0,"rpm"
+    19,19,"avg_power",1,0,"watts"
+    19,20,"max_power",1,0,"watts"
+    19,21,"total_ascent",1,0,"m"
+    19,22,"total_descent",1,0,"m"
     19,26,"event_group"
     19,32,"num_lengths"
-    19,33,"normalized_power"
+    19,33,"normalized_power",1,0,"watts"
     19,35,"first_length_index"
-    19,37,"avg_stroke_distance"
+    19,37,"avg_stroke_distance",100,0,"m"
     19,40,"num_active_lengths"
-    19,41,"total_work"
-    19,42,"avg_altitude"
-    19,43,"max_altitude"
-    19,44,"gps_accuracy"
-    19,45,"avg_grade"
-    19,46,"avg_pos_grade"
-    19,47,"avg_neg_grade"
-    19,48,"max_pos_grade"
-    19,49,"max_neg_grade"
-    19,50,"avg_temperature"
-    19,51,"max_temperature"
-    19,52,"total_moving_time"
-    19,53,"avg_pos_vertical_speed"
-    19,54,"avg_neg_vertical_speed"
-    19,55,"max_pos_vertical_speed"
-    19,56,"max_neg_vertical_speed"
-    19,57,"time_in_hr_zone"
-    19,58,"time_in_speed_zone"
-    19,59,"time_in_cadence_zone"
-    19,60,"time_in_power_zone"
+    19,41,"total_work",1,0,"J"
+    19,42,"avg_altitude",5,500,"m"
+    19,43,"max_altitude",5,500,"m"
+    19,44,"gps_accuracy",1,0,"m"
+    19,45,"avg_grade",100,0,"%"
+    19,46,"avg_pos_grade",100,0,"%"
+    19,47,"avg_neg_grade",100,0,"%"
+    19,48,"max_pos_grade",100,0,"%"
+    19,49,"max_neg_grade",100,0,"%"
+    19,50,"avg_temperature",1,0,"C"
+    19,51,"max_temperature",1,0,"C"
+    19,52,"total_moving_time",1000,0,"s"
+    19,53,"avg_pos_vertical_speed",1000,0,"m/s"
+    19,54,"avg_neg_vertical_speed",1000,0,"m/s"
+    19,55,"max_pos_vertical_speed",1000,0,"m/s"
+    19,56,"max_neg_vertical_speed",1000,0,"m/s"
+    19,57,"time_in_hr_zone",1000,0,"s"
+    19,58,"time_in_speed_zone",1000,0,"s"
+    19,59,"time_in_cadence_zone",1000,0,"s"
+    19,60,"time_in_power_zone",1000,0,"s"
     19,61,"repetition_num"
-    19,62,"min_altitude"
-    19,63,"min_heart_rate"
+    19,62,"min_altitude",5,500,"m"
+    19,63,"min_heart_rate",1,0,"bpm"
     19,74,"opponent_score"
     19,75,"stroke_count"
     19,76,"zone_count"
-    19,77,"avg_vertical_oscillation"
-    19,78,"avg_stance_time_percent"
-    19,79,"avg_stance_time"
-    19,80,"avg_fractional_cadence"
-    19,81,"max_fractional_cadence"
-    19,82,"total_fractional_cycles"
+    19,77,"avg_vertical_oscillation",10,0,"mm"
+    19,78,"avg_stance_time_percent",100,0,"percent"
+    19,79,"avg_stance_time",10,0,"ms"
+    19,80,"avg_fractional_cadence",128,0,"rpm"
+    19,81,"max_fractional_cadence",128,0,"rpm"
+    19,82,"total_fractional_cycles",128,0,"cycles"
     19,83,"player_score"
-    19,84,"avg_total_hemoglobin_conc"
-    19,85,"min_total_hemoglobin_conc"
-    19,86,"max_total_hemoglobin_conc"
-    19,87,"avg_saturated_hemoglobin_percent"
-    19,88,"min_saturated_hemoglobin_percent"
-    19,89,"max_saturated_hemoglobin_percent"
-    19,91,"avg_left_torque_effectiveness"
-    19,92,"avg_right_torque_effectiveness"
-    19,93,"avg_left_pedal_smoothness"
-    19,94,"avg_right_pedal_smoothness"
-    19,95,"avg_combined_pedal_smoothness"
-    19,98,"time_standing"
+    19,84,"avg_total_hemoglobin_conc",100,0,"g/dL"
+    19,85,"min_total_hemoglobin_conc",100,0,"g/dL"
+    19,86,"max_total_hemoglobin_conc",100,0,"g/dL"
+    19,87,"avg_saturated_hemoglobin_percent",10,0,"%"
+    19,88,"min_saturated_hemoglobin_percent",10,0,"%"
+    19,89,"max_saturated_hemoglobin_percent",10,0,"%"
+    19,91,"avg_left_torque_effectiveness",2,0,"percent"
+    19,92,"avg_right_torque_effectiveness",2,0,"percent"
+    19,93,"avg_left_pedal_smoothness",2,0,"percent"
+    19,94,"avg_right_pedal_smoothness",2,0,"percent"
+    19,95,"avg_combined_pedal_smoothness",2,0,"percent"
+    19,98,"time_standing",1000,0,"s"
     19,99,"stand_count"
-    19,100,"avg_left_pco"
-    19,101,"avg_right_pco"
-    19,102,"avg_left_power_phase"
-    19,103,"avg_left_power_phase_peak"
-    19,104,"avg_right_power_phase"
-    19,105,"avg_right_power_phase_peak"
-    19,106,"avg_power_position"
-    19,107,"max_power_position"
-    19,108,"avg_cadence_position"
-    19,109,"max_cadence_position"
-    19,110,"enhanced_avg_speed"
-    19,111,"enhanced_max_speed"
-    19,112,"enhanced_avg_altitude"
-    19,113,"enhanced_min_altitude"
-    19,114,"enhanced_max_altitude"
-    19,115,"avg_lev_motor_power"
-    19,116,"max_lev_motor_power"
-    19,117,"lev_battery_consumption"
-    19,118,"avg_vertical_ratio"
-    19,119,"avg_stance_time_balance"
-    19,120,"avg_step_length"
-    19,121,"avg_vam"
-    19,122,"avg_depth"
-    19,123,"max_depth"
-    19,124,"min_temperature"
-    19,136,"enhanced_avg_respiration_rate"
-    19,137,"enhanced_max_respiration_rate"
-    19,147,"avg_respiration_rate"
-    19,148,"max_respiration_rate"
+    19,100,"avg_left_pco",1,0,"mm"
+    19,101,"avg_right_pco",1,0,"mm"
+    19,102,"avg_left_power_phase",0.7111111,0,"degrees"
+    19,103,"avg_left_power_phase_peak",0.7111111,0,"degrees"
+    19,104,"avg_right_power_phase",0.7111111,0,"degrees"
+    19,105,"avg_right_power_phase_peak",0.7111111,0,"degrees"
+    19,106,"avg_power_position",1,0,"watts"
+    19,107,"max_power_position",1,0,"watts"
+    19,108,"avg_cadence_position",1,0,"rpm"
+    19,109,"max_cadence_position",1,0,"rpm"
+    19,110,"enhanced_avg_speed",1000,0,"m/s"
+    19,111,"enhanced_max_speed",1000,0,"m/s"
+    19,112,"enhanced_avg_altitude",5,500,"m"
+    19,113,"enhanced_min_altitude",5,500,"m"
+    19,114,"enhanced_max_altitude",5,500,"m"
+    19,115,"avg_lev_motor_power",1,0,"watts"
+    19,116,"max_lev_motor_power",1,0,"watts"
+    19,117,"lev_battery_consumption",2,0,"percent"
+    19,118,"avg_vertical_ratio",100,0,"percent"
+    19,119,"avg_stance_time_balance",100,0,"percent"
+    19,120,"avg_step_length",10,0,"mm"
+    19,121,"avg_vam",1000,0,"m/s"
+    19,122,"avg_depth",1000,0,"m"
+    19,123,"max_depth",1000,0,"m"
+    19,124,"min_temperature",1,0,"C"
+    19,136,"enhanced_avg_respiration_rate",100,0,"breaths/min"
+    19,137,"enhanced_max_respiration_rate",100,0,"breaths/min"
+    19,147,"avg_respiration_rate",1,0,"breaths/min"
+    19,148,"max_respiration_rate",1,0,"breaths/min"
     19,149,"total_grit"
     19,150,"total_flow"
     19,151,"jump_count"
     19,153,"avg_grit"
     19,154,"avg_flow"
-    19,156,"total_fractional_ascent"
-    19,157,"total_fractional_descent"
-    19,158,"avg_core_temperature"
-    19,159,"min_core_temperature"
-    19,160,"max_core_temperature"
-    101,3,"total_elapsed_time"
-    101,4,"total_timer_time"
-    101,5,"total_strokes"
-    101,6,"avg_speed"
-    101,9,"avg_swimming_cadence"
+    19,156,"total_fractional_ascent",100,0,"m"
+    19,157,"total_fractional_descent",100,0,"m"
+    19,158,"avg_core_temperature",100,0,"C"
+    19,159,"min_core_temperature",100,0,"C"
+    19,160,"max_core_temperature",100,0,"C"
+    101,3,"total_elapsed_time",1000,0,"s"
+    101,4,"total_timer_time",1000,0,"s"
+    101,5,"total_strokes",1,0,"strokes"
+    101,6,"avg_speed",1000,0,"m/s"
+    101,9,"avg_swimming_cadence",1,0,"strokes/min"
     101,10,"event_group"
-    101,11,"total_calories"
+    101,11,"total_calories",1,0,"kcal"
     101,18,"player_score"
     101,19,"opponent_score"
     101,20,"stroke_count"
     101,21,"zone_count"
-    101,22,"enhanced_avg_respiration_rate"
-    101,23,"enhanced_max_respiration_rate"
-    101,24,"avg_respiration_rate"
-    101,25,"max_respiration_rate"
-    20,0,"position_lat"
-    20,1,"position_long"
-    20,2,"altitude"
-    20,3,"heart_rate"
-    20,4,"cadence"
-    20,5,"distance"
-    20,6,"speed"
-    20,7,"power"
+    101,22,"enhanced_avg_respiration_rate",100,0,"breaths/min"
+    101,23,"enhanced_max_respiration_rate",100,0,"breaths/min"
+    101,24,"avg_respiration_rate",1,0,"breaths/min"
+    101,25,"max_respiration_rate",1,0,"breaths/min"
+    20,0,"position_lat",1,0,"semicircles"
+    20,1,"position_long",1,0,"semicircles"
+    20,2,"altitude",5,500,"m"
+    20,3,"heart_rate",1,0,"bpm"
+    20,4,"cadence",1,0,"rpm"
+    20,5,"distance",100,0,"m"
+    20,6,"speed",1000,0,"m/s"
+    20,7,"power",1,0,"watts"
     20,8,"compressed_speed_distance"
-    20,9,"grade"
+    20,9,"grade",100,0,"%"
     20,10,"resistance"
-    20,11,"time_from_course"
-    20,12,"cycle_length"
-    20,13,"temperature"
-    20,17,"speed_1s"
-    20,18,"cycles"
-    20,19,"total_cycles"
-    20,28,"compressed_accumulated_power"
-    20,29,"accumulated_power"
-    20,31,"gps_accuracy"
-    20,32,"vertical_speed"
-    20,33,"calories"
-    20,39,"vertical_oscillation"
-    20,40,"stance_time_percent"
-    20,41,"stance_time"
-    20,43,"left_torque_effectiveness"
-    20,44,"right_torque_effectiveness"
-    20,45,"left_pedal_smoothness"
-    20,46,"right_pedal_smoothness"
-    20,47,"combined_pedal_smoothness"
-    20,48,"time128"
+    20,11,"time_from_course",1000,0,"s"
+    20,12,"cycle_length",100,0,"m"
+    20,13,"temperature",1,0,"C"
+    20,17,"speed_1s",16,0,"m/s"
+    20,18,"cycles",1,0,"cycles"
+    20,19,"total_cycles",1,0,"cycles"
+    20,28,"compressed_accumulated_power",1,0,"watts"
+    20,29,"accumulated_power",1,0,"watts"
+    20,31,"gps_accuracy",1,0,"m"
+    20,32,"vertical_speed",1000,0,"m/s"
+    20,33,"calories",1,0,"kcal"
+    20,39,"vertical_oscillation",10,0,"mm"
+    20,40,"stance_time_percent",100,0,"percent"
+    20,41,"stance_time",10,0,"ms"
+    20,43,"left_torque_effectiveness",2,0,"percent"
+    20,44,"right_torque_effectiveness",2,0,"percent"
+    20,45,"left_pedal_smoothness",2,0,"percent"
+    20,46,"right_pedal_smoothness",2,0,"percent"
+    20,47,"combined_pedal_smoothness",2,0,"percent"
+    20,48,"time128",128,0,"s"
     20,50,"zone"
-    20,51,"ball_speed"
-    20,52,"cadence256"
-    20,53,"fractional_cadence"
-    20,54,"total_hemoglobin_conc"
-    20,55,"total_hemoglobin_conc_min"
-    20,56,"total_hemoglobin_conc_max"
-    20,57,"saturated_hemoglobin_percent"
-    20,58,"saturated_hemoglobin_percent_min"
-    20,59,"saturated_hemoglobin_percent_max"
-    20,67,"left_pco"
-    20,68,"right_pco"
-    20,69,"left_power_phase"
-    20,70,"left_power_phase_peak"
-    20,71,"right_power_phase"
-    20,72,"right_power_phase_peak"
-    20,73,"enhanced_speed"
-    20,78,"enhanced_altitude"
-    20,81,"battery_soc"
-    20,82,"motor_power"
-    20,83,"vertical_ratio"
-    20,84,"stance_time_balance"
-    20,85,"step_length"
-    20,87,"cycle_length16"
-    20,91,"absolute_pressure"
-    20,92,"depth"
-    20,93,"next_stop_depth"
-    20,94,"next_stop_time"
-    20,95,"time_to_surface"
-    20,96,"ndl_time"
-    20,97,"cns_load"
-    20,98,"n2_load"
-    20,99,"respiration_rate"
-    20,108,"enhanced_respiration_rate"
+    20,51,"ball_speed",100,0,"m/s"
+    20,52,"cadence256",256,0,"rpm"
+    20,53,"fractional_cadence",128,0,"rpm"
+    20,54,"total_hemoglobin_conc",100,0,"g/dL"
+    20,55,"total_hemoglobin_conc_min",100,0,"g/dL"
+    20,56,"total_hemoglobin_conc_max",100,0,"g/dL"
+    20,57,"saturated_hemoglobin_percent",10,0,"%"
+    20,58,"saturated_hemoglobin_percent_min",10,0,"%"
+    20,59,"saturated_hemoglobin_percent_max",10,0,"%"
+    20,67,"left_pco",1,0,"mm"
+    20,68,"right_pco",1,0,"mm"
+    20,69,"left_power_phase",0.7111111,0,"degrees"
+    20,70,"left_power_phase_peak",0.7111111,0,"degrees"
+    20,71,"right_power_phase",0.7111111,0,"degrees"
+    20,72,"right_power_phase_peak",0.7111111,0,"degrees"
+    20,73,"enhanced_speed",1000,0,"m/s"
+    20,78,"enhanced_altitude",5,500,"m"
+    20,81,"battery_soc",2,0,"percent"
+    20,82,"motor_power",1,0,"watts"
+    20,83,"vertical_ratio",100,0,"percent"
+    20,84,"stance_time_balance",100,0,"percent"
+    20,85,"step_length",10,0,"mm"
+    20,87,"cycle_length16",100,0,"m"
+    20,91,"absolute_pressure",1,0,"Pa"
+    20,92,"depth",1000,0,"m"
+    20,93,"next_stop_depth",1000,0,"m"
+    20,94,"next_stop_time",1,0,"s"
+    20,95,"time_to_surface",1,0,"s"
+    20,96,"ndl_time",1,0,"s"
+    20,97,"cns_load",1,0,"percent"
+    20,98,"n2_load",1,0,"percent"
+    20,99,"respiration_rate",1,0,"breaths/min"
+    20,108,"enhanced_respiration_rate",100,0,"breaths/min"
     20,114,"grit"
     20,115,"flow"
     20,116,"current_stress"
-    20,117,"ebike_travel_range"
-    20,118,"ebike_battery_level"
+    20,117,"ebike_travel_range",1,0,"km"
+    20,118,"ebike_battery_level",1,0,"percent"
     20,119,"ebike_assist_mode"
-    20,120,"ebike_assist_level_percent"
-    20,123,"air_time_remaining"
-    20,124,"pressure_sac"
-    20,125,"volume_sac"
-    20,126,"rmv"
-    20,127,"ascent_rate"
-    20,129,"po2"
-    20,139,"core_temperature"
+    20,120,"ebike_assist_level_percent",1,0,"percent"
+    20,123,"air_time_remaining",1,0,"s"
+    20,124,"pressure_sac",100,0,"bar/min"
+    20,125,"volume_sac",100,0,"L/min"
+    20,126,"rmv",100,0,"L/min"
+    20,127,"ascent_rate",1000,0,"m/s"
+    20,129,"po2",100,0,"percent"
+    20,139,"core_temperature",100,0,"C"
     21,2,"data16"
     21,3,"data"
     21,4,"event_group"
@@ -692,15 +1224,15 @@ impl Field {
     21,11,"rear_gear_num"
     21,12,"rear_gear"
     21,22,"radar_threat_count"
-    21,23,"radar_threat_avg_approach_speed"
-    21,24,"radar_threat_max_approach_speed"
+    21,23,"radar_threat_avg_approach_speed",10,0,"m/s"
+    21,24,"radar_threat_max_approach_speed",10,0,"m/s"
     23,1,"device_type"
     23,3,"serial_number"
     23,4,"product"
-    23,5,"software_version"
+    23,5,"software_version",100,0,""
     23,6,"hardware_version"
-    23,7,"cum_operating_time"
-    23,10,"battery_voltage"
+    23,7,"cum_operating_time",1,0,"s"
+    23,10,"battery_voltage",256,0,"V"
     23,19,"descriptor"
     23,20,"ant_transmission_type"
     23,21,"ant_device_number"
@@ -717,18 +1249,18 @@ impl Field {
     128,6,"temperature_feels_like"
     128,7,"relative_humidity"
     128,8,"location"
-    128,10,"observed_location_lat"
-    128,11,"observed_location_long"
+    128,10,"observed_location_lat",1,0,"semicircles"
+    128,11,"observed_location_long",1,0,"semicircles"
     128,13,"high_temperature"
     128,14,"low_temperature"
     129,0,"report_id"
-    160,0,"timestamp_ms"
-    160,1,"position_lat"
-    160,2,"position_long"
-    160,3,"enhanced_altitude"
-    160,4,"enhanced_speed"
-    160,5,"heading"
-    160,7,"velocity"
+    160,0,"timestamp_ms",1,0,"ms"
+    160,1,"position_lat",1,0,"semicircles"
+    160,2,"position_long",1,0,"semicircles"
+    160,3,"enhanced_altitude",5,500,"m"
+    160,4,"enhanced_speed",1000,0,"m/s"
+    160,5,"heading",100,0,"degrees"
+    160,7,"velocity",100,0,"m/s"
     161,0,"timestamp_ms"
     161,2,"camera_file_uuid"
     164,0,"timestamp_ms"
@@ -811,8 +1343,8 @@ impl Field {
     285,2,"rotations"
     285,3,"hang_time"
     285,4,"score"
-    285,5,"position_lat"
-    285,6,"position_long"
+    285,5,"position_lat",1,0,"semicircles"
+    285,6,"position_long",1,0,"semicircles"
     285,7,"speed"
     285,8,"enhanced_speed"
     312,1,"total_elapsed_time"
@@ -821,10 +1353,10 @@ impl Field {
     312,4,"avg_speed"
     312,13,"total_ascent"
     312,14,"total_descent"
-    312,21,"start_position_lat"
-    312,22,"start_position_long"
-    312,23,"end_position_lat"
-    312,24,"end_position_long"
+    312,21,"start_position_lat",1,0,"semicircles"
+    312,22,"start_position_long",1,0,"semicircles"
+    312,23,"end_position_lat",1,0,"semicircles"
+    312,24,"end_position_long",1,0,"semicircles"
     312,25,"max_speed"
     312,26,"avg_vert_speed"
     312,28,"total_calories"
@@ -842,8 +1374,8 @@ impl Field {
     313,12,"avg_vert_speed"
     313,13,"total_calories"
     313,77,"total_moving_time"
-    317,0,"position_lat"
-    317,1,"position_long"
+    317,0,"position_lat",1,0,"semicircles"
+    317,1,"position_long",1,0,"semicircles"
     317,3,"climb_number"
     317,4,"climb_category"
     317,5,"current_dist"
@@ -863,9 +1395,9 @@ impl Field {
     207,3,"developer_data_index"
     207,4,"application_version"
     31,5,"name"
-    32,2,"position_lat"
-    32,3,"position_long"
-    32,4,"distance"
+    32,2,"position_lat",1,0,"semicircles"
+    32,3,"position_long",1,0,"semicircles"
+    32,4,"distance",100,0,"m"
     32,6,"name"
     32,8,"favorite"
     148,0,"name"
@@ -879,95 +1411,95 @@ impl Field {
     149,3,"activity_id"
     149,4,"segment_time"
     149,5,"activity_id_string"
-    150,1,"position_lat"
-    150,2,"position_long"
-    150,3,"distance"
-    150,4,"altitude"
-    150,5,"leader_time"
-    150,6,"enhanced_altitude"
-    142,3,"start_position_lat"
-    142,4,"start_position_long"
-    142,5,"end_position_lat"
-    142,6,"end_position_long"
-    142,7,"total_elapsed_time"
-    142,8,"total_timer_time"
-    142,9,"total_distance"
-    142,10,"total_cycles"
-    142,11,"total_calories"
-    142,12,"total_fat_calories"
-    142,13,"avg_speed"
-    142,14,"max_speed"
-    142,15,"avg_heart_rate"
-    142,16,"max_heart_rate"
-    142,17,"avg_cadence"
-    142,18,"max_cadence"
-    142,19,"avg_power"
-    142,20,"max_power"
-    142,21,"total_ascent"
-    142,22,"total_descent"
+    150,1,"position_lat",1,0,"semicircles"
+    150,2,"position_long",1,0,"semicircles"
+    150,3,"distance",100,0,"m"
+    150,4,"altitude",5,500,"m"
+    150,5,"leader_time",1000,0,"s"
+    150,6,"enhanced_altitude",5,500,"m"
+    142,3,"start_position_lat",1,0,"semicircles"
+    142,4,"start_position_long",1,0,"semicircles"
+    142,5,"end_position_lat",1,0,"semicircles"
+    142,6,"end_position_long",1,0,"semicircles"
+    142,7,"total_elapsed_time",1000,0,"s"
+    142,8,"total_timer_time",1000,0,"s"
+    142,9,"total_distance",100,0,"m"
+    142,10,"total_cycles",1,0,"cycles"
+    142,11,"total_calories",1,0,"kcal"
+    142,12,"total_fat_calories",1,0,"kcal"
+    142,13,"avg_speed",1000,0,"m/s"
+    142,14,"max_speed",1000,0,"m/s"
+    142,15,"avg_heart_rate",1,0,"bpm"
+    142,16,"max_heart_rate",1,0,"bpm"
+    142,17,"avg_cadence",1,0,"rpm"
+    142,18,"max_cadence",1,0,"rpm"
+    142,19,"avg_power",1,0,"watts"
+    142,20,"max_power",1,0,"watts"
+    142,21,"total_ascent",1,0,"m"
+    142,22,"total_descent",1,0,"m"
     142,24,"event_group"
-    142,25,"nec_lat"
-    142,26,"nec_long"
-    142,27,"swc_lat"
-    142,28,"swc_long"
+    142,25,"nec_lat",1,0,"semicircles"
+    142,26,"nec_long",1,0,"semicircles"
+    142,27,"swc_lat",1,0,"semicircles"
+    142,28,"swc_long",1,0,"semicircles"
     142,29,"name"
-    142,30,"normalized_power"
-    142,33,"total_work"
-    142,34,"avg_altitude"
-    142,35,"max_altitude"
-    142,36,"gps_accuracy"
-    142,37,"avg_grade"
-    142,38,"avg_pos_grade"
-    142,39,"avg_neg_grade"
-    142,40,"max_pos_grade"
-    142,41,"max_neg_grade"
-    142,42,"avg_temperature"
-    142,43,"max_temperature"
-    142,44,"total_moving_time"
-    142,45,"avg_pos_vertical_speed"
-    142,46,"avg_neg_vertical_speed"
-    142,47,"max_pos_vertical_speed"
-    142,48,"max_neg_vertical_speed"
-    142,49,"time_in_hr_zone"
-    142,50,"time_in_speed_zone"
-    142,51,"time_in_cadence_zone"
-    142,52,"time_in_power_zone"
+    142,30,"normalized_power",1,0,"watts"
+    142,33,"total_work",1,0,"J"
+    142,34,"avg_altitude",5,500,"m"
+    142,35,"max_altitude",5,500,"m"
+    142,36,"gps_accuracy",1,0,"m"
+    142,37,"avg_grade",100,0,"%"
+    142,38,"avg_pos_grade",100,0,"%"
+    142,39,"avg_neg_grade",100,0,"%"
+    142,40,"max_pos_grade",100,0,"%"
+    142,41,"max_neg_grade",100,0,"%"
+    142,42,"avg_temperature",1,0,"C"
+    142,43,"max_temperature",1,0,"C"
+    142,44,"total_moving_time",1000,0,"s"
+    142,45,"avg_pos_vertical_speed",1000,0,"m/s"
+    142,46,"avg_neg_vertical_speed",1000,0,"m/s"
+    142,47,"max_pos_vertical_speed",1000,0,"m/s"
+    142,48,"max_neg_vertical_speed",1000,0,"m/s"
+    142,49,"time_in_hr_zone",1000,0,"s"
+    142,50,"time_in_speed_zone",1000,0,"s"
+    142,51,"time_in_cadence_zone",1000,0,"s"
+    142,52,"time_in_power_zone",1000,0,"s"
     142,53,"repetition_num"
-    142,54,"min_altitude"
-    142,55,"min_heart_rate"
-    142,56,"active_time"
-    142,59,"avg_left_torque_effectiveness"
-    142,60,"avg_right_torque_effectiveness"
-    142,61,"avg_left_pedal_smoothness"
-    142,62,"avg_right_pedal_smoothness"
-    142,63,"avg_combined_pedal_smoothness"
+    142,54,"min_altitude",5,500,"m"
+    142,55,"min_heart_rate",1,0,"bpm"
+    142,56,"active_time",1000,0,"s"
+    142,59,"avg_left_torque_effectiveness",2,0,"percent"
+    142,60,"avg_right_torque_effectiveness",2,0,"percent"
+    142,61,"avg_left_pedal_smoothness",2,0,"percent"
+    142,62,"avg_right_pedal_smoothness",2,0,"percent"
+    142,63,"avg_combined_pedal_smoothness",2,0,"percent"
     142,65,"uuid"
-    142,66,"avg_fractional_cadence"
-    142,67,"max_fractional_cadence"
-    142,68,"total_fractional_cycles"
+    142,66,"avg_fractional_cadence",128,0,"rpm"
+    142,67,"max_fractional_cadence",128,0,"rpm"
+    142,68,"total_fractional_cycles",128,0,"cycles"
     142,69,"front_gear_shift_count"
     142,70,"rear_gear_shift_count"
-    142,71,"time_standing"
+    142,71,"time_standing",1000,0,"s"
     142,72,"stand_count"
-    142,73,"avg_left_pco"
-    142,74,"avg_right_pco"
-    142,75,"avg_left_power_phase"
-    142,76,"avg_left_power_phase_peak"
-    142,77,"avg_right_power_phase"
-    142,78,"avg_right_power_phase_peak"
-    142,79,"avg_power_position"
-    142,80,"max_power_position"
-    142,81,"avg_cadence_position"
-    142,82,"max_cadence_position"
+    142,73,"avg_left_pco",1,0,"mm"
+    142,74,"avg_right_pco",1,0,"mm"
+    142,75,"avg_left_power_phase",0.7111111,0,"degrees"
+    142,76,"avg_left_power_phase_peak",0.7111111,0,"degrees"
+    142,77,"avg_right_power_phase",0.7111111,0,"degrees"
+    142,78,"avg_right_power_phase_peak",0.7111111,0,"degrees"
+    142,79,"avg_power_position",1,0,"watts"
+    142,80,"max_power_position",1,0,"watts"
+    142,81,"avg_cadence_position",1,0,"rpm"
+    142,82,"max_cadence_position",1,0,"rpm"
     142,84,"total_grit"
     142,85,"total_flow"
     142,86,"avg_grit"
     142,87,"avg_flow"
-    142,89,"total_fractional_ascent"
-    142,90,"total_fractional_descent"
-    142,91,"enhanced_avg_altitude"
-    142,92,"enhanced_max_altitude"
-    142,93,"enhanced_min_altitude"
+    142,89,"total_fractional_ascent",100,0,"m"
+    142,90,"total_fractional_descent",100,0,"m"
+    142,91,"enhanced_avg_altitude",5,500,"m"
+    142,92,"enhanced_max_altitude",5,500,"m"
+    142,93,"enhanced_min_altitude",5,500,"m"
     151,1,"file_uuid"
     151,3,"enabled"
     151,4,"user_profile_primary_key"
@@ -997,60 +1529,60 @@ impl Field {
     28,1,"product"
     28,2,"serial_number"
     28,4,"completed"
-    33,0,"timer_time"
-    33,1,"distance"
-    33,2,"calories"
-    33,4,"elapsed_time"
+    33,0,"timer_time",1,0,"s"
+    33,1,"distance",1,0,"m"
+    33,2,"calories",1,0,"kcal"
+    33,4,"elapsed_time",1,0,"s"
     33,5,"sessions"
-    33,6,"active_time"
+    33,6,"active_time",1,0,"s"
     33,9,"sport_index"
-    30,1,"percent_fat"
-    30,2,"percent_hydration"
-    30,3,"visceral_fat_mass"
-    30,4,"bone_mass"
-    30,5,"muscle_mass"
-    30,7,"basal_met"
+    30,1,"percent_fat",100,0,"%"
+    30,2,"percent_hydration",100,0,"%"
+    30,3,"visceral_fat_mass",100,0,"kg"
+    30,4,"bone_mass",100,0,"kg"
+    30,5,"muscle_mass",100,0,"kg"
+    30,7,"basal_met",4,0,"kcal/day"
     30,8,"physique_rating"
-    30,9,"active_met"
-    30,10,"metabolic_age"
+    30,9,"active_met",4,0,"kcal/day"
+    30,10,"metabolic_age",1,0,"years"
     30,11,"visceral_fat_rating"
-    30,13,"bmi"
-    51,0,"systolic_pressure"
-    51,1,"diastolic_pressure"
-    51,2,"mean_arterial_pressure"
-    51,3,"map_3_sample_mean"
-    51,4,"map_morning_values"
-    51,5,"map_evening_values"
-    51,6,"heart_rate"
+    30,13,"bmi",10,0,"kg/m^2"
+    51,0,"systolic_pressure",1,0,"mmHg"
+    51,1,"diastolic_pressure",1,0,"mmHg"
+    51,2,"mean_arterial_pressure",1,0,"mmHg"
+    51,3,"map_3_sample_mean",1,0,"mmHg"
+    51,4,"map_morning_values",1,0,"mmHg"
+    51,5,"map_evening_values",1,0,"mmHg"
+    51,6,"heart_rate",1,0,"bpm"
     103,3,"cycles_to_distance"
     103,4,"cycles_to_calories"
     103,5,"resting_metabolic_rate"
-    55,1,"calories"
-    55,2,"distance"
-    55,3,"cycles"
-    55,4,"active_time"
+    55,1,"calories",1,0,"kcal"
+    55,2,"distance",100,0,"m"
+    55,3,"cycles",2,0,"cycles"
+    55,4,"active_time",1000,0,"s"
     55,8,"distance_16"
     55,9,"cycles_16"
-    55,10,"active_time_16"
-    55,12,"temperature"
-    55,14,"temperature_min"
-    55,15,"temperature_max"
+    55,10,"active_time_16",1,0,"s"
+    55,12,"temperature",100,0,"C"
+    55,14,"temperature_min",100,0,"C"
+    55,15,"temperature_max",100,0,"C"
     55,16,"activity_time"
-    55,19,"active_calories"
+    55,19,"active_calories",1,0,"kcal"
     55,24,"current_activity_type_intensity"
     55,25,"timestamp_min_8"
     55,26,"timestamp_16"
-    55,27,"heart_rate"
+    55,27,"heart_rate",1,0,"bpm"
     55,28,"intensity"
-    55,29,"duration_min"
-    55,30,"duration"
-    55,31,"ascent"
-    55,32,"descent"
-    55,33,"moderate_activity_minutes"
-    55,34,"vigorous_activity_minutes"
-    211,0,"resting_heart_rate"
-    211,1,"current_day_resting_heart_rate"
-    269,0,"reading_spo2"
+    55,29,"duration_min",1,0,"min"
+    55,30,"duration",1,0,"s"
+    55,31,"ascent",1000,0,"m"
+    55,32,"descent",1000,0,"m"
+    55,33,"moderate_activity_minutes",1,0,"minutes"
+    55,34,"vigorous_activity_minutes",1,0,"minutes"
+    211,0,"resting_heart_rate",1,0,"bpm"
+    211,1,"current_day_resting_heart_rate",1,0,"bpm"
+    269,0,"reading_spo2",1,0,"percent"
     269,1,"reading_confidence"
     132,0,"fractional_timestamp"
     132,1,"time256"
@@ -1152,7 +1684,7 @@ impl Field {
     289,2,"zero_cross_cnt"
     289,3,"instance"
     289,4,"time_above_threshold"
-    78,0,"time"
+    78,0,"time",1000,0,"s"
     290,0,"timestamp_ms"
     290,1,"time"
     370,0,"weekly_average"
@@ -1203,12 +1735,8 @@ impl Field {
     162,3,"local_timestamp",LocalDateTime
     35,254,"message_index",MessageIndex
     106,0,"manufacturer",Manufacturer
-    1,1,"sports",SportBits0
-    1,21,"workouts_supported",WorkoutCapabilities
-    1,23,"connectivity_supported",ConnectivityCapabilities
     37,254,"message_index",MessageIndex
     37,0,"type",File
-    37,1,"flags",FileFlags
     38,254,"message_index",MessageIndex
     38,0,"file",File
     38,1,"mesg_num",MesgNum
@@ -1223,7 +1751,6 @@ impl Field {
     2,55,"display_orientation",DisplayOrientation
     2,56,"mounting_side",Side
     2,89,"auto_sync_frequency",AutoSyncFrequency
-    2,90,"auto_activity_detect",AutoActivityDetect
     2,95,"smart_notification_display_orientation",DisplayOrientation
     2,134,"tap_interface",Switch
     2,174,"tap_sensitivity",TapSensitivity
@@ -1389,7 +1916,6 @@ impl Field {
     177,253,"timestamp",DateTime
     178,253,"timestamp",DateTime
     178,7,"stage",AttitudeStage
-    178,10,"validity",AttitudeValidity
     185,254,"message_index",MessageIndex
     186,254,"message_index",MessageIndex
     187,1,"start_timestamp",DateTime
@@ -1415,7 +1941,6 @@ impl Field {
     206,14,"native_mesg_num",MesgNum
     207,2,"manufacturer_id",Manufacturer
     31,4,"sport",Sport
-    31,6,"capabilities",CourseCapabilities
     31,7,"sub_sport",SubSport
     32,254,"message_index",MessageIndex
     32,1,"timestamp",DateTime
@@ -1442,7 +1967,6 @@ impl Field {
     151,7,"leader_type",SegmentLeaderboardType
     26,254,"message_index",MessageIndex
     26,4,"sport",Sport
-    26,5,"capabilities",WorkoutCapabilities
     26,11,"sub_sport",SubSport
     26,15,"pool_length_unit",DisplayMeasure
     158,254,"message_index",MessageIndex
@@ -1545,4 +2069,203 @@ impl Field {
     79,253,"timestamp",DateTime
     71,253,"timestamp",DateTime
     }
+
+    // message, field, name, bitfield type of every array element
+    crate::expand_bitfields! {
+    1,0,"languages",LanguageBits0,LanguageBitsOne,LanguageBitsTwo,LanguageBitsThree,LanguageBitsFour
+    1,1,"sports",SportBits0,SportBitsOne,SportBitsTwo,SportBitsThree,SportBitsFour,SportBitsFive,SportBitsSix
+    1,21,"workouts_supported",WorkoutCapabilities
+    1,23,"connectivity_supported",ConnectivityCapabilities
+    37,1,"flags",FileFlags
+    2,90,"auto_activity_detect",AutoActivityDetect
+    178,10,"validity",AttitudeValidity
+    31,6,"capabilities",CourseCapabilities
+    26,5,"capabilities",WorkoutCapabilities
+    }
+
+    // message, field of the fields with the base type `string`
+    crate::expand_string_fields! {
+    0,8
+    35,5
+    37,2
+    3,0
+    6,0
+    127,3
+    12,3
+    8,2
+    53,1
+    131,1
+    9,2
+    258,0
+    18,84
+    18,110
+    23,19
+    23,27
+    128,8
+    129,0
+    161,2
+    177,1
+    184,0
+    184,1
+    185,1
+    186,1
+    206,3
+    206,8
+    31,5
+    32,6
+    148,0
+    148,1
+    149,0
+    149,5
+    142,29
+    142,65
+    151,1
+    151,10
+    26,8
+    27,0
+    27,8
+    264,2
+    201,5
+    }
+
+    // message, field, subfield name, reference field, reference value [, scale, offset, units]
+    crate::expand_subfields! {
+    38,3,"num_per_file",2,0
+    38,3,"max_per_file",2,1
+    38,3,"max_per_file_type",2,2
+    18,10,"total_strides",5,1,1,0,"strides"
+    18,10,"total_strides",5,11,1,0,"strides"
+    18,10,"total_strokes",5,2,1,0,"strokes"
+    18,10,"total_strokes",5,5,1,0,"strokes"
+    18,10,"total_strokes",5,15,1,0,"strokes"
+    18,10,"total_strokes",5,37,1,0,"strokes"
+    18,18,"avg_running_cadence",5,1,1,0,"strides/min"
+    18,19,"max_running_cadence",5,1,1,0,"strides/min"
+    19,10,"total_strides",25,1,1,0,"strides"
+    19,10,"total_strides",25,11,1,0,"strides"
+    19,10,"total_strokes",25,2,1,0,"strokes"
+    19,10,"total_strokes",25,5,1,0,"strokes"
+    19,10,"total_strokes",25,15,1,0,"strokes"
+    19,10,"total_strokes",25,37,1,0,"strokes"
+    19,17,"avg_running_cadence",25,1,1,0,"strides/min"
+    19,18,"max_running_cadence",25,1,1,0,"strides/min"
+    21,3,"battery_level",0,11,1000,0,"V"
+    21,3,"virtual_partner_speed",0,12,1000,0,"m/s"
+    21,3,"hr_high_alert",0,13,1,0,"bpm"
+    21,3,"hr_low_alert",0,14,1,0,"bpm"
+    21,3,"speed_high_alert",0,15,1000,0,"m/s"
+    21,3,"speed_low_alert",0,16,1000,0,"m/s"
+    21,3,"cad_high_alert",0,17,1,0,"rpm"
+    21,3,"cad_low_alert",0,18,1,0,"rpm"
+    21,3,"power_high_alert",0,19,1,0,"watts"
+    21,3,"power_low_alert",0,20,1,0,"watts"
+    21,3,"time_duration_alert",0,23,1000,0,"s"
+    21,3,"distance_duration_alert",0,24,100,0,"m"
+    21,3,"calorie_duration_alert",0,25,1,0,"calories"
+    21,3,"sport_point",0,33
+    21,3,"gear_change_data",0,42
+    21,3,"gear_change_data",0,43
+    21,3,"auto_activity_detect_duration",0,54,1,0,"min"
+    21,3,"radar_threat_alert",0,75
+    23,1,"ant_device_type",25,0
+    167,1,"accel_cal_factor",0,0,1,0,"g"
+    167,1,"gyro_cal_factor",0,1,1,0,"deg/s"
+    210,1,"baro_cal_factor",0,3,1,0,"Pa"
+    142,10,"total_strokes",23,2,1,0,"strokes"
+    27,2,"duration_time",1,0,1000,0,"s"
+    27,2,"duration_time",1,28,1000,0,"s"
+    27,2,"duration_distance",1,1,100,0,"m"
+    27,2,"duration_calories",1,4,1,0,"calories"
+    27,2,"duration_step",1,6
+    27,2,"duration_step",1,7
+    27,2,"duration_step",1,8
+    27,2,"duration_step",1,9
+    27,2,"duration_step",1,10
+    27,2,"duration_step",1,11
+    27,2,"duration_step",1,12
+    27,2,"duration_step",1,13
+    27,2,"duration_reps",1,29
+    27,4,"target_speed_zone",3,0
+    27,4,"target_hr_zone",3,1
+    27,4,"target_cadence_zone",3,3
+    27,4,"target_power_zone",3,4
+    27,4,"repeat_steps",1,6
+    27,4,"repeat_time",1,7,1000,0,"s"
+    27,4,"repeat_distance",1,8,100,0,"m"
+    27,4,"repeat_calories",1,9,1,0,"calories"
+    27,5,"custom_target_speed_low",3,0,1000,0,"m/s"
+    27,5,"custom_target_cadence_low",3,3,1,0,"rpm"
+    27,6,"custom_target_speed_high",3,0,1000,0,"m/s"
+    27,6,"custom_target_cadence_high",3,3,1,0,"rpm"
+    27,20,"secondary_target_speed_zone",19,0
+    27,20,"secondary_target_hr_zone",19,1
+    27,20,"secondary_target_cadence_zone",19,3
+    27,20,"secondary_target_power_zone",19,4
+    27,21,"secondary_custom_target_speed_low",19,0,1000,0,"m/s"
+    27,21,"secondary_custom_target_cadence_low",19,3,1,0,"rpm"
+    27,22,"secondary_custom_target_speed_high",19,0,1000,0,"m/s"
+    27,22,"secondary_custom_target_cadence_high",19,3,1,0,"rpm"
+    55,3,"steps",5,6,1,0,"steps"
+    55,3,"steps",5,1,1,0,"steps"
+    55,3,"strokes",5,2,2,0,"strokes"
+    55,3,"strokes",5,5,2,0,"strokes"
+    }
+    crate::expand_subfields! {
+    0,2,"garmin_product",1,1,GarminProduct
+    0,2,"garmin_product",1,13,GarminProduct
+    0,2,"garmin_product",1,15,GarminProduct
+    0,2,"garmin_product",1,89,GarminProduct
+    0,2,"favero_product",1,263,FaveroProduct
+    106,1,"garmin_product",0,1,GarminProduct
+    106,1,"garmin_product",0,13,GarminProduct
+    106,1,"garmin_product",0,15,GarminProduct
+    106,1,"garmin_product",0,89,GarminProduct
+    106,1,"favero_product",0,263,FaveroProduct
+    159,1,"digital_layout",0,0,DigitalWatchfaceLayout
+    159,1,"analog_layout",0,1,AnalogWatchfaceLayout
+    258,20,"heart_rate_antplus_device_type",19,1,AntplusDeviceType
+    258,20,"heart_rate_local_device_type",19,5,LocalDeviceType
+    21,3,"timer_trigger",0,0,TimerTrigger
+    21,3,"course_point_index",0,10,MessageIndex
+    21,3,"fitness_equipment_state",0,27,FitnessEquipmentState
+    21,3,"rider_position",0,44,RiderPositionType
+    21,3,"comm_timeout",0,47,CommTimeoutType
+    21,3,"dive_alert",0,56,DiveAlert
+    23,1,"antplus_device_type",25,1,AntplusDeviceType
+    23,1,"ble_device_type",25,3,BleDeviceType
+    23,1,"local_device_type",25,5,LocalDeviceType
+    23,4,"garmin_product",2,1,GarminProduct
+    23,4,"garmin_product",2,13,GarminProduct
+    23,4,"garmin_product",2,15,GarminProduct
+    23,4,"garmin_product",2,89,GarminProduct
+    23,4,"favero_product",2,263,FaveroProduct
+    72,2,"garmin_product",1,1,GarminProduct
+    72,2,"garmin_product",1,13,GarminProduct
+    72,2,"garmin_product",1,15,GarminProduct
+    72,2,"garmin_product",1,89,GarminProduct
+    72,2,"favero_product",1,263,FaveroProduct
+    27,2,"duration_hr",1,2,WorkoutHr
+    27,2,"duration_hr",1,3,WorkoutHr
+    27,2,"duration_power",1,14,WorkoutPower
+    27,2,"duration_power",1,15,WorkoutPower
+    27,4,"repeat_hr",1,10,WorkoutHr
+    27,4,"repeat_hr",1,11,WorkoutHr
+    27,4,"repeat_power",1,12,WorkoutPower
+    27,4,"repeat_power",1,13,WorkoutPower
+    27,4,"target_stroke_type",3,11,SwimStroke
+    27,5,"custom_target_heart_rate_low",3,1,WorkoutHr
+    27,5,"custom_target_power_low",3,4,WorkoutPower
+    27,6,"custom_target_heart_rate_high",3,1,WorkoutHr
+    27,6,"custom_target_power_high",3,4,WorkoutPower
+    27,20,"secondary_target_stroke_type",19,11,SwimStroke
+    27,21,"secondary_custom_target_heart_rate_low",19,1,WorkoutHr
+    27,21,"secondary_custom_target_power_low",19,4,WorkoutPower
+    27,22,"secondary_custom_target_heart_rate_high",19,1,WorkoutHr
+    27,22,"secondary_custom_target_power_high",19,4,WorkoutPower
+    28,1,"garmin_product",0,1,GarminProduct
+    28,1,"garmin_product",0,13,GarminProduct
+    28,1,"garmin_product",0,15,GarminProduct
+    28,1,"garmin_product",0,89,GarminProduct
+    28,1,"favero_product",0,263,FaveroProduct
+    }
 }
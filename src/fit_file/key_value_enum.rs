@@ -1,8 +1,34 @@
 #[macro_export]
 macro_rules! key_value_enum {
-    (pub enum $name:ident { $( $key:ident = $val:literal ),* $(,)? } ) => {
+    (@profile_name $key:ident) => { $crate::fit_file::key_value_enum::snake_case(stringify!($key)) };
+    (@profile_name $key:ident $profile_name:literal) => { $profile_name.to_string() };
+    (#[bitfield] pub enum $name:ident { $( $key:ident = $val:literal $(=> $profile_name:literal)? ),* $(,)? } ) => {
+        $crate::key_value_enum! {
+            pub enum $name {
+                $(
+                    $key = $val $(=> $profile_name)?,
+                )*
+            }
+        }
+
+        impl $name {
+            /// names of the flags that are set, values with more than one bit are not flags
+            pub fn flags(bitfield_value: &u32) -> Vec<String> {
+                let mut flags = vec![];
+                $(
+                    let flag: u32 = $val;
+                    if flag.count_ones() == 1 && bitfield_value & flag != 0 {
+                        flags.push(Self::$key.to_string());
+                    }
+                )*
+                flags
+            }
+        }
+    };
+    (pub enum $name:ident { $( $key:ident = $val:literal $(=> $profile_name:literal)? ),* $(,)? } ) => {
         #[derive(PartialEq, Debug)]
         #[repr(u32)]
+        #[allow(clippy::enum_variant_names)]
         pub enum $name {
             $(
                 $key = $val,
@@ -19,6 +45,37 @@ macro_rules! key_value_enum {
                     _ => Self::Invalid
                 }
             }
+
+            /// name of the value, `None` if the value is not defined
+            pub fn translate(enum_value: &u32) -> Option<String> {
+                let resolved = Self::resolve(enum_value);
+                if resolved == Self::Invalid {
+                    None
+                } else {
+                    Some(resolved.to_string())
+                }
+            }
+
+            /// name of the value in the FIT profile like `edge_530`, `None` if the value is not defined
+            pub fn profile_name(enum_value: &u32) -> Option<String> {
+                match enum_value {
+                    $(
+                        $val => Some($crate::key_value_enum!(@profile_name $key $($profile_name)?)),
+                    )*
+                    _ => None
+                }
+            }
+
+            /// value of a name as returned by [Self::translate] or [Self::profile_name]
+            #[allow(unused_variables)]
+            pub fn parse(name: &str) -> Option<u32> {
+                $(
+                    if name == stringify!($key) || name == $crate::key_value_enum!(@profile_name $key $($profile_name)?) {
+                        return Some($val);
+                    }
+                )*
+                None
+            }
         }
         impl Display for $name {
             fn fmt(&self, f: &mut Formatter) -> fmt::Result {
@@ -35,17 +92,21 @@ macro_rules! expand_fields {
             let message_number = message_type.number;
             return match (message_type.number, field_number) {
                 $(
-                    ($MESG_NUM, $FIELD_NUM) => Field::EnumField(EnumField::from($field_name.to_string(), |value| $enum_type::resolve(value).to_string())),
+                    ($MESG_NUM, $FIELD_NUM) => Field::EnumField(EnumField::from($field_name.to_string(), stringify!($enum_type), $enum_type::translate, $enum_type::profile_name, $enum_type::parse)),
                 )+
-                _ => Field::Unknown(UnknownField { message_number, field_number }),
+                _ => Field::resolve_bitfield(message_number, field_number),
             };
         }
     };
-    ($($MESG_NUM:literal, $FIELD_NUM:literal, $field_name:literal)+)  => {
-        pub fn resolve_field(message_type: &MessageType, field_number: u8) -> Field {
+    (@scale) => { None };
+    (@scale $scale:literal, $offset:literal, $units:literal) => {
+        Some(Scale::from($scale as f64, $offset as f64, $units.to_string()))
+    };
+    ($($MESG_NUM:literal, $FIELD_NUM:literal, $field_name:literal $(, $scale:literal, $offset:literal, $units:literal)?)+)  => {
+        fn resolve_profile_field(message_type: &MessageType, field_number: u8) -> Field {
             return match (message_type.number, field_number) {
                 $(
-                    ($MESG_NUM, $FIELD_NUM) => Field::ValueField(ValueField::from($field_name.to_string())),
+                    ($MESG_NUM, $FIELD_NUM) => Field::ValueField(ValueField::from($field_name.to_string(), $crate::expand_fields!(@scale $($scale, $offset, $units)?))),
                 )+
                 _ => Field::resolve_enum(message_type, field_number),
             };
@@ -53,6 +114,109 @@ macro_rules! expand_fields {
     };
 }
 
+#[macro_export]
+macro_rules! expand_bitfields {
+    (@type_name $first_type:ident $(, $bitfield_type:ident)*) => { stringify!($first_type) };
+    ($($MESG_NUM:literal, $FIELD_NUM:literal, $field_name:literal, $($bitfield_type:ident),+)+)  => {
+        /// bitfield arrays use the next bitfield type for every element, e.g. `SportBits0`, `SportBitsOne`, ...
+        fn resolve_bitfield(message_number: u16, field_number: u8) -> Field {
+            return match (message_number, field_number) {
+                $(
+                    ($MESG_NUM, $FIELD_NUM) => Field::EnumField(EnumField::bitfield($field_name.to_string(), $crate::expand_bitfields!(@type_name $($bitfield_type),+), |values| {
+                        let bitfield_types: &[fn(&u32) -> Vec<String>] = &[$($bitfield_type::flags),+];
+                        values
+                            .iter()
+                            .zip(bitfield_types)
+                            .flat_map(|(value, flags)| flags(value))
+                            .collect()
+                    })),
+                )+
+                _ => Field::Unknown(UnknownField { message_number, field_number }),
+            };
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! expand_string_fields {
+    ($($MESG_NUM:literal, $FIELD_NUM:literal)+)  => {
+        /// `true` if the field has the base type `string` in the profile
+        pub(crate) fn is_string(message_number: u16, field_number: u8) -> bool {
+            matches!((message_number, field_number), $(($MESG_NUM, $FIELD_NUM))|+)
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! expand_subfields {
+    ($($MESG_NUM:literal, $FIELD_NUM:literal, $subfield_name:literal, $REF_FIELD_NUM:literal, $REF_VALUE:literal, $enum_type:ident)+)  => {
+        #[allow(unreachable_patterns)]
+        fn resolve_subfield_enum(message_number: u16, field_number: u8, reference_field_number: u8, reference_value: u32) -> Option<Field> {
+            return match (message_number, field_number, reference_field_number, reference_value) {
+                $(
+                    ($MESG_NUM, $FIELD_NUM, $REF_FIELD_NUM, $REF_VALUE) => Some(Field::EnumField(EnumField::from($subfield_name.to_string(), stringify!($enum_type), $enum_type::translate, $enum_type::profile_name, $enum_type::parse))),
+                )+
+                _ => None,
+            };
+        }
+
+        #[allow(unreachable_patterns)]
+        fn resolve_subfield_enum_by_name(message_number: u16, subfield_name: &str) -> Option<(u8, Field)> {
+            return match (message_number, subfield_name) {
+                $(
+                    ($MESG_NUM, $subfield_name) => Some(($FIELD_NUM, Field::EnumField(EnumField::from($subfield_name.to_string(), stringify!($enum_type), $enum_type::translate, $enum_type::profile_name, $enum_type::parse)))),
+                )+
+                _ => None,
+            };
+        }
+    };
+    ($($MESG_NUM:literal, $FIELD_NUM:literal, $subfield_name:literal, $REF_FIELD_NUM:literal, $REF_VALUE:literal $(, $scale:literal, $offset:literal, $units:literal)?)+)  => {
+        /// resolves the subfield of a field that is selected by the value of another field in the same message
+        #[allow(unreachable_patterns)]
+        pub fn resolve_subfield(message_number: u16, field_number: u8, reference_field_number: u8, reference_value: u32) -> Option<Field> {
+            return match (message_number, field_number, reference_field_number, reference_value) {
+                $(
+                    ($MESG_NUM, $FIELD_NUM, $REF_FIELD_NUM, $REF_VALUE) => Some(Field::ValueField(ValueField::from($subfield_name.to_string(), $crate::expand_fields!(@scale $($scale, $offset, $units)?)))),
+                )+
+                _ => Field::resolve_subfield_enum(message_number, field_number, reference_field_number, reference_value),
+            };
+        }
+
+        /// resolves a subfield by its name, together with the number of the field it belongs to
+        #[allow(unreachable_patterns)]
+        pub fn resolve_subfield_by_name(message_number: u16, subfield_name: &str) -> Option<(u8, Field)> {
+            return match (message_number, subfield_name) {
+                $(
+                    ($MESG_NUM, $subfield_name) => Some(($FIELD_NUM, Field::ValueField(ValueField::from($subfield_name.to_string(), $crate::expand_fields!(@scale $($scale, $offset, $units)?))))),
+                )+
+                _ => Field::resolve_subfield_enum_by_name(message_number, subfield_name),
+            };
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! expand_components {
+    (@reference) => { None };
+    (@reference $REF_FIELD:literal, $REF_VALUE:literal) => { Some(($REF_FIELD, $REF_VALUE)) };
+    ($($MESG_NUM:literal, $FIELD_NUM:literal, $TARGET_NUM:literal, $BITS:literal, $scale:literal, $offset:literal, $accumulate:literal $(, $REF_FIELD:literal, $REF_VALUE:literal)?)+)  => {
+        pub const COMPONENTS: &[Component] = &[
+            $(
+                Component {
+                    message_number: $MESG_NUM,
+                    field_number: $FIELD_NUM,
+                    target_field_number: $TARGET_NUM,
+                    bits: $BITS,
+                    scale: $scale as f64,
+                    offset: $offset as f64,
+                    accumulate: $accumulate,
+                    reference: $crate::expand_components!(@reference $($REF_FIELD, $REF_VALUE)?),
+                },
+            )+
+        ];
+    };
+}
+
 #[macro_export]
 macro_rules! base_type {
     ($($NAME:ident, $READ_SIZE:literal, $TYPE_NUMBER:literal, $INVALID_VALUE:literal, $DATA_TYPE:ty, $VALUE_TYPE:ident, $VALUE_TYPE_VEC:ident)+) => {
@@ -63,34 +227,56 @@ macro_rules! base_type {
         invalid_value: $INVALID_VALUE,
         read: |me, data, endianness| {
             let size = data.len();
+            if size == 0 {
+                return Value::Invalid;
+            }
+            // the bytes are compared, the invalid value of floats is a NaN
+            let invalid = me.invalid_bytes(*endianness);
             // also create vec if type is enum
             if size > me.read_size || me.type_number == 0 {
                 let mut value: Vec<$DATA_TYPE> = vec![];
-                let mut invalid_count = 0;
                 for i in (0..size).step_by(me.read_size) {
-                    let bytes = data[i..i + me.read_size].try_into().unwrap();
-                    let read_value = if *endianness == 0 {<$DATA_TYPE>::from_le_bytes(bytes)} else {<$DATA_TYPE>::from_be_bytes(bytes)};
-                    if read_value != me.invalid_value as $DATA_TYPE {
-                        value.push(read_value);
-                    } else {
-                        invalid_count += 1;
+                    let bytes = &data[i..i + me.read_size];
+                    if bytes != invalid.as_slice() {
+                        let bytes = bytes.try_into().unwrap();
+                        value.push(if *endianness == 0 {<$DATA_TYPE>::from_le_bytes(bytes)} else {<$DATA_TYPE>::from_be_bytes(bytes)});
                     }
                 }
-                if invalid_count == size {
+                if value.is_empty() {
                     Value::Invalid
                 } else {
                     Value::$VALUE_TYPE_VEC(value)
                 }
+            } else if data == invalid.as_slice() {
+                Value::Invalid
             } else {
                 let value = if *endianness == 0 {<$DATA_TYPE>::from_le_bytes(data.try_into().unwrap())} else {<$DATA_TYPE>::from_be_bytes(data.try_into().unwrap())};
-                if value == me.invalid_value as $DATA_TYPE {
-                    Value::Invalid
-                } else {
-                    Value::$VALUE_TYPE(value)
-                }
+                Value::$VALUE_TYPE(value)
             }
         },
     };
       )+
     }
 }
+
+/// converts a name like `FrontGearChange` into the snake case of the FIT profile, `front_gear_change`
+pub fn snake_case(name: &str) -> String {
+    let characters = name.chars().collect::<Vec<char>>();
+    let mut snake_case = String::with_capacity(name.len() + 4);
+    for (i, character) in characters.iter().enumerate() {
+        if character.is_ascii_uppercase() && i > 0 {
+            let previous = characters[i - 1];
+            let next_lowercase = characters
+                .get(i + 1)
+                .is_some_and(|next| next.is_ascii_lowercase());
+            if previous.is_ascii_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_ascii_uppercase() && next_lowercase)
+            {
+                snake_case.push('_');
+            }
+        }
+        snake_case.push(character.to_ascii_lowercase());
+    }
+    snake_case
+}
@@ -1,23 +1,30 @@
 use serde::ser::{SerializeMap, SerializeStruct};
 use serde::Serialize;
 use serde::Serializer;
+use std::sync::Arc;
 
 use crate::data_types::Value;
-use crate::fit_file::fields::Field;
-use crate::fit_file::message_types::MessageType;
+use crate::error::{read_bytes, FitError};
+use crate::fit_file::fields::{EnumField, Field, ValueField};
+use crate::fit_file::message_types::{MessageDefinition, MessageType};
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Clone)]
 #[allow(dead_code)] // derived debug does not touch the fields
 pub struct Header {
     pub length: usize,
     protocol_version: u8,
     profile_version: String,
-    data_size: u32,
+    pub data_size: u32,
     data_type: String,
     crc: [u8; 2],
 }
 
 impl Header {
+    /// size of the header and data records, excluding the trailing file CRC
+    pub fn content_size(&self) -> usize {
+        self.length + self.data_size as usize
+    }
+
     fn from(
         length: usize,
         protocol_version: u8,
@@ -32,57 +39,81 @@ impl Header {
             profile_version,
             data_size,
             data_type,
-            crc: <[u8; 2]>::try_from(crc).unwrap(),
+            crc,
         }
     }
 
-    pub fn read_header(header_info: &[u8]) -> Header {
-        let length = *header_info.get(0).unwrap() as usize;
-        let protocol_version = *header_info.get(1).unwrap() >> 4;
+    pub fn read_header(header_info: &[u8]) -> Result<Header, FitError> {
+        let length = read_bytes(header_info, 0, 1, "header length")?[0] as usize;
+        if length < 12 {
+            return Err(FitError::InvalidHeader {
+                offset: 0,
+                context: format!("header length {} is smaller than 12 bytes", length),
+            });
+        }
+        let header_info = read_bytes(header_info, 0, length, "header")?;
+        let protocol_version = header_info[1] >> 4;
 
-        let profile_field1: u16 = u16::from(*header_info.get(2).unwrap());
-        let profile_field2: u16 = u16::from(*header_info.get(3).unwrap()) << 8;
+        let profile_field1: u16 = u16::from(header_info[2]);
+        let profile_field2: u16 = u16::from(header_info[3]) << 8;
         let profile_version_value = profile_field1 + profile_field2;
         let first_part = profile_version_value / 100;
         let second_part = profile_version_value % 100;
         let profile_version = format!("{}.{}", first_part, second_part);
 
-        let data_size = u32::from(*header_info.get(4).unwrap())
-            + (u32::from(*header_info.get(5).unwrap()) << 8)
-            + (u32::from(*header_info.get(6).unwrap()) << 16)
-            + (u32::from(*header_info.get(7).unwrap()) << 24);
+        let data_size = u32::from(header_info[4])
+            + (u32::from(header_info[5]) << 8)
+            + (u32::from(header_info[6]) << 16)
+            + (u32::from(header_info[7]) << 24);
 
         let data_type = format!(
             "{}{}{}{}",
-            (*header_info.get(8).unwrap() as char).to_string(),
-            (*header_info.get(9).unwrap() as char).to_string(),
-            (*header_info.get(10).unwrap() as char).to_string(),
-            (*header_info.get(11).unwrap() as char).to_string()
+            (header_info[8] as char),
+            (header_info[9] as char),
+            (header_info[10] as char),
+            (header_info[11] as char)
         );
+        if data_type != ".FIT" {
+            return Err(FitError::InvalidHeader {
+                offset: 8,
+                context: format!("expected data type .FIT, got {:?}", data_type),
+            });
+        }
 
-        let crc: [u8; 2] = [
-            header_info.get(13).unwrap().clone(),
-            header_info.get(12).unwrap().clone(),
-        ];
-        Header::from(
+        // the legacy 12 byte header has no CRC
+        let crc: [u8; 2] = if length >= 14 {
+            [header_info[13], header_info[12]]
+        } else {
+            [0, 0]
+        };
+        Ok(Header::from(
             length,
             protocol_version,
             profile_version,
             data_size,
             data_type,
             crc,
-        )
+        ))
     }
 }
 
 pub struct Message {
     pub message_type: MessageType,
     pub data: Messages,
+    /// local message type of the record header the message was read with
+    pub local_message_number: u8,
+    /// definition the message was read with, `None` for messages that were not decoded from a file
+    pub(crate) definition: Option<Arc<MessageDefinition>>,
 }
 
 impl Message {
     pub fn from(message_type: MessageType, data: Messages) -> Message {
-        Message { message_type, data }
+        Message {
+            message_type,
+            data,
+            local_message_number: 0,
+            definition: None,
+        }
     }
 
     pub fn is_unknown(&self) -> bool {
@@ -106,6 +137,8 @@ impl Clone for Message {
         Message {
             message_type: self.message_type.clone(),
             data: self.data.clone(),
+            local_message_number: self.local_message_number,
+            definition: self.definition.clone(),
         }
     }
 }
@@ -115,9 +148,7 @@ impl Serialize for Message {
     where
         S: Serializer,
     {
-        let mut serialized = serializer
-            .serialize_struct(self.message_type.name, 2)
-            .unwrap();
+        let mut serialized = serializer.serialize_struct("Message", 2).unwrap();
         serialized
             .serialize_field("message_type", &self.display_name())
             .unwrap();
@@ -137,18 +168,18 @@ pub struct Messages {
 }
 
 impl Messages {
-    pub fn value(&self, field_name: &str) -> &Value {
-        &self
-            .data
-            .iter()
-            .find(|&entry| match &entry.field {
-                Field::Unknown(_inner_field) => false,
-                Field::EnumField(inner_field) => inner_field.name.eq(field_name),
-                Field::ValueField(inner_field) => inner_field.name.eq(field_name),
-                Field::DeveloperField => false,
-            })
-            .unwrap()
-            .value
+    pub fn value(&self, field_name: &str) -> Option<&Value> {
+        self.field_value(field_name).map(|entry| &entry.value)
+    }
+
+    /// field and value by the profile name of the field
+    pub fn field_value(&self, field_name: &str) -> Option<&FieldValue> {
+        self.data.iter().find(|&entry| match &entry.field {
+            Field::Unknown(_inner_field) => false,
+            Field::EnumField(inner_field) => inner_field.name.eq(field_name),
+            Field::ValueField(inner_field) => inner_field.name.eq(field_name),
+            Field::DeveloperField => false,
+        })
     }
 }
 
@@ -170,26 +201,124 @@ impl Serialize for Messages {
             .unwrap();
         for entry in &self.data {
             map.serialize_key(&entry.field).unwrap();
-            match &entry.value {
-                // todo revisit this part
-                Value::EnumValue(value) => {
-                    if value.is_empty() {
-                        map.serialize_value("").unwrap()
-                    } else {
-                        let enum_field_value = &u32::from(value[0]);
-                        match &entry.field {
-                            Field::Unknown(_) => map.serialize_value(value).unwrap(),
-                            Field::EnumField(enum_field) => {
-                                let string = (enum_field.translate_enum)(enum_field_value);
-                                map.serialize_value(&string).unwrap()
-                            }
-                            _ => map.serialize_value(&entry.value).unwrap(),
-                        }
-                    }
-                }
-                _ => map.serialize_value(&entry.value).unwrap(),
+            match &entry.field {
+                Field::ValueField(ValueField {
+                    native_field: Some(native_field),
+                    ..
+                }) => map.serialize_value(&NativeFieldValue {
+                    value: OutputValue(entry),
+                    native_field,
+                }),
+                _ => map.serialize_value(&OutputValue(entry)),
             }
+            .unwrap();
         }
         map.end()
     }
 }
+
+/// Value of a developer field with the profile field it provides, e.g. `power`
+#[derive(Serialize)]
+struct NativeFieldValue<'a> {
+    value: OutputValue<'a>,
+    native_field: &'a str,
+}
+
+/// Value of a field as output, with enum names and timestamps translated
+pub(crate) struct OutputValue<'a>(pub &'a FieldValue);
+
+impl Serialize for OutputValue<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match (&self.0.field, &self.0.value) {
+            (_, Value::EnumValue(value)) if value.is_empty() => serializer.serialize_str(""),
+            (Field::EnumField(enum_field), value) => {
+                EnumName::from(enum_field, value).serialize(serializer)
+            }
+            (_, value) => value.serialize(serializer),
+        }
+    }
+}
+
+/// Value of an enum field translated by the profile type of the field, independent of its base type.
+/// Arrays are translated element by element, values that are not defined by the profile stay numbers.
+/// `date_time` and `local_date_time` values become RFC 3339 timestamps.
+#[derive(Serialize)]
+#[serde(untagged)]
+enum EnumName<'a> {
+    Name(String),
+    Number(u64),
+    Names(Vec<EnumName<'a>>),
+    Value(&'a Value),
+}
+
+impl<'a> EnumName<'a> {
+    fn from(enum_field: &EnumField, value: &'a Value) -> EnumName<'a> {
+        if let Some(translate_flags) = enum_field.translate_flags {
+            let bitfield_values = match value {
+                Value::EnumValue(values) => values.iter().map(|&value| u32::from(value)).collect(),
+                value => match (value.as_f64(), value.as_f64_vec()) {
+                    (Some(number), _) => vec![number as u32],
+                    (_, Some(numbers)) => numbers.into_iter().map(|number| number as u32).collect(),
+                    _ => return EnumName::Value(value),
+                },
+            };
+            return EnumName::Names(
+                translate_flags(&bitfield_values)
+                    .into_iter()
+                    .map(EnumName::Name)
+                    .collect(),
+            );
+        }
+        if enum_field.is_date_time() {
+            // system times below 0x10000000 stay numbers
+            return match value
+                .as_f64()
+                .and_then(|number| enum_field.format_date_time(number as u32))
+            {
+                Some(timestamp) => EnumName::Name(timestamp),
+                None => EnumName::Value(value),
+            };
+        }
+        let translate = |number: u64| match u32::try_from(number)
+            .ok()
+            .and_then(|number| enum_field.translate(&number))
+        {
+            Some(name) => EnumName::Name(name),
+            None => EnumName::Number(number),
+        };
+        match value {
+            Value::EnumValue(values) if values.len() == 1 => translate(u64::from(values[0])),
+            Value::EnumValue(values) => EnumName::Names(
+                values
+                    .iter()
+                    .map(|&value| translate(u64::from(value)))
+                    .collect(),
+            ),
+            Value::NumberValueU8(_)
+            | Value::NumberValueU16(_)
+            | Value::NumberValueU32(_)
+            | Value::NumberValueU64(_) => {
+                match value.as_f64().map(|number| translate(number as u64)) {
+                    Some(EnumName::Name(name)) => EnumName::Name(name),
+                    _ => EnumName::Value(value),
+                }
+            }
+            Value::NumberValueVecU8(_)
+            | Value::NumberValueVecU16(_)
+            | Value::NumberValueVecU32(_)
+            | Value::NumberValueVecU64(_) => match value.as_f64_vec() {
+                Some(numbers) => EnumName::Names(
+                    numbers
+                        .into_iter()
+                        .map(|number| translate(number as u64))
+                        .collect(),
+                ),
+                None => EnumName::Value(value),
+            },
+            _ => EnumName::Value(value),
+        }
+    }
+}
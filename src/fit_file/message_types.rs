@@ -1,9 +1,14 @@
+use std::borrow::Cow;
+
 use serde::Serialize;
 
-use crate::data_types::BaseType;
-use crate::fit_file::fields::{DeveloperField, Field, ValueField};
+use crate::data_types::{BaseType, Value};
+use crate::error::{read_bytes, FitError};
+use crate::fit_file::components;
+use crate::fit_file::components::{Accumulators, StoredField};
+use crate::fit_file::fields::{DeveloperField, Field, Scale, ValueField};
 use crate::fit_file::message::{FieldValue, Messages};
-use crate::fit_file::{FitFileConfig, Message};
+use crate::fit_file::{FitFileConfig, Message, ProfileExtension};
 
 #[derive(Debug)]
 pub struct MessageDefinition {
@@ -15,65 +20,175 @@ impl MessageDefinition {
     pub fn read_message(
         &self,
         current_position: &usize,
-        buffer: &Vec<u8>,
+        buffer: &[u8],
         config: &FitFileConfig,
         endianness: &u8,
-        developer_fields: &Vec<DeveloperField>,
-    ) -> (Message, usize) {
+        developer_fields: &[DeveloperField],
+        accumulators: &mut Accumulators,
+    ) -> Result<(Message, usize), FitError> {
         let print_unknown = config.include_unknown_fields;
         let print_invalid = config.include_invalid_values;
-        let mut position = current_position.clone();
-        let mut message_data = Vec::new();
-        for field_definition in self.fields.iter().clone() {
+        let extension = config.profile_extension.as_deref();
+        let mut position = *current_position;
+        let mut read_fields = Vec::new();
+        for field_definition in self.fields.iter() {
             let mut data_field = field_definition.field.clone();
-            let base_type_value = field_definition.base_type_value_or_dev_index.clone();
-            let mut base_type = BaseType::parse(&0);
+            let base_type_value = field_definition.base_type_value_or_dev_index;
+            let mut base_type = BaseType::ENUM;
             let read_size = field_definition.size;
-            if field_definition.field == Field::DeveloperField
-                && self.message_type.number != 207
-                && self.message_type.number != 206
-            {
-                let mut got_field = false;
-                for dev_field in developer_fields {
-                    if dev_field.developer_data_index.eq(&base_type_value)
-                        && dev_field
-                            .field_definition_number
-                            .eq(&field_definition.number)
-                    {
-                        let field = dev_field.clone();
-                        let string = field.field_name.clone();
-                        base_type = BaseType::parse(&dev_field.fit_base_type_id);
-                        if got_field {
-                            panic!("Overwrite {} for {}", &string, self.message_type.number);
-                        }
-                        got_field = true;
-                        data_field = Field::ValueField(ValueField { name: string });
+            let mut developer_field = None;
+            if field_definition.field == Field::DeveloperField {
+                if self.message_type.number != 207 && self.message_type.number != 206 {
+                    if let Some(dev_field) = developer_fields.iter().find(|dev_field| {
+                        dev_field.developer_data_index.eq(&base_type_value)
+                            && dev_field
+                                .field_definition_number
+                                .eq(&field_definition.number)
+                    }) {
+                        base_type = BaseType::parse(&dev_field.fit_base_type_id).ok_or(
+                            FitError::UnknownBaseType {
+                                offset: position,
+                                base_type: dev_field.fit_base_type_id,
+                            },
+                        )?;
+                        data_field = Field::ValueField(ValueField {
+                            name: dev_field.field_name.clone(),
+                            scale: dev_field.scale(),
+                            native_field: dev_field
+                                .native_field_name(&self.message_type, extension),
+                        });
+                        developer_field = Some(dev_field);
                     }
                 }
             } else {
-                base_type = BaseType::parse(&field_definition.base_type_value_or_dev_index);
+                base_type = BaseType::parse(&base_type_value).ok_or(FitError::UnknownBaseType {
+                    offset: position,
+                    base_type: base_type_value,
+                })?;
+                // the base type of a profile extension, if the size of the field fits it
+                if let Some(extension_base_type) = extension.and_then(|extension| {
+                    extension.base_type(self.message_type.number, field_definition.number)
+                }) {
+                    if read_size as usize % extension_base_type.read_size == 0 {
+                        base_type = extension_base_type;
+                    }
+                }
+            }
+            // like the FIT SDK, a field whose size does not fit its base type is read as bytes
+            if read_size as usize % base_type.read_size != 0 {
+                base_type = BaseType::BYTE;
+            }
+            let data = read_bytes(buffer, position, read_size as usize, "field value")?;
+            let mut value = ((base_type).read)(&base_type, data, endianness);
+            if let Some(developer_field) = developer_field {
+                value = developer_field.elements(value);
             }
-            let end = position + (read_size as usize);
-            let data = &buffer[position..end];
-            let value = ((base_type).read)(&base_type, data, endianness);
             position += read_size as usize;
-            if (!data_field.is_unknown() || print_unknown) && (!value.is_invalid() || print_invalid)
+            let developer = field_definition.field == Field::DeveloperField;
+            let stored_field = StoredField::from(
+                field_definition.number,
+                data,
+                base_type.read_size,
+                endianness,
+                value,
+            );
+            read_fields.push((data_field, stored_field, developer));
+        }
+
+        // the field that selects a subfield can follow it, so subfields are resolved after all fields are read
+        let references = read_fields
+            .iter()
+            .filter(|(_, _, developer)| !developer)
+            .filter_map(|(_, stored_field, _)| {
+                stored_field
+                    .raw_value()
+                    .map(|raw_value| (stored_field.number, raw_value))
+            })
+            .collect::<Vec<(u8, u32)>>();
+        let mut message_data = Vec::new();
+        let mut stored_fields = Vec::new();
+        for (mut data_field, mut stored_field, developer) in read_fields {
+            if !developer {
+                if let Some(subfield) = references.iter().find_map(|(number, raw_value)| {
+                    Field::resolve_subfield(
+                        self.message_type.number,
+                        stored_field.number,
+                        *number,
+                        *raw_value,
+                    )
+                }) {
+                    // enum subfields are translated by the tables of the extension as well
+                    data_field = match extension {
+                        Some(extension) => extension.extend(subfield),
+                        None => subfield,
+                    };
+                }
+            }
+            if config.numeric_timestamps {
+                data_field = data_field.without_date_time();
+            }
+            // invalid elements of a bitfield array must not shift the following elements
+            if let Field::EnumField(enum_field) = &data_field {
+                if enum_field.is_bitfield()
+                    && stored_field.element_size == 1
+                    && stored_field.bytes.len() > 1
+                {
+                    stored_field.value = Value::NumberValueVecU8(stored_field.bytes.clone());
+                }
+            }
+            stored_field.value = match &mut data_field {
+                // without scale the field is serialized without units
+                Field::ValueField(value_field) if config.raw_values => {
+                    value_field.scale = None;
+                    stored_field.value
+                }
+                Field::ValueField(value_field)
+                    if config.coordinates_in_degrees
+                        && value_field
+                            .scale
+                            .as_ref()
+                            .is_some_and(Scale::is_semicircles) =>
+                {
+                    value_field.scale = Some(Scale::from(1.0, 0.0, "degrees".to_string()));
+                    stored_field.value.semicircles_to_degrees()
+                }
+                Field::ValueField(ValueField {
+                    scale: Some(scale), ..
+                }) => scale.apply(&stored_field.value),
+                _ => stored_field.value,
+            };
+            if (!data_field.is_unknown() || print_unknown)
+                && (!stored_field.value.is_invalid() || print_invalid)
             {
-                message_data.push(FieldValue {field: data_field.clone(), value: value.clone() });
+                message_data.push(FieldValue {
+                    field: data_field,
+                    value: stored_field.value.clone(),
+                });
             }
+            if !config.raw_values && !developer {
+                stored_fields.push(stored_field);
+            }
+        }
+        if !stored_fields.is_empty() {
+            message_data.extend(components::expand(
+                &self.message_type,
+                &stored_fields,
+                accumulators,
+                print_invalid,
+                extension,
+            ));
         }
-        (
+        Ok((
             Message::from(self.message_type.clone(), Messages { data: message_data }),
             position,
-        )
+        ))
     }
 }
 
 #[derive(Debug)]
 pub struct FieldDefinition {
     pub field: Field,
-    #[allow(dead_code)]
-    pub number: u8, // still here for debugging purposes
+    pub number: u8,
     pub size: u8,
     pub base_type_value_or_dev_index: u8,
 }
@@ -81,7 +196,8 @@ pub struct FieldDefinition {
 #[derive(Serialize, Debug)]
 pub struct MessageType {
     pub number: u16,
-    pub name: &'static str,
+    /// name of the profile or of a [ProfileExtension](crate::fit_file::ProfileExtension)
+    pub name: Cow<'static, str>,
 }
 
 impl PartialEq for MessageType {
@@ -94,7 +210,7 @@ impl Clone for MessageType {
     fn clone(&self) -> Self {
         MessageType {
             number: self.number,
-            name: self.name,
+            name: self.name.clone(),
         }
     }
 }
@@ -102,386 +218,393 @@ impl Clone for MessageType {
 impl MessageType {
     pub const FILE_ID: MessageType = MessageType {
         number: 0,
-        name: "File Id",
+        name: Cow::Borrowed("File Id"),
     };
     pub const CAPABILITIES: MessageType = MessageType {
         number: 1,
-        name: "Capabilities",
+        name: Cow::Borrowed("Capabilities"),
     };
     pub const DEVICE_SETTINGS: MessageType = MessageType {
         number: 2,
-        name: "Device settings",
+        name: Cow::Borrowed("Device settings"),
     };
     pub const USER_PROFILE: MessageType = MessageType {
         number: 3,
-        name: "User profile",
+        name: Cow::Borrowed("User profile"),
     };
     pub const HRM_PROFILE: MessageType = MessageType {
         number: 4,
-        name: "HRM profile",
+        name: Cow::Borrowed("HRM profile"),
     };
     pub const SDM_PROFILE: MessageType = MessageType {
         number: 5,
-        name: "SDM profile",
+        name: Cow::Borrowed("SDM profile"),
     };
     pub const BIKE_PROFILE: MessageType = MessageType {
         number: 6,
-        name: "Bike profile",
+        name: Cow::Borrowed("Bike profile"),
     };
     pub const ZONES_TARGET: MessageType = MessageType {
         number: 7,
-        name: "Zones target",
+        name: Cow::Borrowed("Zones target"),
     };
     pub const HR_ZONE: MessageType = MessageType {
         number: 8,
-        name: "HR zone",
+        name: Cow::Borrowed("HR zone"),
     };
     pub const POWER_ZONE: MessageType = MessageType {
         number: 9,
-        name: "Power zone",
+        name: Cow::Borrowed("Power zone"),
     };
     pub const MET_ZONE: MessageType = MessageType {
         number: 10,
-        name: "MET zone",
+        name: Cow::Borrowed("MET zone"),
     };
     pub const SPORT: MessageType = MessageType {
         number: 12,
-        name: "Sport",
+        name: Cow::Borrowed("Sport"),
     };
     pub const GOAL: MessageType = MessageType {
         number: 15,
-        name: "Goal",
+        name: Cow::Borrowed("Goal"),
     };
     pub const SESSION: MessageType = MessageType {
         number: 18,
-        name: "Session",
+        name: Cow::Borrowed("Session"),
     };
     pub const LAP: MessageType = MessageType {
         number: 19,
-        name: "Lap",
+        name: Cow::Borrowed("Lap"),
     };
     pub const RECORD: MessageType = MessageType {
         number: 20,
-        name: "Record",
+        name: Cow::Borrowed("Record"),
     };
     pub const EVENT: MessageType = MessageType {
         number: 21,
-        name: "Event",
+        name: Cow::Borrowed("Event"),
     };
     pub const DEVICE_INFO: MessageType = MessageType {
         number: 23,
-        name: "Device info",
+        name: Cow::Borrowed("Device info"),
     };
     pub const WORKOUT: MessageType = MessageType {
         number: 26,
-        name: "Workout",
+        name: Cow::Borrowed("Workout"),
     };
     pub const WORKOUT_STEP: MessageType = MessageType {
         number: 27,
-        name: "Workout step",
+        name: Cow::Borrowed("Workout step"),
     };
     pub const SCHEDULE: MessageType = MessageType {
         number: 28,
-        name: "Schedule",
+        name: Cow::Borrowed("Schedule"),
     };
     pub const WEIGHT_SCALE: MessageType = MessageType {
         number: 30,
-        name: "Weight scale",
+        name: Cow::Borrowed("Weight scale"),
     };
     pub const COURSE: MessageType = MessageType {
         number: 31,
-        name: "Course",
+        name: Cow::Borrowed("Course"),
     };
     pub const COURSE_POINT: MessageType = MessageType {
         number: 32,
-        name: "Course point",
+        name: Cow::Borrowed("Course point"),
     };
     pub const TOTALS: MessageType = MessageType {
         number: 33,
-        name: "Totals",
+        name: Cow::Borrowed("Totals"),
     };
     pub const ACTIVITY: MessageType = MessageType {
         number: 34,
-        name: "Activity",
+        name: Cow::Borrowed("Activity"),
     };
     pub const SOFTWARE: MessageType = MessageType {
         number: 35,
-        name: "Software",
+        name: Cow::Borrowed("Software"),
     };
     pub const FILE_CAPABILITIES: MessageType = MessageType {
         number: 37,
-        name: "File capabilities",
+        name: Cow::Borrowed("File capabilities"),
     };
     pub const MESSAGE_CAPABILITIES: MessageType = MessageType {
         number: 38,
-        name: "Message capabilities",
+        name: Cow::Borrowed("Message capabilities"),
     };
     pub const FIELD_CAPABILITIES: MessageType = MessageType {
         number: 39,
-        name: "Field capabilities",
+        name: Cow::Borrowed("Field capabilities"),
     };
     pub const FILE_CREATOR: MessageType = MessageType {
         number: 49,
-        name: "File creator",
+        name: Cow::Borrowed("File creator"),
     };
     pub const BLOOD_PRESSURE: MessageType = MessageType {
         number: 51,
-        name: "Blood pressure",
+        name: Cow::Borrowed("Blood pressure"),
     };
     pub const SPEED_ZONE: MessageType = MessageType {
         number: 53,
-        name: "Speed zone",
+        name: Cow::Borrowed("Speed zone"),
     };
     pub const MONITORING: MessageType = MessageType {
         number: 55,
-        name: "Monitoring",
+        name: Cow::Borrowed("Monitoring"),
     };
     pub const TRAINING_FILE: MessageType = MessageType {
         number: 72,
-        name: "Training file",
+        name: Cow::Borrowed("Training file"),
     };
     pub const HRV: MessageType = MessageType {
         number: 78,
-        name: "HRV",
+        name: Cow::Borrowed("HRV"),
     };
     pub const ANT_RX: MessageType = MessageType {
         number: 80,
-        name: "ANT rx",
+        name: Cow::Borrowed("ANT rx"),
     };
     pub const ANT_TX: MessageType = MessageType {
         number: 81,
-        name: "ANT tx",
+        name: Cow::Borrowed("ANT tx"),
     };
     pub const ANT_CHANNEL_ID: MessageType = MessageType {
         number: 82,
-        name: "ANT channel id",
+        name: Cow::Borrowed("ANT channel id"),
     };
     pub const LENGTH: MessageType = MessageType {
         number: 101,
-        name: "Length",
+        name: Cow::Borrowed("Length"),
     };
     pub const MONITORING_INFO: MessageType = MessageType {
         number: 103,
-        name: "Monitoring info",
+        name: Cow::Borrowed("Monitoring info"),
     };
     pub const PAD: MessageType = MessageType {
         number: 105,
-        name: "Pad",
+        name: Cow::Borrowed("Pad"),
     };
     pub const SLAVE_DEVICE: MessageType = MessageType {
         number: 106,
-        name: "Slave device",
+        name: Cow::Borrowed("Slave device"),
     };
     pub const CONNECTIVITY: MessageType = MessageType {
         number: 127,
-        name: "Connectivity",
+        name: Cow::Borrowed("Connectivity"),
     };
     pub const WEATHER_CONDITIONS: MessageType = MessageType {
         number: 128,
-        name: "Weather conditions",
+        name: Cow::Borrowed("Weather conditions"),
     };
     pub const WEATHER_ALERT: MessageType = MessageType {
         number: 129,
-        name: "Weather alert",
+        name: Cow::Borrowed("Weather alert"),
     };
     pub const CADENCE_ZONE: MessageType = MessageType {
         number: 131,
-        name: "Cadence zone",
+        name: Cow::Borrowed("Cadence zone"),
     };
     pub const HR: MessageType = MessageType {
         number: 132,
-        name: "HR",
+        name: Cow::Borrowed("HR"),
     };
     pub const SEGMENT_LAP: MessageType = MessageType {
         number: 142,
-        name: "Segment lap",
+        name: Cow::Borrowed("Segment lap"),
     };
     pub const MEMO_GLOB: MessageType = MessageType {
         number: 145,
-        name: "Memo glob",
+        name: Cow::Borrowed("Memo glob"),
     };
     pub const SEGMENT_ID: MessageType = MessageType {
         number: 148,
-        name: "Segment id",
+        name: Cow::Borrowed("Segment id"),
     };
     pub const SEGMENT_LEADERBOARD_ENTRY: MessageType = MessageType {
         number: 149,
-        name: "Segment leaderboard entry",
+        name: Cow::Borrowed("Segment leaderboard entry"),
     };
     pub const SEGMENT_POINT: MessageType = MessageType {
         number: 150,
-        name: "Segment point",
+        name: Cow::Borrowed("Segment point"),
     };
     pub const SEGMENT_FILE: MessageType = MessageType {
         number: 151,
-        name: "Segment file",
+        name: Cow::Borrowed("Segment file"),
     };
     pub const WORKOUT_SESSION: MessageType = MessageType {
         number: 158,
-        name: "Workout session",
+        name: Cow::Borrowed("Workout session"),
     };
     pub const WATCHFACE_SETTINGS: MessageType = MessageType {
         number: 159,
-        name: "Watchface settings",
+        name: Cow::Borrowed("Watchface settings"),
     };
     pub const GPS_METADATA: MessageType = MessageType {
         number: 160,
-        name: "GPS Metadata",
+        name: Cow::Borrowed("GPS Metadata"),
     };
     pub const CAMERA_EVENT: MessageType = MessageType {
         number: 161,
-        name: "Camera event",
+        name: Cow::Borrowed("Camera event"),
     };
     pub const TIMESTAMP_CORRELATION: MessageType = MessageType {
         number: 162,
-        name: "Timestamp correlation",
+        name: Cow::Borrowed("Timestamp correlation"),
     };
     pub const GYROSCOPE_DATA: MessageType = MessageType {
         number: 164,
-        name: "Gyroscope data",
+        name: Cow::Borrowed("Gyroscope data"),
     };
     pub const ACCELEROMETER_DATA: MessageType = MessageType {
         number: 165,
-        name: "Accelerometer data",
+        name: Cow::Borrowed("Accelerometer data"),
     };
     pub const THREE_D_SENSOR_CALIBRATION: MessageType = MessageType {
         number: 167,
-        name: "3D sensor calibration",
+        name: Cow::Borrowed("3D sensor calibration"),
     };
     pub const VIDEO_FRAME: MessageType = MessageType {
         number: 169,
-        name: "Video frame",
+        name: Cow::Borrowed("Video frame"),
     };
     pub const OBD_II_DATA: MessageType = MessageType {
         number: 174,
-        name: "OBD II data",
+        name: Cow::Borrowed("OBD II data"),
     };
     pub const NMEA_SENTENCE: MessageType = MessageType {
         number: 177,
-        name: "NMEA sentence",
+        name: Cow::Borrowed("NMEA sentence"),
     };
     pub const AVIATION_ATTITUDE: MessageType = MessageType {
         number: 178,
-        name: "Aviation attitude",
+        name: Cow::Borrowed("Aviation attitude"),
     };
     pub const VIDEO: MessageType = MessageType {
         number: 184,
-        name: "Video",
+        name: Cow::Borrowed("Video"),
     };
     pub const VIDEO_TITLE: MessageType = MessageType {
         number: 185,
-        name: "Video title",
+        name: Cow::Borrowed("Video title"),
     };
     pub const VIDEO_DESCRIPTION: MessageType = MessageType {
         number: 186,
-        name: "Video description",
+        name: Cow::Borrowed("Video description"),
     };
     pub const VIDEO_CLIP: MessageType = MessageType {
         number: 187,
-        name: "Video clip",
+        name: Cow::Borrowed("Video clip"),
     };
     pub const OHR_SETTINGS: MessageType = MessageType {
         number: 188,
-        name: "OHR settings",
+        name: Cow::Borrowed("OHR settings"),
     };
     pub const EXD_SCREEN_CONFIGURATION: MessageType = MessageType {
         number: 200,
-        name: "EXD screen configuration",
+        name: Cow::Borrowed("EXD screen configuration"),
     };
     pub const EXD_DATA_FIELD_CONFIGURATION: MessageType = MessageType {
         number: 201,
-        name: "EXD data field configuration",
+        name: Cow::Borrowed("EXD data field configuration"),
     };
     pub const EXD_DATA_CONCEPT_CONFIGURATION: MessageType = MessageType {
         number: 202,
-        name: "EXD data concept configuration",
+        name: Cow::Borrowed("EXD data concept configuration"),
     };
     pub const FIELD_DESCRIPTION: MessageType = MessageType {
         number: 206,
-        name: "Field description",
+        name: Cow::Borrowed("Field description"),
     };
     pub const DEVELOPER_DATA_ID: MessageType = MessageType {
         number: 207,
-        name: "Developer data id",
+        name: Cow::Borrowed("Developer data id"),
     };
     pub const MAGNETOMETER_DATA: MessageType = MessageType {
         number: 208,
-        name: "Magnetometer data",
+        name: Cow::Borrowed("Magnetometer data"),
     };
     pub const BAROMETER_DATA: MessageType = MessageType {
         number: 209,
-        name: "Barometer data",
+        name: Cow::Borrowed("Barometer data"),
     };
     pub const ONE_D_SENSOR_CALIBRATION: MessageType = MessageType {
         number: 210,
-        name: "1D sensor calibration",
+        name: Cow::Borrowed("1D sensor calibration"),
     };
     pub const TIME_IN_ZONE: MessageType = MessageType {
         number: 216,
-        name: "Time in zone",
+        name: Cow::Borrowed("Time in zone"),
     };
     pub const SET: MessageType = MessageType {
         number: 225,
-        name: "Set",
+        name: Cow::Borrowed("Set"),
     };
     pub const STRESS_LEVEL: MessageType = MessageType {
         number: 227,
-        name: "Stress level",
+        name: Cow::Borrowed("Stress level"),
     };
     pub const DIVE_SETTINGS: MessageType = MessageType {
         number: 258,
-        name: "Dive settings",
+        name: Cow::Borrowed("Dive settings"),
     };
     pub const DIVE_GAS: MessageType = MessageType {
         number: 259,
-        name: "Dive gas",
+        name: Cow::Borrowed("Dive gas"),
     };
     pub const DIVE_ALARM: MessageType = MessageType {
         number: 262,
-        name: "Dive alarm",
+        name: Cow::Borrowed("Dive alarm"),
     };
     pub const EXERCISE_TITLE: MessageType = MessageType {
         number: 264,
-        name: "Exercise title",
+        name: Cow::Borrowed("Exercise title"),
     };
     pub const DIVE_SUMMARY: MessageType = MessageType {
         number: 268,
-        name: "Dive summary",
+        name: Cow::Borrowed("Dive summary"),
     };
     pub const JUMP: MessageType = MessageType {
         number: 285,
-        name: "Jump",
+        name: Cow::Borrowed("Jump"),
     };
     pub const SPLIT: MessageType = MessageType {
         number: 312,
-        name: "Split",
+        name: Cow::Borrowed("Split"),
     };
     pub const SPLIT_SUMMARY: MessageType = MessageType {
         number: 313,
-        name: "Split summary",
+        name: Cow::Borrowed("Split summary"),
     };
     pub const CLIMB_PRO: MessageType = MessageType {
         number: 317,
-        name: "Climb pro",
+        name: Cow::Borrowed("Climb pro"),
     };
     pub const DEVICE_AUX_BATTERY_INFO: MessageType = MessageType {
         number: 375,
-        name: "Device AUX battery info",
+        name: Cow::Borrowed("Device AUX battery info"),
     };
     pub const MFG_RANGE_MIN: MessageType = MessageType {
         number: 0xFF00,
-        name: "MFG range min",
+        name: Cow::Borrowed("MFG range min"),
     };
     pub const MFG_RANGE_MAX: MessageType = MessageType {
         number: 0xFFFE,
-        name: "MFG range max",
+        name: Cow::Borrowed("MFG range max"),
     };
     pub const UNDOCUMENTED_CONNECTED_DEVICES: MessageType = MessageType {
         number: 147,
-        name: "Connected devices (undocumented)",
+        name: Cow::Borrowed("Connected devices (undocumented)"),
     };
 
-    pub fn resolve(i: u16) -> MessageType {
+    /// the message type of the [ProfileExtension](crate::fit_file::ProfileExtension), if any, or of the FIT profile
+    pub fn resolve(i: u16, extension: Option<&ProfileExtension>) -> MessageType {
+        extension
+            .and_then(|extension| extension.message_type(i))
+            .unwrap_or_else(|| MessageType::resolve_profile(i))
+    }
+
+    fn resolve_profile(i: u16) -> MessageType {
         match i {
             0 => Self::FILE_ID,
             1 => Self::CAPABILITIES,
@@ -578,10 +701,32 @@ impl MessageType {
             0xFF00 => Self::MFG_RANGE_MIN,
             0xFFFE => Self::MFG_RANGE_MAX,
             147 => Self::UNDOCUMENTED_CONNECTED_DEVICES,
-            1024 | _ => MessageType {
+            _ => MessageType {
                 number: i,
-                name: "Unknown",
+                name: Cow::Borrowed("Unknown"),
             },
         }
     }
+
+    /// resolves a message type of the FIT profile by its profile name like `file_id` or its name like `File ID`
+    pub fn resolve_by_name(name: &str) -> Option<MessageType> {
+        (0..=u16::MAX)
+            .map(MessageType::resolve_profile)
+            .filter(|message_type| message_type.name != "Unknown")
+            .find(|message_type| message_type.name == name || message_type.profile_name() == name)
+    }
+
+    /// name of the message type in the FIT profile as used by the FIT SDK, e.g. `file_id`
+    pub fn profile_name(&self) -> String {
+        match self.name.as_ref() {
+            "Unknown" => "unknown".to_string(),
+            "OBD II data" => "obdii_data".to_string(),
+            "3D sensor calibration" => "three_d_sensor_calibration".to_string(),
+            "1D sensor calibration" => "one_d_sensor_calibration".to_string(),
+            name => name
+                .trim_end_matches(" (undocumented)")
+                .to_lowercase()
+                .replace(' ', "_"),
+        }
+    }
 }
@@ -0,0 +1,284 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde::Deserialize;
+
+use crate::data_types::BaseType;
+use crate::fit_file::fields::{EnumField, Field, Scale, ValueField};
+use crate::fit_file::message_types::MessageType;
+
+/// Message types, fields and enum types that extend or override the FIT profile at runtime,
+/// e.g. undocumented Garmin messages. Read from JSON like
+/// ```json
+/// {
+///   "messages": {
+///     "104": {
+///       "name": "Battery (undocumented)",
+///       "fields": {
+///         "0": {"name": "voltage", "base_type": "uint16", "scale": 1000, "units": "V"},
+///         "3": {"name": "status", "type": "BatteryStatus"}
+///       }
+///     }
+///   },
+///   "types": {"BatteryStatus": {"0": "ok", "1": "low"}}
+/// }
+/// ```
+/// Fields with a `type` are enum fields translated by the table of that name in `types`,
+/// tables named like a type of the profile, e.g. `Manufacturer`, add or override its values.
+/// A `base_type` replaces the one of the field definition if the size of the field fits it.
+///
+/// The extension is used by the decoder of [FitFileConfig::profile_extension](crate::fit_file::FitFileConfig),
+/// which passes it to `MessageType::resolve` and `Field::resolve_field`.
+pub struct ProfileExtension {
+    messages: HashMap<u16, MessageExtension>,
+    /// tables of enum values by type name, shared with the enum fields that are translated by them
+    types: HashMap<String, Arc<HashMap<u32, String>>>,
+}
+
+struct MessageExtension {
+    name: Option<String>,
+    fields: HashMap<u8, FieldExtension>,
+}
+
+struct FieldExtension {
+    name: String,
+    /// type number of the base type
+    base_type: Option<u8>,
+    scale: Option<Scale>,
+    type_name: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ProfileExtensionJson {
+    #[serde(default)]
+    messages: HashMap<u16, MessageJson>,
+    #[serde(default)]
+    types: HashMap<String, HashMap<u32, String>>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MessageJson {
+    name: Option<String>,
+    #[serde(default)]
+    fields: HashMap<u8, FieldJson>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FieldJson {
+    name: String,
+    base_type: Option<String>,
+    scale: Option<f64>,
+    offset: Option<f64>,
+    units: Option<String>,
+    #[serde(rename = "type")]
+    type_name: Option<String>,
+}
+
+impl ProfileExtension {
+    /// reads the extension from JSON, see [ProfileExtension] for the layout
+    pub fn from_json(json: &str) -> Result<ProfileExtension, String> {
+        let extension: ProfileExtensionJson =
+            serde_json::from_str(json).map_err(|error| error.to_string())?;
+        let mut messages = HashMap::new();
+        for (message_number, message) in extension.messages {
+            let mut fields = HashMap::new();
+            for (field_number, field) in message.fields {
+                let base_type = match &field.base_type {
+                    Some(name) => Some(
+                        BaseType::parse_name(name)
+                            .ok_or(format!(
+                                "message {} field {}: unknown base type {:?}",
+                                message_number, field_number, name
+                            ))?
+                            .type_number,
+                    ),
+                    None => None,
+                };
+                if field.scale == Some(0.0) {
+                    return Err(format!(
+                        "message {} field {}: scale must not be 0",
+                        message_number, field_number
+                    ));
+                }
+                let scale =
+                    if field.scale.is_some() || field.offset.is_some() || field.units.is_some() {
+                        Some(Scale::from(
+                            field.scale.unwrap_or(1.0),
+                            field.offset.unwrap_or(0.0),
+                            field.units.unwrap_or_default(),
+                        ))
+                    } else {
+                        None
+                    };
+                fields.insert(
+                    field_number,
+                    FieldExtension {
+                        name: field.name,
+                        base_type,
+                        scale,
+                        type_name: field.type_name,
+                    },
+                );
+            }
+            messages.insert(
+                message_number,
+                MessageExtension {
+                    name: message.name,
+                    fields,
+                },
+            );
+        }
+        Ok(ProfileExtension {
+            messages,
+            types: extension
+                .types
+                .into_iter()
+                .map(|(type_name, values)| (type_name, Arc::new(values)))
+                .collect(),
+        })
+    }
+
+    /// the message type with the name of the extension
+    pub(crate) fn message_type(&self, message_number: u16) -> Option<MessageType> {
+        let name = self.messages.get(&message_number)?.name.as_ref()?;
+        Some(MessageType {
+            number: message_number,
+            name: Cow::Owned(name.clone()),
+        })
+    }
+
+    /// the field as defined by the extension
+    pub(crate) fn field(&self, message_number: u16, field_number: u8) -> Option<Field> {
+        let field = self.field_extension(message_number, field_number)?;
+        Some(match &field.type_name {
+            Some(type_name) => Field::EnumField(EnumField::extension(
+                field.name.clone(),
+                type_name.clone(),
+                self.types.get(type_name).cloned(),
+            )),
+            None => Field::ValueField(ValueField::from(field.name.clone(), field.scale.clone())),
+        })
+    }
+
+    /// the base type of the field as defined by the extension
+    pub(crate) fn base_type(&self, message_number: u16, field_number: u8) -> Option<BaseType> {
+        BaseType::parse(
+            &self
+                .field_extension(message_number, field_number)?
+                .base_type?,
+        )
+    }
+
+    /// an enum field of the profile with the table of the extension that is named like its type
+    pub(crate) fn extend(&self, field: Field) -> Field {
+        match field {
+            Field::EnumField(mut enum_field) => {
+                if let Some(values) = self.types.get(enum_field.type_name.as_ref()) {
+                    enum_field.extension_values = Some(Arc::clone(values));
+                }
+                Field::EnumField(enum_field)
+            }
+            field => field,
+        }
+    }
+
+    fn field_extension(&self, message_number: u16, field_number: u8) -> Option<&FieldExtension> {
+        self.messages
+            .get(&message_number)?
+            .fields
+            .get(&field_number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_types::Value;
+    use crate::fit_encoder::{encode, EncodeMessage, FitEncoderConfig};
+    use crate::fit_file::{FitFile, FitFileConfig};
+    use crate::fit_file_raw::{developer_apps, FitFileRaw};
+
+    #[test]
+    fn resolves_only_for_the_decoder_with_the_extension() {
+        let extension = ProfileExtension::from_json(
+            r#"{
+                "messages": {"104": {"name": "Battery", "fields": {"3": {"name": "status", "type": "BatteryStatus"}}}},
+                "types": {"BatteryStatus": {"1": "low"}, "Manufacturer": {"1": "my_brand"}}
+            }"#,
+        )
+        .unwrap();
+        let messages = [
+            EncodeMessage::new(0).field(1, Value::NumberValueU16(1)),
+            EncodeMessage::new(104).field(3, Value::EnumValue(vec![1])),
+        ];
+        let file = encode(&messages, FitEncoderConfig::default()).unwrap();
+        let extended = FitFileConfig {
+            profile_extension: Some(Arc::new(extension)),
+            ..FitFileConfig::default()
+        };
+        let profile = FitFileConfig {
+            include_unknown_message_types: true,
+            include_unknown_fields: true,
+            ..FitFileConfig::default()
+        };
+
+        let extended = FitFile::from(&file, &extended).unwrap();
+        let profile = FitFile::from(&file, &profile).unwrap();
+        let json = |fit_file: &FitFile| serde_json::to_string(&fit_file.messages).unwrap();
+        assert_eq!(
+            json(&extended),
+            r#"[{"message_type":"File Id","message":{"manufacturer":"my_brand"}},{"message_type":"Battery","message":{"status":"low"}}]"#
+        );
+        assert_eq!(
+            json(&profile),
+            r#"[{"message_type":"File Id","message":{"manufacturer":"Garmin"}},{"message_type":"Unknown104","message":{"unknown 104/3":[1]}}]"#
+        );
+    }
+
+    #[test]
+    fn resolves_native_fields_of_the_extension() {
+        let extension = ProfileExtension::from_json(
+            r#"{"messages": {"104": {"name": "Battery", "fields": {"2": {"name": "charge", "type": "uint8"}}}}}"#,
+        )
+        .unwrap();
+        let messages = [
+            EncodeMessage::developer_data_id(0, [7; 16]),
+            EncodeMessage::field_description(0, 0, &BaseType::UINT8, "Charge", "")
+                .field(14, Value::NumberValueU16(104))
+                .field(15, Value::NumberValueU8(2)),
+            EncodeMessage::by_name("record").unwrap().developer_field(
+                0,
+                0,
+                BaseType::UINT8,
+                Value::NumberValueU8(80),
+            ),
+        ];
+        let file = encode(&messages, FitEncoderConfig::default()).unwrap();
+        let extension = Arc::new(extension);
+        let config = FitFileConfig {
+            profile_extension: Some(extension.clone()),
+            ..FitFileConfig::default()
+        };
+
+        let fit_file = FitFile::from(&file, &config).unwrap();
+        assert_eq!(
+            serde_json::to_string(&fit_file.messages[2]).unwrap(),
+            r#"{"message_type":"Record","message":{"Charge":{"value":80,"native_field":"Battery.charge"}}}"#
+        );
+        let fit_files = [FitFileRaw::from(&file).unwrap()];
+        let native_field = |extension| {
+            developer_apps(&fit_files, extension)[0].fields[0]
+                .native_field
+                .clone()
+        };
+        assert_eq!(
+            native_field(Some(&extension)),
+            Some("Battery.charge".to_string())
+        );
+        assert_eq!(native_field(None), None);
+    }
+}
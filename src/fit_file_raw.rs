@@ -1,119 +1,76 @@
+mod apps;
+mod decoder;
+mod fit_csv;
+mod json;
 mod message_raw;
+mod records;
 
-use crate::fit_file_raw::message_raw::{FieldDefinitionRaw, MessageDefinitionRaw, MessageRaw};
+use crate::crc::CrcReport;
+use crate::error::{read_bytes, FitError};
+pub use apps::{developer_apps, DeveloperApp, DeveloperAppField};
+pub use decoder::FitDecoderRaw;
+pub use message_raw::{FieldDefinitionRaw, FieldValueRaw, Fields, MessageRaw, TypedMessageRaw};
+pub use records::{DataRecordRaw, DefinitionRecordRaw, FieldBytesRaw, FitFileRecordsRaw, RecordRaw};
 use serde::Serialize;
-use std::collections::HashMap;
+use std::io::Read;
 
 #[derive(Serialize)]
 pub struct FitFileRaw {
     pub messages: Vec<MessageRaw>,
+    /// results of the header and file CRC checks
+    pub crc: CrcReport,
 }
 
 impl FitFileRaw {
-    pub fn from(buffer: &Vec<u8>) -> FitFileRaw {
-        let header_info = &buffer[0..14];
-        let header_length = header_length(header_info);
-        let mut messages: Vec<MessageRaw> = Vec::new();
-        let mut current_position: usize = header_length;
-        let mut local_message_types: HashMap<u8, MessageDefinitionRaw> = HashMap::new();
-        let mut parse_configs = HashMap::new();
-        loop {
-            // exit on message crc
-            if current_position == buffer.len() - 2 {
-                break;
-            }
-            // start: record header
-            let header_type: u8 = buffer[current_position] >> 7 & 1;
-            let developer_flag: u8 = buffer[current_position] >> 5 & 1;
-            let local_message_number: u8 = buffer[current_position] & 0x0F;
-            if header_type == 1 {
-                panic!("!! Not implemented !! Compressed timestamp header needs special handling");
-            }
-            let definition_message: bool = buffer[current_position] >> 6 & 1 == 1;
-            current_position += 1;
-            // end: record header
-
-            if definition_message {
-                let mut fields: Vec<FieldDefinitionRaw> = vec![];
-                let _reserved = buffer[current_position]; // reserved
-                let endianness = buffer[current_position + 1]; // architecture
-                current_position += 2; // skip the header part besides the last byte for the field number
-                let type_fields: [u8; 2] = buffer[current_position..current_position + 2]
-                    .try_into()
-                    .unwrap();
-                let local_message_type_value: u16 = if endianness == 0 {
-                    u16::from_le_bytes(type_fields)
-                } else {
-                    u16::from_be_bytes(type_fields)
-                };
-
-                current_position += 2; // skip the header part besides the last byte for the field number
-                let number_of_fields: u8 = buffer[current_position];
-                current_position += 1;
-
-                for i in 0..number_of_fields {
-                    let field_index = (i as i32 * 3) as usize;
-                    let field_definition_number = buffer[current_position + field_index + 0];
-                    let field_length = buffer[current_position + field_index + 1];
-                    let base_type_value = buffer[current_position + field_index + 2];
-                    let field_definition = FieldDefinitionRaw {
-                        number: field_definition_number,
-                        size: field_length,
-                        base_type_value_or_dev_index: base_type_value,
-                        is_dev_field: false,
-                    };
-                    fields.push(field_definition);
-                }
-                current_position += number_of_fields as usize * 3;
+    /// parses all FIT files that are chained in the buffer, each with its own header and CRC
+    pub fn from_chained(buffer: &[u8]) -> Result<Vec<FitFileRaw>, FitError> {
+        let mut decoder = FitDecoderRaw::new(buffer);
+        let mut fit_files = vec![];
+        while decoder.next_file()? {
+            fit_files.push(FitFileRaw::read_file(&mut decoder)?);
+        }
+        Ok(fit_files)
+    }
 
-                if developer_flag == 1 {
-                    let number_of_developer_fields: u8 = buffer[current_position];
-                    if number_of_developer_fields > 0 {
-                        current_position += 1;
-                        for i in 0..number_of_developer_fields {
-                            let field_index = (i as i32 * 3) as usize;
-                            let field_definition_number = buffer[current_position + field_index + 0];
-                            let field_length = buffer[current_position + field_index + 1];
-                            let dev_index = buffer[current_position + field_index + 2];
+    /// parses the first FIT file in the buffer, see [FitFileRaw::from_chained] for chained files
+    pub fn from(buffer: &[u8]) -> Result<FitFileRaw, FitError> {
+        let mut decoder = FitDecoderRaw::new(buffer);
+        if !decoder.next_file()? {
+            return Err(FitError::UnexpectedEndOfFile {
+                offset: 0,
+                context: "header length".to_string(),
+            });
+        }
+        FitFileRaw::read_file(&mut decoder)
+    }
 
-                            let dev_field_definition = FieldDefinitionRaw {
-                                number: field_definition_number,
-                                size: field_length,
-                                base_type_value_or_dev_index: dev_index,
-                                is_dev_field: true,
-                            };
-                            fields.push(dev_field_definition);
-                        }
-                        current_position += number_of_developer_fields as usize * 3;
-                    }
-                }
-                let definition_message = MessageDefinitionRaw {
-                    message_type_number: local_message_type_value,
-                    fields,
-                };
-                local_message_types.insert(local_message_number, definition_message);
-                parse_configs.insert(local_message_number, endianness);
-            } else {
-                let option = local_message_types.get(&local_message_number);
-                if option.is_none() {
-                    panic!("What the heck is {}", local_message_number);
-                }
-                let definition_message = option.unwrap();
-                let endianness = parse_configs.get(&local_message_number).unwrap();
-                let message = definition_message.read_message(
-                    &current_position,
-                    buffer,
-                    endianness,
-                );
-                current_position = message.1;
-                messages.push(message.0);
-            }
+    /// collects the messages of the decoder's current FIT file
+    fn read_file<R: Read>(decoder: &mut FitDecoderRaw<R>) -> Result<FitFileRaw, FitError> {
+        let mut messages: Vec<MessageRaw> = Vec::new();
+        while let Some(message) = decoder.read_message()? {
+            messages.push(message);
         }
+        Ok(FitFileRaw {
+            messages,
+            crc: decoder.crc().clone(),
+        })
+    }
+}
 
-        FitFileRaw { messages }
+/// reads the header length and makes sure that the buffer contains the complete header
+fn header_length(header_info: &[u8]) -> Result<usize, FitError> {
+    let length = read_bytes(header_info, 0, 1, "header length")?[0] as usize;
+    if length < 12 {
+        return Err(FitError::InvalidHeader {
+            offset: 0,
+            context: format!("header length {} is smaller than 12 bytes", length),
+        });
     }
+    read_bytes(header_info, 0, length, "header")?;
+    Ok(length)
 }
 
-fn header_length(header_info: &[u8]) -> usize {
-    *header_info.get(0).unwrap() as usize
+/// expects a header that was checked by [header_length]
+fn data_size(header_info: &[u8]) -> usize {
+    u32::from_le_bytes(header_info[4..8].try_into().unwrap()) as usize
 }
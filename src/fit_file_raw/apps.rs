@@ -0,0 +1,180 @@
+use serde::Serialize;
+
+use crate::fit_file::fields::{DeveloperDataId, DeveloperField};
+use crate::fit_file::message_types::MessageType;
+use crate::fit_file::ProfileExtension;
+use crate::fit_file_raw::FitFileRaw;
+
+const FIELD_DESCRIPTION: u16 = 206;
+const DEVELOPER_DATA_ID: u16 = 207;
+
+/// An application that writes developer fields, e.g. a Connect IQ data field,
+/// with the fields it describes and the number of values it wrote.
+#[derive(Serialize)]
+pub struct DeveloperApp {
+    /// the `application_id` as UUID, see `DeveloperDataId::app_id`
+    pub app: String,
+    pub developer_data_index: u8,
+    pub manufacturer_id: Option<u16>,
+    pub application_version: Option<u32>,
+    pub fields: Vec<DeveloperAppField>,
+    /// number of values of all fields
+    pub count: usize,
+}
+
+#[derive(Serialize)]
+pub struct DeveloperAppField {
+    pub field_definition_number: u8,
+    /// `None` for values without `field_description` message
+    pub name: Option<String>,
+    pub units: Option<String>,
+    /// the profile field the developer field provides, e.g. `Record.power`
+    pub native_field: Option<String>,
+    pub count: usize,
+}
+
+/// Lists the applications of the developer fields in order of their `developer_data_id` messages.
+/// Applications of chained FIT files with the same `application_id` are combined.
+/// The profile extension resolves the native fields of the message types and fields it adds.
+pub fn developer_apps(
+    fit_files: &[FitFileRaw],
+    extension: Option<&ProfileExtension>,
+) -> Vec<DeveloperApp> {
+    let mut apps: Vec<DeveloperApp> = vec![];
+    for fit_file in fit_files {
+        // applications by developer data index, valid until the end of the FIT file
+        let mut developer_data_ids: Vec<DeveloperDataId> = vec![];
+        for message in &fit_file.messages {
+            match message.message_number {
+                DEVELOPER_DATA_ID => {
+                    if let Ok(developer_data_id) = DeveloperDataId::from_raw(message) {
+                        find_app(
+                            &mut apps,
+                            &developer_data_id.app_id(),
+                            Some(&developer_data_id),
+                            developer_data_id.developer_data_index,
+                        );
+                        developer_data_ids.retain(|id| {
+                            id.developer_data_index != developer_data_id.developer_data_index
+                        });
+                        developer_data_ids.push(developer_data_id);
+                    }
+                }
+                FIELD_DESCRIPTION => {
+                    if let Ok(mut developer_field) = DeveloperField::from_raw(message) {
+                        developer_field.developer_data_id = developer_data_ids
+                            .iter()
+                            .find(|id| {
+                                id.developer_data_index == developer_field.developer_data_index
+                            })
+                            .cloned();
+                        let app = find_app(
+                            &mut apps,
+                            &developer_field.app_id(),
+                            developer_field.developer_data_id.as_ref(),
+                            developer_field.developer_data_index,
+                        );
+                        let field = find_app_field(app, developer_field.field_definition_number);
+                        field.name = Some(developer_field.field_name.clone());
+                        field.units = developer_field.units.clone();
+                        if developer_field.native_mesg_num.is_some() {
+                            field.native_field =
+                                native_field(&developer_field, message.message_number, extension);
+                        }
+                    }
+                }
+                _ => {}
+            }
+            for entry in &message.fields.data {
+                let Some(developer_data_index) = entry.developer_data_index else {
+                    continue;
+                };
+                let (app_id, developer_data_id) = match &entry.developer_field {
+                    Some(developer_field) => (
+                        developer_field.app_id(),
+                        developer_field.developer_data_id.as_ref(),
+                    ),
+                    None => {
+                        let developer_data_id = developer_data_ids
+                            .iter()
+                            .find(|id| id.developer_data_index == developer_data_index);
+                        let app_id = match developer_data_id {
+                            Some(developer_data_id) => developer_data_id.app_id(),
+                            None => format!("developer_data_index:{}", developer_data_index),
+                        };
+                        (app_id, developer_data_id)
+                    }
+                };
+                let app = find_app(&mut apps, &app_id, developer_data_id, developer_data_index);
+                app.count += 1;
+                let field = find_app_field(app, entry.field_number);
+                field.count += 1;
+                if let Some(developer_field) = &entry.developer_field {
+                    // without native_mesg_num the native field belongs to the message of the value
+                    if field.native_field.is_none() {
+                        field.native_field =
+                            native_field(developer_field, message.message_number, extension);
+                    }
+                }
+            }
+        }
+    }
+    apps
+}
+
+/// the application with the identifier, added if it is new
+fn find_app<'a>(
+    apps: &'a mut Vec<DeveloperApp>,
+    app_id: &str,
+    developer_data_id: Option<&DeveloperDataId>,
+    developer_data_index: u8,
+) -> &'a mut DeveloperApp {
+    let position = match apps.iter().position(|app| app.app == app_id) {
+        Some(position) => position,
+        None => {
+            apps.push(DeveloperApp {
+                app: app_id.to_string(),
+                developer_data_index,
+                manufacturer_id: developer_data_id.and_then(|id| id.manufacturer_id),
+                application_version: developer_data_id.and_then(|id| id.application_version),
+                fields: vec![],
+                count: 0,
+            });
+            apps.len() - 1
+        }
+    };
+    &mut apps[position]
+}
+
+/// the field of the application with the number, added if it is new
+fn find_app_field(app: &mut DeveloperApp, field_definition_number: u8) -> &mut DeveloperAppField {
+    let position = match app
+        .fields
+        .iter()
+        .position(|field| field.field_definition_number == field_definition_number)
+    {
+        Some(position) => position,
+        None => {
+            app.fields.push(DeveloperAppField {
+                field_definition_number,
+                name: None,
+                units: None,
+                native_field: None,
+                count: 0,
+            });
+            app.fields.len() - 1
+        }
+    };
+    &mut app.fields[position]
+}
+
+/// the native field as `<message type>.<field>`, e.g. `Record.power`
+fn native_field(
+    developer_field: &DeveloperField,
+    message_number: u16,
+    extension: Option<&ProfileExtension>,
+) -> Option<String> {
+    let (message_type, field) = developer_field
+        .native_field(&MessageType::resolve(message_number, extension), extension)?;
+    Some(format!("{}.{}", message_type.name, field.name()?))
+}
@@ -0,0 +1,123 @@
+use std::io::Read;
+
+use crate::crc::CrcReport;
+use crate::data_types::{BaseType, Value};
+use crate::error::FitError;
+use crate::fit_file_raw::message_raw::{FieldValueRaw, MessageRaw};
+use crate::fit_file_raw::{data_size, header_length};
+use crate::record_header::TIMESTAMP_FIELD_NUMBER;
+use crate::record_reader::{next_chained_message, Record, RecordDecoder, RecordReader};
+
+/// Streaming decoder of raw messages, the counterpart of [FitDecoder](crate::fit_file::FitDecoder)
+/// for [FitFileRaw](crate::fit_file_raw::FitFileRaw).
+///
+/// Only the local message definitions are kept as state.
+/// Chained FIT files are decoded one after another when used as [Iterator],
+/// use [FitDecoderRaw::next_file] and [FitDecoderRaw::read_message] to process them separately.
+pub struct FitDecoderRaw<R: Read> {
+    records: RecordReader<R>,
+    /// absolute position of the record header of the last message
+    message_position: usize,
+}
+
+impl<R: Read> FitDecoderRaw<R> {
+    pub fn new(reader: R) -> FitDecoderRaw<R> {
+        FitDecoderRaw {
+            records: RecordReader::new(reader),
+            message_position: 0,
+        }
+    }
+
+    /// CRC results of the current FIT file, the file CRC is available after its last message
+    pub fn crc(&self) -> &CrcReport {
+        self.records.crc()
+    }
+
+    /// absolute byte position in the stream
+    pub fn position(&self) -> usize {
+        self.records.position()
+    }
+
+    /// absolute byte position of the record header of the last message that was read
+    pub fn message_position(&self) -> usize {
+        self.message_position
+    }
+
+    /// Skips the rest of the current FIT file and reads the header of the next one.
+    /// Returns `false` if the stream ends before another FIT file starts.
+    pub fn next_file(&mut self) -> Result<bool, FitError> {
+        let header = self.records.next_file(|header_info| {
+            let header_length = header_length(header_info)?;
+            Ok(((), header_length + data_size(header_info)))
+        })?;
+        Ok(header.is_some())
+    }
+
+    /// Reads the next message of the current FIT file.
+    /// Returns `None` after the last message, when the file CRC was read.
+    pub fn read_message(&mut self) -> Result<Option<MessageRaw>, FitError> {
+        while let Some(record) = self.records.read_record()? {
+            let record = match record {
+                Record::Definition(definition) => {
+                    definition.check_base_types(self.records.record_position())?;
+                    continue;
+                }
+                Record::Data(record) => record,
+            };
+            let definition = &record.definition;
+            let (mut message, _) = definition
+                .read_message(
+                    &0,
+                    &record.data,
+                    &definition.architecture,
+                    self.records.developer_fields(),
+                )
+                .map_err(|error| error.shifted(record.data_position()))?;
+            message.local_message_number = record.local_message_number();
+            if let Some(timestamp) = record.compressed_timestamp {
+                // the compressed header replaces the timestamp field of the message
+                message.fields.data.insert(
+                    0,
+                    FieldValueRaw {
+                        field_number: TIMESTAMP_FIELD_NUMBER,
+                        value: Value::NumberValueU32(timestamp),
+                        base_type: Some(BaseType::UINT32.type_number),
+                        size: Some(BaseType::UINT32.read_size as u8),
+                        developer_data_index: None,
+                        developer_field: None,
+                    },
+                );
+            }
+            self.records.update_developer_data(&record)?;
+            self.message_position = record.record_position;
+            return Ok(Some(message));
+        }
+        Ok(None)
+    }
+}
+
+impl<R: Read> RecordDecoder for FitDecoderRaw<R> {
+    type Reader = R;
+    type Message = MessageRaw;
+
+    fn record_reader(&mut self) -> &mut RecordReader<R> {
+        &mut self.records
+    }
+
+    fn next_file(&mut self) -> Result<bool, FitError> {
+        FitDecoderRaw::next_file(self)
+    }
+
+    fn read_message(&mut self) -> Result<Option<MessageRaw>, FitError> {
+        FitDecoderRaw::read_message(self)
+    }
+}
+
+impl<R: Read> Iterator for FitDecoderRaw<R> {
+    type Item = Result<MessageRaw, FitError>;
+
+    /// returns the messages of all chained FIT files in the stream
+    fn next(&mut self) -> Option<Self::Item> {
+        next_chained_message(self)
+    }
+}
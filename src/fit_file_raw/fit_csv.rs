@@ -0,0 +1,340 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::crc::CrcReport;
+use crate::data_types::Value;
+use crate::error::FitError;
+use crate::fit_file::fields::{Field, Scale, ValueField};
+use crate::fit_file::message_types::MessageType;
+use crate::fit_file_raw::message_raw::{FieldValueRaw, Fields, MessageRaw};
+use crate::fit_file_raw::FitFileRaw;
+use crate::record_header::TIMESTAMP_FIELD_NUMBER;
+
+/// message type and field numbers of a `Definition` row, `None` for message types unknown to the profile
+type CsvDefinition = Option<(MessageType, Vec<u8>)>;
+
+impl FitFileRaw {
+    /// Reads the CSV layout of the FIT SDK's `FitCSVTool`, as written by
+    /// [to_fit_csv](crate::export::fit_csv::to_fit_csv), into raw messages.
+    ///
+    /// Names of message types, fields and enum values are resolved by the profile and
+    /// scale and offset are reverted, so the values are the ones stored in a FIT file.
+    /// Quoted cells can contain commas, quotes and line breaks.
+    /// A data message only keeps the fields of its definition (and the timestamp of compressed headers),
+    /// fields expanded from components are dropped.
+    /// Message types and fields named `unknown`, developer fields and empty values are skipped.
+    /// The layout does not contain base types, fields with the base type `string` in the profile are read as text,
+    /// numbers are read as 32 bit values if they fit, otherwise as 64 bit values, and as `f64` if they have decimals.
+    /// The CRC report is empty.
+    pub fn from_fit_csv(csv: &str) -> Result<FitFileRaw, FitError> {
+        let message_numbers = message_numbers();
+        let mut fields_by_name: HashMap<(u16, String), Option<(u8, Field)>> = HashMap::new();
+        let mut definitions: HashMap<u8, CsvDefinition> = HashMap::new();
+        let mut messages = vec![];
+        for (row_offset, cells) in parse_rows(csv)? {
+            let invalid_csv = |context: String| FitError::InvalidCsv {
+                offset: row_offset,
+                context,
+            };
+            let row_type = cells[0].as_str();
+            if row_type.is_empty() || row_type == "Type" {
+                continue;
+            }
+            if cells.len() < 3 {
+                return Err(invalid_csv(format!(
+                    "expected type, local number and message, got {} cells",
+                    cells.len()
+                )));
+            }
+            let local_message_number = cells[1]
+                .parse::<u8>()
+                .map_err(|_| invalid_csv(format!("local number {:?} is not a number", cells[1])))?;
+            let message_type = message_numbers
+                .get(cells[2].as_str())
+                .map(|&message_number| MessageType::resolve(message_number, None));
+            let field_triplets = cells[3..].chunks(3);
+            let mut resolve_field = |message_type: &MessageType, field_name: &str| {
+                fields_by_name
+                    .entry((message_type.number, field_name.to_string()))
+                    .or_insert_with(|| Field::resolve_by_name(message_type, field_name))
+                    .clone()
+            };
+            match row_type {
+                "Definition" => {
+                    let definition = message_type.map(|message_type| {
+                        let field_numbers = field_triplets
+                            .filter_map(|triplet| resolve_field(&message_type, &triplet[0]))
+                            .map(|(field_number, _)| field_number)
+                            .collect();
+                        (message_type, field_numbers)
+                    });
+                    definitions.insert(local_message_number, definition);
+                }
+                "Data" => {
+                    let Some(definition) = definitions.get(&local_message_number) else {
+                        return Err(FitError::UndefinedLocalMessage {
+                            offset: row_offset,
+                            local_message_number,
+                        });
+                    };
+                    let Some((defined_type, field_numbers)) = definition else {
+                        continue;
+                    };
+                    if message_type.as_ref() != Some(defined_type) {
+                        return Err(invalid_csv(format!(
+                            "message {:?} does not match the definition of local number {}",
+                            cells[2], local_message_number
+                        )));
+                    }
+                    let mut fields: Vec<FieldValueRaw> = vec![];
+                    for triplet in field_triplets {
+                        let Some((field_number, field)) = resolve_field(defined_type, &triplet[0])
+                        else {
+                            continue;
+                        };
+                        let defined = field_numbers.contains(&field_number)
+                            || field_number == TIMESTAMP_FIELD_NUMBER;
+                        let value_text = triplet.get(1).map_or("", |value| value.as_str());
+                        if !defined
+                            || value_text.is_empty()
+                            || fields
+                                .iter()
+                                .any(|entry| entry.field_number == field_number)
+                        {
+                            continue;
+                        }
+                        let string = Field::is_string(defined_type.number, field_number);
+                        fields.push(FieldValueRaw {
+                            field_number,
+                            value: stored_value(&field, string, value_text),
+                            base_type: None,
+                            size: None,
+                            developer_data_index: None,
+                            developer_field: None,
+                        });
+                    }
+                    messages.push(MessageRaw {
+                        message_number: defined_type.number,
+                        fields: Fields { data: fields },
+                        local_message_number,
+                    });
+                }
+                _ => {
+                    return Err(invalid_csv(format!(
+                        "expected Definition or Data, got {:?}",
+                        row_type
+                    )))
+                }
+            }
+        }
+        Ok(FitFileRaw {
+            messages,
+            crc: CrcReport::default(),
+        })
+    }
+}
+
+/// message numbers by the names of the message types in the FIT profile, resolved once
+fn message_numbers() -> &'static HashMap<String, u16> {
+    static MESSAGE_NUMBERS: OnceLock<HashMap<String, u16>> = OnceLock::new();
+    MESSAGE_NUMBERS.get_or_init(|| {
+        (0..=u16::MAX)
+            .filter_map(|message_number| {
+                let message_type = MessageType::resolve(message_number, None);
+                (message_type.name != "Unknown")
+                    .then(|| (message_type.profile_name(), message_number))
+            })
+            .collect()
+    })
+}
+
+/// splits the CSV into rows of cells with the offset of every row, quotes of quoted cells are removed,
+/// the quote state is kept across lines so quoted cells can contain line breaks
+fn parse_rows(csv: &str) -> Result<Vec<(usize, Vec<String>)>, FitError> {
+    let mut rows = vec![];
+    let mut cells = vec![];
+    let mut cell = String::new();
+    let mut row_offset = 0;
+    let mut quoted = false;
+    let mut characters = csv.char_indices().peekable();
+    while let Some((offset, character)) = characters.next() {
+        let next = characters.peek().map(|&(_, next)| next);
+        match character {
+            '"' if quoted && next == Some('"') => {
+                cell.push('"');
+                characters.next();
+            }
+            '"' if quoted => quoted = false,
+            '"' if cell.is_empty() => quoted = true,
+            ',' if !quoted => cells.push(std::mem::take(&mut cell)),
+            '\r' if !quoted && next == Some('\n') => {}
+            '\n' if !quoted => {
+                cells.push(std::mem::take(&mut cell));
+                rows.push((row_offset, std::mem::take(&mut cells)));
+                row_offset = offset + 1;
+            }
+            character => cell.push(character),
+        }
+    }
+    if quoted {
+        return Err(FitError::InvalidCsv {
+            offset: row_offset,
+            context: "a quoted cell is not closed".to_string(),
+        });
+    }
+    if row_offset < csv.len() {
+        cells.push(cell);
+        rows.push((row_offset, cells));
+    }
+    Ok(rows)
+}
+
+/// the value as stored in a FIT file, elements of arrays are separated by `|`,
+/// the text of `string` fields is kept
+fn stored_value(field: &Field, string: bool, text: &str) -> Value {
+    if string {
+        return Value::StringValue(text.to_string());
+    }
+    let numbers = text
+        .split('|')
+        .map(|element| match field {
+            Field::EnumField(enum_field) => enum_field
+                .parse(element)
+                .map(|number| Number::Integer(i128::from(number)))
+                .or_else(|| Number::parse(element)),
+            Field::ValueField(ValueField {
+                scale: Some(scale), ..
+            }) => Number::parse(element).map(|number| number.unscaled(scale)),
+            _ => Number::parse(element),
+        })
+        .collect::<Option<Vec<Number>>>();
+    let Some(numbers) = numbers else {
+        return Value::StringValue(text.to_string());
+    };
+    let integers = numbers
+        .iter()
+        .map(|number| match number {
+            Number::Integer(integer) => Some(*integer),
+            Number::Decimal(_) => None,
+        })
+        .collect::<Option<Vec<i128>>>();
+    let single = numbers.len() == 1;
+    let Some(integers) = integers else {
+        let decimals = numbers.iter().map(Number::as_f64).collect::<Vec<f64>>();
+        return if single {
+            Value::NumberValueF64(decimals[0])
+        } else {
+            Value::NumberValueVecF64(decimals)
+        };
+    };
+    if let Ok(values) = convert::<u32>(&integers) {
+        if single {
+            Value::NumberValueU32(values[0])
+        } else {
+            Value::NumberValueVecU32(values)
+        }
+    } else if let Ok(values) = convert::<i32>(&integers) {
+        if single {
+            Value::NumberValueS32(values[0])
+        } else {
+            Value::NumberValueVecS32(values)
+        }
+    } else if let Ok(values) = convert::<u64>(&integers) {
+        if single {
+            Value::NumberValueU64(values[0])
+        } else {
+            Value::NumberValueVecU64(values)
+        }
+    } else if let Ok(values) = convert::<i64>(&integers) {
+        if single {
+            Value::NumberValueS64(values[0])
+        } else {
+            Value::NumberValueVecS64(values)
+        }
+    } else {
+        Value::StringValue(text.to_string())
+    }
+}
+
+fn convert<T: TryFrom<i128>>(integers: &[i128]) -> Result<Vec<T>, T::Error> {
+    integers
+        .iter()
+        .map(|&integer| T::try_from(integer))
+        .collect()
+}
+
+enum Number {
+    Integer(i128),
+    Decimal(f64),
+}
+
+impl Number {
+    fn parse(text: &str) -> Option<Number> {
+        if let Ok(integer) = text.parse::<i128>() {
+            return Some(Number::Integer(integer));
+        }
+        text.parse::<f64>()
+            .ok()
+            .filter(|decimal| decimal.is_finite())
+            .map(Number::Decimal)
+    }
+
+    /// the integer that is stored for a physical value, inverse of [Scale::apply]
+    fn unscaled(self, scale: &Scale) -> Number {
+        if scale.scale == 1.0 && scale.offset == 0.0 {
+            return self;
+        }
+        Number::Integer(((self.as_f64() + scale.offset) * scale.scale).round() as i128)
+    }
+
+    fn as_f64(&self) -> f64 {
+        match self {
+            Number::Integer(integer) => *integer as f64,
+            Number::Decimal(decimal) => *decimal,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::fit_csv::to_fit_csv;
+    use crate::fit_encoder::{encode, EncodeMessage, FitEncoderConfig};
+    use crate::fit_file::{FitFile, FitFileConfig};
+
+    #[test]
+    fn reads_the_fit_csv_it_exports() {
+        let messages = [EncodeMessage::new(0)
+            .field(1, Value::NumberValueU16(1))
+            .field(2, Value::NumberValueU16(3121))
+            .field(8, Value::StringValue("530\n\"plus\"".to_string()))];
+        let file = encode(&messages, FitEncoderConfig::default()).unwrap();
+        let csv = to_fit_csv(&FitFile::from(&file, &FitFileConfig::default()).unwrap());
+        assert!(csv.contains(",garmin_product,\"edge_530\",,"));
+
+        let fit_file = FitFileRaw::from_fit_csv(&csv).unwrap();
+        let values: Vec<(u8, &Value)> = fit_file.messages[0]
+            .fields
+            .data
+            .iter()
+            .map(|field| (field.field_number, &field.value))
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                (1, &Value::NumberValueU32(1)),
+                (2, &Value::NumberValueU32(3121)),
+                (8, &Value::StringValue("530\n\"plus\"".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_a_quoted_cell_that_is_not_closed() {
+        let csv = "Type,Local Number,Message,\nDefinition,0,file_id,product_name,1,\nData,0,file_id,product_name,\"Edge,\n";
+        assert!(matches!(
+            FitFileRaw::from_fit_csv(csv),
+            Err(FitError::InvalidCsv { offset: 64, .. })
+        ));
+    }
+}
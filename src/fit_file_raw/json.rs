@@ -0,0 +1,419 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::Formatter;
+
+use serde::de::{MapAccess, Visitor};
+use serde::{Deserialize, Deserializer};
+use serde_json::Map;
+
+use crate::crc::CrcReport;
+use crate::data_types::{BaseType, Value};
+use crate::error::FitError;
+use crate::fit_file::fields::{DeveloperDataId, DeveloperField};
+use crate::fit_file_raw::message_raw::{FieldValueRaw, Fields, MessageRaw};
+use crate::fit_file_raw::FitFileRaw;
+
+const FIELD_DESCRIPTION: u16 = 206;
+const DEVELOPER_DATA_ID: u16 = 207;
+
+/// the JSON of `fit-to-json`, messages in an array, a serialized [FitFileRaw] or single messages
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonInput {
+    Messages(Vec<JsonMessage>),
+    FitFile { messages: Vec<JsonMessage> },
+    Message(JsonMessage),
+}
+
+#[derive(Deserialize)]
+struct JsonMessage {
+    message_number: u16,
+    fields: OrderedFields,
+}
+
+/// the fields of a message in the order of the JSON object, which is the order of the FIT file
+struct OrderedFields(Vec<(String, serde_json::Value)>);
+
+impl<'de> Deserialize<'de> for OrderedFields {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(OrderedFieldsVisitor)
+    }
+}
+
+struct OrderedFieldsVisitor;
+
+impl<'de> Visitor<'de> for OrderedFieldsVisitor {
+    type Value = OrderedFields;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("an object of fields")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut fields = vec![];
+        while let Some(entry) = map.next_entry()? {
+            fields.push(entry);
+        }
+        Ok(OrderedFields(fields))
+    }
+}
+
+impl FitFileRaw {
+    /// Reads raw messages from the JSON that `fit-to-json` writes, an array of messages,
+    /// newline-delimited messages (`--ndjson`) or a serialized [FitFileRaw] with `messages`.
+    ///
+    /// A field is either its plain value or an object with `value`, `base_type` (by name like `uint16`
+    /// or by number) and `size` in bytes, as written by `fit-to-json --types`.
+    /// Developer fields are keyed by `dev:<developer_data_index>:<field_number>` and get the description of
+    /// the preceding `field_description` and `developer_data_id` messages, without base type also its base type.
+    /// Their values are converted back with the scale and offset of the description.
+    /// Without a base type numbers get the smallest unsigned (or, if negative, signed) integer type
+    /// that fits all elements and `float64` if they have decimals. `null` is an invalid value.
+    /// The fields are written in the order of the JSON, extra keys like `index` and `offset` are ignored.
+    /// The CRC report is empty.
+    pub fn from_json(json: &str) -> Result<FitFileRaw, FitError> {
+        let mut messages = vec![];
+        let mut developer_fields: HashMap<(u8, u8), DeveloperField> = HashMap::new();
+        let mut developer_data_ids: HashMap<u8, DeveloperDataId> = HashMap::new();
+        let mut inputs = serde_json::Deserializer::from_str(json).into_iter::<JsonInput>();
+        let mut offset = 0;
+        while let Some(input) = inputs.next() {
+            let invalid_json = |context: String| FitError::InvalidJson { offset, context };
+            let json_messages = match input {
+                Ok(JsonInput::Messages(json_messages))
+                | Ok(JsonInput::FitFile {
+                    messages: json_messages,
+                }) => json_messages,
+                Ok(JsonInput::Message(json_message)) => vec![json_message],
+                Err(error) if error.is_data() => {
+                    return Err(invalid_json(
+                        "expected messages with message_number and fields".to_string(),
+                    ))
+                }
+                Err(error) => return Err(invalid_json(error.to_string())),
+            };
+            for json_message in json_messages {
+                let mut message = read_message(json_message).map_err(|context| {
+                    invalid_json(format!("message {}: {}", messages.len(), context))
+                })?;
+                for field in &mut message.fields.data {
+                    let Some(developer_data_index) = field.developer_data_index else {
+                        continue;
+                    };
+                    field.developer_field = developer_fields
+                        .get(&(developer_data_index, field.field_number))
+                        .cloned();
+                    if field.base_type.is_none() {
+                        field.base_type = field
+                            .developer_field
+                            .as_ref()
+                            .map(|developer_field| developer_field.fit_base_type_id);
+                    }
+                    // `fit-to-json` writes developer values converted with scale and offset
+                    let scale = field
+                        .developer_field
+                        .as_ref()
+                        .and_then(DeveloperField::scale);
+                    let base_type = field.base_type.and_then(|number| BaseType::parse(&number));
+                    if let (Some(scale), Some(base_type)) = (scale, base_type) {
+                        field.value = scale.unapply(&field.value, &base_type);
+                    }
+                }
+                if message.message_number == FIELD_DESCRIPTION {
+                    if let Ok(mut developer_field) = DeveloperField::from_raw(&message) {
+                        developer_field.developer_data_id = developer_data_ids
+                            .get(&developer_field.developer_data_index)
+                            .cloned();
+                        developer_fields.insert(
+                            (
+                                developer_field.developer_data_index,
+                                developer_field.field_definition_number,
+                            ),
+                            developer_field,
+                        );
+                    }
+                }
+                if message.message_number == DEVELOPER_DATA_ID {
+                    if let Ok(developer_data_id) = DeveloperDataId::from_raw(&message) {
+                        developer_data_ids
+                            .insert(developer_data_id.developer_data_index, developer_data_id);
+                    }
+                }
+                messages.push(message);
+            }
+            offset = inputs.byte_offset();
+        }
+        Ok(FitFileRaw {
+            messages,
+            crc: CrcReport::default(),
+        })
+    }
+}
+
+fn read_message(json_message: JsonMessage) -> Result<MessageRaw, String> {
+    let mut data = vec![];
+    for (key, field_value) in &json_message.fields.0 {
+        let (developer_data_index, field_number) =
+            parse_key(key).ok_or(format!("field {:?} is not a field number", key))?;
+        let mut field = read_field(field_number, field_value)
+            .map_err(|context| format!("field {}: {}", key, context))?;
+        field.developer_data_index = developer_data_index;
+        data.push(field);
+    }
+    Ok(MessageRaw {
+        message_number: json_message.message_number,
+        fields: Fields { data },
+        local_message_number: 0,
+    })
+}
+
+/// the field number of a key, with the developer data index for `dev:<developer_data_index>:<field_number>`
+fn parse_key(key: &str) -> Option<(Option<u8>, u8)> {
+    match key.strip_prefix("dev:") {
+        Some(developer_key) => {
+            let (developer_data_index, field_number) = developer_key.split_once(':')?;
+            Some((
+                Some(developer_data_index.parse().ok()?),
+                field_number.parse().ok()?,
+            ))
+        }
+        None => Some((None, key.parse().ok()?)),
+    }
+}
+
+fn read_field(field_number: u8, field_value: &serde_json::Value) -> Result<FieldValueRaw, String> {
+    let empty = Map::new();
+    let (value, typed) = match field_value {
+        serde_json::Value::Object(typed) => (typed.get("value").ok_or("expected value")?, typed),
+        value => (value, &empty),
+    };
+    let base_type = match typed.get("base_type") {
+        None => None,
+        Some(serde_json::Value::String(name)) => {
+            Some(BaseType::parse_name(name).ok_or(format!("unknown base type {:?}", name))?)
+        }
+        Some(base_type) => Some(
+            base_type
+                .as_u64()
+                .and_then(|number| u8::try_from(number).ok())
+                .and_then(|number| BaseType::parse(&number))
+                .ok_or(format!("unknown base type {}", base_type))?,
+        ),
+    };
+    let size = match typed.get("size") {
+        None => None,
+        Some(size) => Some(
+            size.as_u64()
+                .and_then(|size| u8::try_from(size).ok())
+                .ok_or(format!("size {} is not between 0 and 255", size))?,
+        ),
+    };
+    Ok(FieldValueRaw {
+        field_number,
+        value: read_value(value)?,
+        base_type: base_type.map(|base_type| base_type.type_number),
+        size,
+        developer_data_index: None,
+        developer_field: None,
+    })
+}
+
+/// the value with the smallest type that fits all elements,
+/// arrays of a single byte are enums as `fit-to-json` writes them
+fn read_value(value: &serde_json::Value) -> Result<Value, String> {
+    let (elements, single) = match value {
+        serde_json::Value::Null => return Ok(Value::Invalid),
+        serde_json::Value::String(text) => return Ok(Value::StringValue(text.clone())),
+        serde_json::Value::Array(elements) if elements.is_empty() => {
+            return Err("empty array".to_string())
+        }
+        serde_json::Value::Array(elements) => (elements.as_slice(), false),
+        value => (std::slice::from_ref(value), true),
+    };
+    let mut integers = vec![];
+    let mut decimals = vec![];
+    for element in elements {
+        let serde_json::Value::Number(number) = element else {
+            return Err(format!("expected a number, got {}", element));
+        };
+        match (number.as_i64(), number.as_u64()) {
+            (Some(integer), _) => integers.push(i128::from(integer)),
+            (_, Some(integer)) => integers.push(i128::from(integer)),
+            _ => {}
+        }
+        decimals.push(number.as_f64().ok_or(format!("{} is no number", number))?);
+    }
+    if integers.len() < elements.len() {
+        return Ok(values(
+            decimals,
+            single,
+            Value::NumberValueF64,
+            Value::NumberValueVecF64,
+        ));
+    }
+    if !single && integers.len() == 1 {
+        if let Ok(integers) = convert(&integers) {
+            return Ok(Value::EnumValue(integers));
+        }
+    }
+    if let Ok(integers) = convert(&integers) {
+        Ok(values(
+            integers,
+            single,
+            Value::NumberValueU8,
+            Value::NumberValueVecU8,
+        ))
+    } else if let Ok(integers) = convert(&integers) {
+        Ok(values(
+            integers,
+            single,
+            Value::NumberValueU16,
+            Value::NumberValueVecU16,
+        ))
+    } else if let Ok(integers) = convert(&integers) {
+        Ok(values(
+            integers,
+            single,
+            Value::NumberValueU32,
+            Value::NumberValueVecU32,
+        ))
+    } else if let Ok(integers) = convert(&integers) {
+        Ok(values(
+            integers,
+            single,
+            Value::NumberValueU64,
+            Value::NumberValueVecU64,
+        ))
+    } else if let Ok(integers) = convert(&integers) {
+        Ok(values(
+            integers,
+            single,
+            Value::NumberValueS8,
+            Value::NumberValueVecS8,
+        ))
+    } else if let Ok(integers) = convert(&integers) {
+        Ok(values(
+            integers,
+            single,
+            Value::NumberValueS16,
+            Value::NumberValueVecS16,
+        ))
+    } else if let Ok(integers) = convert(&integers) {
+        Ok(values(
+            integers,
+            single,
+            Value::NumberValueS32,
+            Value::NumberValueVecS32,
+        ))
+    } else if let Ok(integers) = convert(&integers) {
+        Ok(values(
+            integers,
+            single,
+            Value::NumberValueS64,
+            Value::NumberValueVecS64,
+        ))
+    } else {
+        Err(format!("{} does not fit 64 bits", value))
+    }
+}
+
+fn convert<T: TryFrom<i128>>(integers: &[i128]) -> Result<Vec<T>, T::Error> {
+    integers
+        .iter()
+        .map(|&integer| T::try_from(integer))
+        .collect()
+}
+
+fn values<T>(
+    elements: Vec<T>,
+    single: bool,
+    one: fn(T) -> Value,
+    many: fn(Vec<T>) -> Value,
+) -> Value {
+    if single {
+        one(elements.into_iter().next().unwrap())
+    } else {
+        many(elements)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fit_encoder::{encode, encode_raw, EncodeMessage, FitEncoderConfig};
+    use crate::fit_file_raw::TypedMessageRaw;
+
+    /// FIT file with developer fields and a field whose size is larger than its value
+    fn fit_file() -> Vec<u8> {
+        let record = EncodeMessage::new(20)
+            .field(253, Value::NumberValueU32(1_000_000_000))
+            .field(0, Value::NumberValueS32(-123_456_789))
+            .field(3, Value::NumberValueU8(150))
+            .field(13, Value::NumberValueS8(-5))
+            .developer_field(0, 0, BaseType::FLOAT32, Value::NumberValueF32(1.5))
+            .developer_field(0, 1, BaseType::UINT16, Value::NumberValueU16(501));
+        let messages = [
+            EncodeMessage::new(0)
+                .field(0, Value::EnumValue(vec![4]))
+                .field_with_type(
+                    8,
+                    BaseType::STRING,
+                    Value::StringValue("Edge".to_string()),
+                    Some(20),
+                ),
+            EncodeMessage::developer_data_id(0, [7; 16]),
+            EncodeMessage::field_description(0, 0, &BaseType::FLOAT32, "Efficiency", "%"),
+            // the scale of 2 is written as uint8
+            EncodeMessage::field_description(0, 1, &BaseType::UINT16, "Power", "W")
+                .field(6, Value::NumberValueU8(2)),
+            record,
+        ];
+        encode(&messages, FitEncoderConfig::default()).unwrap()
+    }
+
+    fn to_json(file: &[u8], typed: bool) -> String {
+        let messages = FitFileRaw::from(file).unwrap().messages;
+        if typed {
+            let typed: Vec<_> = messages.iter().map(TypedMessageRaw).collect();
+            serde_json::to_string(&typed).unwrap()
+        } else {
+            serde_json::to_string(&messages).unwrap()
+        }
+    }
+
+    fn json_to_fit(json: &str) -> Vec<u8> {
+        let messages = FitFileRaw::from_json(json).unwrap().messages;
+        encode_raw(&messages, FitEncoderConfig::default()).unwrap()
+    }
+
+    #[test]
+    fn converts_typed_json_back_to_the_same_fit_file() {
+        let file = fit_file();
+        let json = to_json(&file, true);
+        assert_eq!(json_to_fit(&json), file);
+        assert_eq!(to_json(&json_to_fit(&json), true), json);
+    }
+
+    #[test]
+    fn converts_developer_values_with_scale_and_offset() {
+        let json = to_json(&fit_file(), true);
+        let messages: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(messages[4]["fields"]["dev:0:1"]["value"], 250.5);
+        let record = &FitFileRaw::from_json(&json).unwrap().messages[4];
+        assert_eq!(record.fields.data[5].value, Value::NumberValueF64(501.0));
+    }
+
+    #[test]
+    fn keeps_the_values_of_json_without_types() {
+        let json = to_json(&fit_file(), false);
+        let file = json_to_fit(&json);
+        assert!(FitFileRaw::from(&file).unwrap().crc.is_valid());
+        assert_eq!(to_json(&file, false), json);
+    }
+}
@@ -1,31 +1,47 @@
 use serde::{Serialize, Serializer};
-use serde::ser::{SerializeMap, SerializeStruct};
+use serde::ser::SerializeMap;
 use crate::data_types::{BaseType, Value};
+use crate::error::{read_bytes, FitError};
+use crate::fit_file::fields::DeveloperField;
+use crate::fit_file_raw::DefinitionRecordRaw;
+use crate::record_header::TIMESTAMP_FIELD_NUMBER;
 
 #[derive(Serialize)]
 pub struct MessageRaw {
     pub message_number: u16,
     pub fields: Fields,
+    /// local message type of the record header the message was read with
+    #[serde(skip)]
+    pub local_message_number: u8,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
 pub struct FieldDefinitionRaw {
-    pub(crate) number: u8,
-    pub(crate) size: u8,
-    pub(crate) base_type_value_or_dev_index: u8,
-    pub(crate) is_dev_field: bool,
-}
-
-#[derive(Serialize)]
-pub struct MessageDefinitionRaw {
-    pub message_type_number: u16,
-    pub fields: Vec<FieldDefinitionRaw>,
+    pub number: u8,
+    pub size: u8,
+    pub base_type_value_or_dev_index: u8,
+    pub is_dev_field: bool,
 }
 
 pub struct Fields {
     pub data: Vec<FieldValueRaw>,
 }
 
+impl Fields {
+    /// returns the value of the `timestamp` field, if present
+    pub fn timestamp(&self) -> Option<u32> {
+        self.data
+            .iter()
+            .find(|entry| {
+                entry.field_number == TIMESTAMP_FIELD_NUMBER && entry.developer_data_index.is_none()
+            })
+            .and_then(|entry| match entry.value {
+                Value::NumberValueU32(timestamp) => Some(timestamp),
+                _ => None,
+            })
+    }
+}
+
 impl Serialize for Fields {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -33,8 +49,12 @@ impl Serialize for Fields {
     {
         let mut serialized = serializer.serialize_map(Some(self.data.len())).unwrap();
         for entry in &self.data {
-            serialized.serialize_key(entry.field_number.to_string().as_str()).unwrap();
-            serialized.serialize_value(&entry.value).unwrap();
+            serialized.serialize_key(entry.key().as_str()).unwrap();
+            let value = FieldEntry {
+                entry,
+                typed: false,
+            };
+            serialized.serialize_value(&value).unwrap();
         }
 
         serialized.end()
@@ -44,35 +64,184 @@ impl Serialize for Fields {
 pub struct FieldValueRaw {
     pub field_number: u8,
     pub value: Value,
+    /// type number of the base type the value was read with, `None` if it is not known
+    pub base_type: Option<u8>,
+    /// size in bytes of the field definition, `None` if it is not known
+    pub size: Option<u8>,
+    /// developer data index of a developer field, `None` for fields of the profile
+    pub developer_data_index: Option<u8>,
+    /// description of a developer field from its `field_description` message
+    pub developer_field: Option<DeveloperField>,
+}
+
+impl FieldValueRaw {
+    /// the field number, or `dev:<developer_data_index>:<field_number>` for developer fields
+    pub fn key(&self) -> String {
+        match self.developer_data_index {
+            Some(developer_data_index) => {
+                format!("dev:{}:{}", developer_data_index, self.field_number)
+            }
+            None => self.field_number.to_string(),
+        }
+    }
+}
+
+/// Serializes a message like [MessageRaw] but with every field as object of
+/// `value`, `base_type` (by its name, e.g. `uint16`) and `size` in bytes,
+/// the types that are needed to encode the message again. Developer fields also have their description.
+pub struct TypedMessageRaw<'a>(pub &'a MessageRaw);
+
+impl Serialize for TypedMessageRaw<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut serialized = serializer.serialize_map(Some(2))?;
+        serialized.serialize_entry("message_number", &self.0.message_number)?;
+        serialized.serialize_entry("fields", &TypedFields(&self.0.fields))?;
+        serialized.end()
+    }
+}
+
+struct TypedFields<'a>(&'a Fields);
+
+impl Serialize for TypedFields<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut serialized = serializer.serialize_map(Some(self.0.data.len()))?;
+        for entry in &self.0.data {
+            serialized.serialize_entry(&entry.key(), &FieldEntry { entry, typed: true })?;
+        }
+        serialized.end()
+    }
+}
+
+/// the value of a field, an object with the description of a developer field and the types if `typed` is set.
+/// The value of a developer field is converted with the scale and offset of its description.
+struct FieldEntry<'a> {
+    entry: &'a FieldValueRaw,
+    typed: bool,
+}
+
+impl Serialize for FieldEntry<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let entry = self.entry;
+        if !self.typed && entry.developer_field.is_none() {
+            return entry.value.serialize(serializer);
+        }
+        let mut serialized = serializer.serialize_map(None)?;
+        if let Some(developer_field) = &entry.developer_field {
+            serialized.serialize_entry("name", &developer_field.field_name)?;
+            serialized.serialize_entry("app", &developer_field.app_id())?;
+            if let Some(units) = &developer_field.units {
+                serialized.serialize_entry("units", units)?;
+            }
+            if let Some(scale) = developer_field.scale {
+                serialized.serialize_entry("scale", &scale)?;
+            }
+            if let Some(offset) = developer_field.offset {
+                serialized.serialize_entry("offset", &offset)?;
+            }
+            if let Some(native_mesg_num) = developer_field.native_mesg_num {
+                serialized.serialize_entry("native_mesg_num", &native_mesg_num)?;
+            }
+            if let Some(native_field_num) = developer_field.native_field_num {
+                serialized.serialize_entry("native_field_num", &native_field_num)?;
+            }
+        }
+        // like the profile fields of a decoded message, developer values are converted with their scale and offset
+        match entry
+            .developer_field
+            .as_ref()
+            .and_then(DeveloperField::scale)
+        {
+            Some(scale) => serialized.serialize_entry("value", &scale.apply(&entry.value))?,
+            None => serialized.serialize_entry("value", &entry.value)?,
+        }
+        if self.typed {
+            if let Some(base_type) = entry.base_type.and_then(|number| BaseType::parse(&number)) {
+                serialized.serialize_entry("base_type", base_type.name())?;
+            }
+            if let Some(size) = entry.size {
+                serialized.serialize_entry("size", &size)?;
+            }
+        }
+        serialized.end()
+    }
 }
 
-impl MessageDefinitionRaw {
-    pub fn read_message(
+impl DefinitionRecordRaw {
+    /// reads the fields of a data message, developer fields with the base type
+    /// of their description, undescribed developer fields as bytes
+    pub(crate) fn read_message(
         &self,
         current_position: &usize,
-        buffer: &Vec<u8>,
+        buffer: &[u8],
         endianness: &u8,
-    ) -> (MessageRaw, usize) {
-        let mut position = current_position.clone();
+        developer_fields: &[DeveloperField],
+    ) -> Result<(MessageRaw, usize), FitError> {
+        let mut position = *current_position;
         let mut message_data = Vec::new();
         for field_definition in &self.fields {
             let read_size = field_definition.size;
-            let base_type = if field_definition.is_dev_field {
-                BaseType::parse(&0)
+            let developer_field = developer_fields.iter().find(|developer_field| {
+                field_definition.is_dev_field
+                    && developer_field.developer_data_index
+                        == field_definition.base_type_value_or_dev_index
+                    && developer_field.field_definition_number == field_definition.number
+            });
+            let mut base_type = if let Some(developer_field) = developer_field {
+                BaseType::parse(&developer_field.fit_base_type_id).ok_or(
+                    FitError::UnknownBaseType {
+                        offset: position,
+                        base_type: developer_field.fit_base_type_id,
+                    },
+                )?
+            } else if field_definition.is_dev_field {
+                BaseType::BYTE
             } else {
-                BaseType::parse(&field_definition.base_type_value_or_dev_index)
+                BaseType::parse(&field_definition.base_type_value_or_dev_index).ok_or(
+                    FitError::UnknownBaseType {
+                        offset: position,
+                        base_type: field_definition.base_type_value_or_dev_index,
+                    },
+                )?
             };
-            let end = position + (read_size as usize);
-            let data = &buffer[position..end];
-            let value = ((base_type).read)(&base_type, data, endianness);
+            // like the FIT SDK, a field whose size does not fit its base type is read as bytes
+            if read_size as usize % base_type.read_size != 0 {
+                base_type = BaseType::BYTE;
+            }
+            let data = read_bytes(buffer, position, read_size as usize, "field value")?;
+            let mut value = ((base_type).read)(&base_type, data, endianness);
+            if let Some(developer_field) = developer_field {
+                value = developer_field.elements(value);
+            }
             position += read_size as usize;
             if !value.is_invalid() {
-                message_data.push(FieldValueRaw { field_number: field_definition.number, value: value.clone() });
+                message_data.push(FieldValueRaw {
+                    field_number: field_definition.number,
+                    value: value.clone(),
+                    base_type: Some(base_type.type_number),
+                    size: Some(read_size),
+                    developer_data_index: field_definition
+                        .is_dev_field
+                        .then_some(field_definition.base_type_value_or_dev_index),
+                    developer_field: developer_field.cloned(),
+                });
             }
         }
-        (
-            MessageRaw { message_number: self.message_type_number, fields: Fields{data: message_data }},
+        Ok((
+            MessageRaw {
+                message_number: self.message_number,
+                fields: Fields { data: message_data },
+                local_message_number: 0,
+            },
             position,
-        )
+        ))
     }
 }
@@ -0,0 +1,325 @@
+use std::io::Read;
+
+use crate::crc::crc16;
+use crate::data_types::{BaseType, Value};
+use crate::error::FitError;
+use crate::fit_file_raw::message_raw::FieldDefinitionRaw;
+use crate::fit_file_raw::{data_size, header_length};
+use crate::record_header::RecordHeader;
+use crate::record_reader::{DataRecord, Record, RecordReader};
+
+/// A FIT file as all of its definition and data records in file order with their original bytes.
+///
+/// Unlike [FitFileRaw](crate::fit_file_raw::FitFileRaw) nothing is dropped or decoded:
+/// [FitFileRecordsRaw::to_bytes] writes the file byte by byte as it was read,
+/// including invalid values, padding, reserved bytes and wrong CRCs.
+/// After editing the records, [FitFileRecordsRaw::update_header_and_crc] fixes the data size and the CRCs.
+pub struct FitFileRecordsRaw {
+    /// the header as read, 12 or 14 bytes or longer
+    pub header: Vec<u8>,
+    pub records: Vec<RecordRaw>,
+    /// the file CRC as read, little endian
+    pub crc: [u8; 2],
+}
+
+pub enum RecordRaw {
+    Definition(DefinitionRecordRaw),
+    Data(DataRecordRaw),
+}
+
+#[derive(Clone)]
+pub struct DefinitionRecordRaw {
+    /// the record header byte with the local message number and the developer data flag
+    pub record_header: u8,
+    pub reserved: u8,
+    /// 0 for little endian, 1 for big endian
+    pub architecture: u8,
+    pub message_number: u16,
+    /// the field definitions followed by the developer field definitions,
+    /// the number of developer fields is only written if the record header has the developer data flag
+    pub fields: Vec<FieldDefinitionRaw>,
+}
+
+pub struct DataRecordRaw {
+    /// the record header byte with the local message number, or the time offset of a compressed timestamp header
+    pub record_header: u8,
+    /// message number of the definition the record was read with
+    pub message_number: u16,
+    /// architecture of the definition the record was read with
+    pub architecture: u8,
+    pub fields: Vec<FieldBytesRaw>,
+}
+
+/// the bytes of a field as stored in a data record
+pub struct FieldBytesRaw {
+    pub number: u8,
+    /// base type of the field definition or the developer data index of a developer field
+    pub base_type_value_or_dev_index: u8,
+    pub is_dev_field: bool,
+    pub bytes: Vec<u8>,
+}
+
+impl DefinitionRecordRaw {
+    /// checks that the base types of the fields of the profile are known,
+    /// `record_position` is the position of the record header
+    pub(crate) fn check_base_types(&self, record_position: usize) -> Result<(), FitError> {
+        let fields = self.fields.iter().filter(|field| !field.is_dev_field);
+        for (i, field_definition) in fields.enumerate() {
+            let base_type = field_definition.base_type_value_or_dev_index;
+            if BaseType::parse(&base_type).is_none() {
+                return Err(FitError::UnknownBaseType {
+                    // record header and 5 bytes of definition header before the field definitions
+                    offset: record_position + 6 + i * 3 + 2,
+                    base_type,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<&DataRecord> for DataRecordRaw {
+    /// splits the data into the bytes of the fields
+    fn from(record: &DataRecord) -> DataRecordRaw {
+        let mut position = 0;
+        let fields = record
+            .definition
+            .fields
+            .iter()
+            .map(|field_definition| {
+                let size = field_definition.size as usize;
+                position += size;
+                FieldBytesRaw {
+                    number: field_definition.number,
+                    base_type_value_or_dev_index: field_definition.base_type_value_or_dev_index,
+                    is_dev_field: field_definition.is_dev_field,
+                    bytes: record.data[position - size..position].to_vec(),
+                }
+            })
+            .collect();
+        DataRecordRaw {
+            record_header: record.record_header,
+            message_number: record.definition.message_number,
+            architecture: record.definition.architecture,
+            fields,
+        }
+    }
+}
+
+impl FieldBytesRaw {
+    /// Decodes the bytes with the base type of the field definition, developer fields are read as bytes.
+    /// Returns [Value::Invalid] for unknown base types and sizes that do not fit the base type.
+    pub fn value(&self, architecture: u8) -> Value {
+        let base_type = if self.is_dev_field {
+            Some(BaseType::BYTE)
+        } else {
+            BaseType::parse(&self.base_type_value_or_dev_index)
+        };
+        match base_type {
+            Some(base_type) if self.bytes.len() % base_type.read_size == 0 => {
+                (base_type.read)(&base_type, &self.bytes, &architecture)
+            }
+            _ => Value::Invalid,
+        }
+    }
+}
+
+impl FitFileRecordsRaw {
+    /// reads all FIT files that are chained in the buffer, each with its own header and CRC
+    pub fn from_chained(buffer: &[u8]) -> Result<Vec<FitFileRecordsRaw>, FitError> {
+        let mut reader = RecordReader::new(buffer);
+        let mut fit_files = vec![];
+        while let Some(fit_file) = FitFileRecordsRaw::read_file(&mut reader)? {
+            fit_files.push(fit_file);
+        }
+        Ok(fit_files)
+    }
+
+    /// reads the first FIT file in the buffer, see [FitFileRecordsRaw::from_chained] for chained files
+    pub fn from(buffer: &[u8]) -> Result<FitFileRecordsRaw, FitError> {
+        FitFileRecordsRaw::read_file(&mut RecordReader::new(buffer))?.ok_or(
+            FitError::UnexpectedEndOfFile {
+                offset: 0,
+                context: "header length".to_string(),
+            },
+        )
+    }
+
+    /// reads the next FIT file of the reader, `None` at the end of the stream
+    fn read_file<R: Read>(
+        reader: &mut RecordReader<R>,
+    ) -> Result<Option<FitFileRecordsRaw>, FitError> {
+        let Some(header) = reader.next_file(|header_info| {
+            let header_length = header_length(header_info)?;
+            Ok((header_info.to_vec(), header_length + data_size(header_info)))
+        })?
+        else {
+            return Ok(None);
+        };
+        let mut records = vec![];
+        while let Some(record) = reader.read_record()? {
+            records.push(match record {
+                Record::Definition(definition) => RecordRaw::Definition((*definition).clone()),
+                Record::Data(data) => RecordRaw::Data(DataRecordRaw::from(&data)),
+            });
+        }
+        let crc = reader.crc().file.as_ref().map_or(0, |crc| crc.expected);
+        Ok(Some(FitFileRecordsRaw {
+            header,
+            records,
+            crc: crc.to_le_bytes(),
+        }))
+    }
+
+    /// the bytes of all records, without header and file CRC
+    pub fn records_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        for record in &self.records {
+            match record {
+                RecordRaw::Definition(definition) => {
+                    let message_number = if definition.architecture == 0 {
+                        definition.message_number.to_le_bytes()
+                    } else {
+                        definition.message_number.to_be_bytes()
+                    };
+                    let (fields, developer_fields): (Vec<_>, Vec<_>) = definition
+                        .fields
+                        .iter()
+                        .partition(|field| !field.is_dev_field);
+                    bytes.extend([
+                        definition.record_header,
+                        definition.reserved,
+                        definition.architecture,
+                        message_number[0],
+                        message_number[1],
+                        fields.len() as u8,
+                    ]);
+                    let field_definition_bytes = |field: &&FieldDefinitionRaw| {
+                        [field.number, field.size, field.base_type_value_or_dev_index]
+                    };
+                    bytes.extend(fields.iter().flat_map(field_definition_bytes));
+                    if RecordHeader::parse(definition.record_header).developer_data {
+                        bytes.push(developer_fields.len() as u8);
+                        bytes.extend(developer_fields.iter().flat_map(field_definition_bytes));
+                    }
+                }
+                RecordRaw::Data(data) => {
+                    bytes.push(data.record_header);
+                    for field in &data.fields {
+                        bytes.extend(&field.bytes);
+                    }
+                }
+            }
+        }
+        bytes
+    }
+
+    /// the FIT file with the header and file CRC as stored
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.header.clone();
+        bytes.extend(self.records_bytes());
+        bytes.extend(self.crc);
+        bytes
+    }
+
+    /// Sets the data size of the header to the size of the records and recomputes the header CRC,
+    /// unless it is 0x0000 or the header has none, and the file CRC.
+    pub fn update_header_and_crc(&mut self) {
+        let records_bytes = self.records_bytes();
+        self.header[4..8].copy_from_slice(&(records_bytes.len() as u32).to_le_bytes());
+        if self.header.len() >= 14 && self.header[12..14] != [0, 0] {
+            let header_crc = crc16(&self.header[..12]);
+            self.header[12..14].copy_from_slice(&header_crc.to_le_bytes());
+        }
+        let mut content = self.header.clone();
+        content.extend(records_bytes);
+        self.crc = crc16(&content).to_le_bytes();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fit_encoder::{encode, EncodeMessage, FitEncoderConfig};
+    use crate::fit_file_raw::FitFileRaw;
+
+    /// FIT file with records of heart rates and a developer field
+    fn fit_file(big_endian: bool) -> Vec<u8> {
+        let mut messages = vec![
+            EncodeMessage::new(0).field(0, Value::EnumValue(vec![4])),
+            EncodeMessage::developer_data_id(0, [7; 16]),
+            EncodeMessage::field_description(0, 0, &BaseType::UINT16, "Power", "W"),
+        ];
+        for heart_rate in [140, 141, 142] {
+            messages.push(
+                EncodeMessage::new(20)
+                    .field(3, Value::NumberValueU8(heart_rate))
+                    .developer_field(0, 0, BaseType::UINT16, Value::NumberValueU16(200)),
+            );
+        }
+        let config = FitEncoderConfig {
+            big_endian,
+            ..FitEncoderConfig::default()
+        };
+        encode(&messages, config).unwrap()
+    }
+
+    #[test]
+    fn writes_the_bytes_it_has_read() {
+        let file = fit_file(false);
+        let fit_file_records = FitFileRecordsRaw::from(&file).unwrap();
+        // the definitions of four message types and six data records
+        assert_eq!(fit_file_records.records.len(), 10);
+        assert_eq!(fit_file_records.to_bytes(), file);
+    }
+
+    #[test]
+    fn writes_the_bytes_of_chained_files() {
+        let mut buffer = fit_file(false);
+        let mut second_file = fit_file(true);
+        // a wrong CRC and a reserved byte are kept as well
+        second_file[14 + 1] = 0xAA;
+        let crc_position = second_file.len() - 2;
+        second_file[crc_position] ^= 0xFF;
+        buffer.extend(&second_file);
+
+        let fit_files = FitFileRecordsRaw::from_chained(&buffer).unwrap();
+        assert_eq!(fit_files.len(), 2);
+        let RecordRaw::Definition(definition) = &fit_files[1].records[0] else {
+            panic!("expected a definition record");
+        };
+        assert_eq!(definition.reserved, 0xAA);
+        assert_eq!(definition.architecture, 1);
+        let bytes = fit_files.iter().flat_map(FitFileRecordsRaw::to_bytes);
+        assert_eq!(bytes.collect::<Vec<_>>(), buffer);
+    }
+
+    #[test]
+    fn updates_header_and_crc_after_editing() {
+        let mut fit_file_records = FitFileRecordsRaw::from(&fit_file(false)).unwrap();
+        // drops the last record and raises the heart rate of the others
+        fit_file_records.records.pop();
+        for record in &mut fit_file_records.records {
+            if let RecordRaw::Data(data) = record {
+                if data.message_number == 20 {
+                    data.fields[0].bytes[0] += 10;
+                }
+            }
+        }
+        fit_file_records.update_header_and_crc();
+        let file = fit_file_records.to_bytes();
+
+        let fit_file_raw = FitFileRaw::from(&file).unwrap();
+        assert!(fit_file_raw.crc.is_valid());
+        let heart_rates = fit_file_raw
+            .messages
+            .iter()
+            .filter(|message| message.message_number == 20)
+            .map(|message| message.fields.data[0].value.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            heart_rates,
+            vec![Value::NumberValueU8(150), Value::NumberValueU8(151)]
+        );
+    }
+}
@@ -1,3 +1,11 @@
+pub mod crc;
+pub mod data_types;
+pub mod date_time;
+pub mod error;
+pub mod export;
+pub mod fit_encoder;
 pub mod fit_file;
 pub mod fit_file_raw;
-mod data_types;
+pub mod ndjson;
+mod record_header;
+mod record_reader;
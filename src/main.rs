@@ -1,9 +1,20 @@
+use std::collections::HashMap;
+use std::fs;
 use std::fs::File;
+use std::io;
 use std::io::BufReader;
 use std::io::Read;
+use std::path::Path;
+use std::process::exit;
+use std::sync::Arc;
 
 use clap::{Args, Parser, Subcommand};
-use garmin_fit::fit_file::{FitFile, FitFileConfig};
+use garmin_fit::export::{csv, fit_csv, geojson, gpx, tcx};
+use garmin_fit::fit_file::{FitDecoder, FitFile, FitFileConfig, ProfileExtension};
+use garmin_fit::fit_file_raw::{developer_apps, FitFileRaw};
+use garmin_fit::ndjson::NdjsonLine;
+use itertools::Itertools;
+use serde::Serialize;
 
 // CLI / clap definitions
 #[derive(Parser)]
@@ -23,6 +34,32 @@ struct Cli {
     unknown_message_types: bool,
     #[arg(short, long, help = "Output invalid values")]
     invalid_values: bool,
+    #[arg(
+        long,
+        help = "Output values as stored, without scale, offset and units"
+    )]
+    raw_values: bool,
+    #[arg(
+        long,
+        help = "Output timestamps as seconds since the FIT epoch instead of RFC 3339"
+    )]
+    numeric_timestamps: bool,
+    #[arg(
+        long,
+        help = "Output positions in decimal degrees instead of semicircles"
+    )]
+    degrees: bool,
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "JSON file with message types, fields and enum types that extend the FIT profile"
+    )]
+    profile_ext: Option<String>,
+    #[arg(
+        long,
+        help = "Output raw, header and validate as array of the chained FIT files, also for a single file"
+    )]
+    array: bool,
 }
 
 #[derive(Subcommand)]
@@ -32,9 +69,31 @@ enum Commands {
     #[command(about = "Return messages defined by the -m parameter")]
     Messages(MessagesArgs),
     #[command(about = "Outputs all messages, incl. unknown messages and invalid fields")]
-    Raw,
+    Raw(NdjsonArgs),
     #[command(about = "Show parsed header")]
     Header,
+    #[command(about = "Validate header and file CRC, exits with 1 on mismatch")]
+    Validate,
+    #[command(about = "Export records as GPX 1.1 track, course points as waypoints")]
+    Gpx,
+    #[command(about = "Export sessions, laps and records as TCX")]
+    Tcx,
+    #[command(
+        about = "Export laps as GeoJSON lines, course points, segment points and session starts as points"
+    )]
+    Geojson(GeojsonArgs),
+    #[command(
+        about = "Export one CSV per message type, or the message type defined by the -m parameter"
+    )]
+    Csv(CsvArgs),
+    #[command(
+        about = "Export definitions and data messages in the CSV layout of the FIT SDK's FitCSVTool"
+    )]
+    FitCsv,
+    #[command(
+        about = "List the apps of the developer fields with their fields and number of values"
+    )]
+    Apps,
 }
 
 #[derive(Args)]
@@ -46,6 +105,58 @@ struct MessagesArgs {
         help = "Message types as enumerated from 'summary' command. Can be repeated for multiple messages."
     )]
     message_types: Vec<String>,
+    #[command(flatten)]
+    ndjson: NdjsonArgs,
+}
+
+#[derive(Args)]
+struct NdjsonArgs {
+    #[arg(
+        long,
+        help = "Output one message per line (newline-delimited JSON) while the file is decoded"
+    )]
+    ndjson: bool,
+    #[arg(
+        long,
+        requires = "ndjson",
+        help = "Add the index of the message in the file to every line"
+    )]
+    index: bool,
+    #[arg(
+        long,
+        requires = "ndjson",
+        help = "Add the byte offset of the message in the file to every line"
+    )]
+    offset: bool,
+}
+
+#[derive(Args)]
+struct GeojsonArgs {
+    #[arg(
+        long,
+        value_name = "COUNT",
+        help = "Simplify every line to at most COUNT positions"
+    )]
+    max_vertices: Option<usize>,
+}
+
+#[derive(Args)]
+struct CsvArgs {
+    #[arg(
+        short,
+        long = "message_type",
+        value_name = "MESSAGE_TYPE",
+        help = "Message type as enumerated from 'summary' command, written to stdout"
+    )]
+    message_type: Option<String>,
+    #[arg(
+        short,
+        long,
+        value_name = "DIRECTORY",
+        default_value = ".",
+        help = "Directory for the CSV files of all message types"
+    )]
+    output: String,
 }
 
 fn main() {
@@ -54,20 +165,36 @@ fn main() {
     let file_name = &args.file;
     let file = File::open(file_name);
     if file.is_err() {
-        panic!("File {} cannot be read.", file_name);
+        eprintln!("File {} cannot be read.", file_name);
+        eprintln!("Exit 3");
+        exit(3);
     }
 
     let f = file.unwrap();
     let mut reader = BufReader::new(f);
-    let mut buffer = Vec::new();
-
-    reader.read_to_end(&mut buffer).unwrap();
+    let profile_extension = args.profile_ext.as_ref().map(|profile_file_name| {
+        let extension = fs::read_to_string(profile_file_name)
+            .map_err(|error| error.to_string())
+            .and_then(|json| ProfileExtension::from_json(&json));
+        match extension {
+            Ok(extension) => Arc::new(extension),
+            Err(error) => {
+                eprintln!(
+                    "Profile extension {} cannot be read: {}",
+                    profile_file_name, error
+                );
+                eprintln!("Exit 6");
+                exit(6);
+            }
+        }
+    });
     let fit_file_config = match args.command {
-        Commands::Raw =>
+        Commands::Raw(_) =>
         // enforce raw jq parsable output
         // * no debug message
         // * include unknown fields and invalid values
         // * include unknown message types
+        // * values and timestamps as stored
         {
             FitFileConfig {
                 debug: false,
@@ -75,14 +202,34 @@ fn main() {
                 include_unknown_message_types: true,
                 include_invalid_values: true,
                 header_only: false,
+                raw_values: true,
+                numeric_timestamps: true,
+                coordinates_in_degrees: false,
+                profile_extension,
             }
         }
+        // exports need the values as defined by the profile
+        Commands::Gpx | Commands::Tcx | Commands::Geojson(_) => FitFileConfig {
+            profile_extension,
+            ..FitFileConfig::default()
+        },
+        // like the FIT SDK, with the fields and messages that are unknown to the profile
+        Commands::FitCsv => FitFileConfig {
+            include_unknown_fields: true,
+            include_unknown_message_types: true,
+            profile_extension,
+            ..FitFileConfig::default()
+        },
         Commands::Header => FitFileConfig {
             debug: args.debug,
             include_unknown_fields: args.unknown_fields,
             include_unknown_message_types: args.unknown_message_types,
             include_invalid_values: args.invalid_values,
             header_only: true,
+            raw_values: args.raw_values,
+            numeric_timestamps: args.numeric_timestamps,
+            coordinates_in_degrees: args.degrees,
+            profile_extension,
         },
         _ => FitFileConfig {
             debug: args.debug,
@@ -90,16 +237,182 @@ fn main() {
             include_unknown_message_types: args.unknown_message_types,
             include_invalid_values: args.invalid_values,
             header_only: false,
+            raw_values: args.raw_values,
+            numeric_timestamps: args.numeric_timestamps,
+            coordinates_in_degrees: args.degrees,
+            profile_extension,
         },
     };
-    let fit_file = FitFile::from(&buffer, &fit_file_config);
+    let ndjson_args = match &args.command {
+        Commands::Messages(messages_args) if messages_args.ndjson.ndjson => {
+            Some((&messages_args.ndjson, Some(&messages_args.message_types)))
+        }
+        Commands::Raw(ndjson_args) if ndjson_args.ndjson => Some((ndjson_args, None)),
+        _ => None,
+    };
+    if let Some((ndjson_args, message_types)) = ndjson_args {
+        stream_ndjson(
+            reader,
+            &fit_file_config,
+            ndjson_args,
+            message_types,
+            file_name,
+        );
+        return;
+    }
+
+    let mut buffer = Vec::new();
+    if reader.read_to_end(&mut buffer).is_err() {
+        eprintln!("File {} cannot be read.", file_name);
+        eprintln!("Exit 3");
+        exit(3);
+    }
+    let fit_files = match FitFile::from_chained(&buffer, &fit_file_config) {
+        Ok(fit_files) => fit_files,
+        Err(error) => {
+            eprintln!("File {} is not a valid FIT file: {}", file_name, error);
+            eprintln!("Exit 4");
+            exit(4);
+        }
+    };
     match args.command {
-        Commands::Summary => println!("{:?}", fit_file.get_message_types()),
+        Commands::Summary => {
+            let mut message_types: HashMap<String, usize> = HashMap::new();
+            for fit_file in &fit_files {
+                for (message_type, count) in fit_file.get_message_types() {
+                    *message_types.entry(message_type).or_default() += count;
+                }
+            }
+            println!("{:?}", message_types)
+        }
         Commands::Messages(messages_args) => {
-            let result = fit_file.get_messages(messages_args.message_types);
+            let result = fit_files
+                .iter()
+                .flat_map(|fit_file| fit_file.get_messages(messages_args.message_types.clone()))
+                .collect_vec();
             println!("{}", serde_json::to_string(&result).unwrap());
         }
-        Commands::Raw => println!("{}", serde_json::to_string(&fit_file).unwrap()),
-        Commands::Header => println!("{}", serde_json::to_string(&fit_file.header).unwrap()),
+        Commands::Raw(_) => println!("{}", per_file_json(fit_files.iter().collect(), args.array)),
+        Commands::Header => println!(
+            "{}",
+            per_file_json(
+                fit_files.iter().map(|fit_file| &fit_file.header).collect(),
+                args.array
+            )
+        ),
+        Commands::Validate => {
+            println!(
+                "{}",
+                per_file_json(
+                    fit_files.iter().map(|fit_file| &fit_file.crc).collect(),
+                    args.array
+                )
+            );
+            if !fit_files.iter().all(|fit_file| fit_file.crc.is_valid()) {
+                exit(1);
+            }
+        }
+        Commands::Gpx => print!("{}", gpx::to_gpx(&combined(fit_files, file_name))),
+        Commands::Tcx => print!("{}", tcx::to_tcx(&combined(fit_files, file_name))),
+        Commands::Geojson(geojson_args) => println!(
+            "{}",
+            geojson::to_geojson(&combined(fit_files, file_name), geojson_args.max_vertices)
+        ),
+        Commands::Csv(csv_args) => {
+            let fit_file = combined(fit_files, file_name);
+            if let Some(message_type) = csv_args.message_type {
+                print!("{}", csv::to_csv(&fit_file, &message_type));
+            } else {
+                for (message_type, content) in csv::to_csv_per_message_type(&fit_file) {
+                    // e.g. `file_id.csv` for `File Id`
+                    let csv_file_name =
+                        format!("{}.csv", message_type.to_lowercase().replace(' ', "_"));
+                    let path = Path::new(&csv_args.output).join(csv_file_name);
+                    if fs::write(&path, content).is_err() {
+                        eprintln!("File {} cannot be written.", path.display());
+                        eprintln!("Exit 5");
+                        exit(5);
+                    }
+                }
+            }
+        }
+        Commands::FitCsv => print!("{}", fit_csv::to_fit_csv(&combined(fit_files, file_name))),
+        Commands::Apps => {
+            // the raw messages keep the developer data index of every developer field
+            let fit_files = match FitFileRaw::from_chained(&buffer) {
+                Ok(fit_files) => fit_files,
+                Err(error) => {
+                    eprintln!("File {} is not a valid FIT file: {}", file_name, error);
+                    eprintln!("Exit 4");
+                    exit(4);
+                }
+            };
+            let extension = fit_file_config.profile_extension.as_deref();
+            println!(
+                "{}",
+                serde_json::to_string(&developer_apps(&fit_files, extension)).unwrap()
+            );
+        }
     }
 }
+
+/// prints the messages as newline-delimited JSON while they are decoded,
+/// all message types if `message_types` is `None`
+fn stream_ndjson<R: Read>(
+    reader: R,
+    config: &FitFileConfig,
+    ndjson_args: &NdjsonArgs,
+    message_types: Option<&Vec<String>>,
+    file_name: &str,
+) {
+    let mut decoder = FitDecoder::new(reader, config);
+    let mut stdout = io::stdout().lock();
+    let mut index = 0;
+    while let Some(message) = decoder.next() {
+        let message = match message {
+            Ok(message) => message,
+            Err(error) => {
+                eprintln!("File {} is not a valid FIT file: {}", file_name, error);
+                eprintln!("Exit 4");
+                exit(4);
+            }
+        };
+        if message_types.map_or(true, |message_types| {
+            message_types.contains(&message.display_name())
+        }) {
+            let line = NdjsonLine {
+                message: &message,
+                index: ndjson_args.index.then_some(index),
+                offset: ndjson_args.offset.then_some(decoder.message_position()),
+            };
+            // stop quietly when the reader of the output is gone, e.g. `head`
+            if line.write_to(&mut stdout).is_err() {
+                return;
+            }
+        }
+        index += 1;
+    }
+}
+
+/// one entry per chained FIT file, a single file is output as object unless `--array` is given
+fn per_file_json<T: Serialize>(entries: Vec<T>, array: bool) -> String {
+    match entries.as_slice() {
+        [entry] if !array => serde_json::to_string(entry).unwrap(),
+        entries => serde_json::to_string(entries).unwrap(),
+    }
+}
+
+/// combines chained FIT files for the exports, e.g. settings and activity written together
+fn combined(fit_files: Vec<FitFile>, file_name: &str) -> FitFile {
+    fit_files
+        .into_iter()
+        .reduce(|mut fit_file, chained| {
+            fit_file.messages.extend(chained.messages);
+            fit_file
+        })
+        .unwrap_or_else(|| {
+            eprintln!("File {} does not contain a FIT file.", file_name);
+            eprintln!("Exit 4");
+            exit(4);
+        })
+}
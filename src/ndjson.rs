@@ -0,0 +1,26 @@
+use std::io;
+use std::io::Write;
+
+use serde::Serialize;
+
+/// A message as line of newline-delimited JSON, optionally with its position in the file.
+///
+/// The fields of the message are followed by `index`, the number of messages before it,
+/// and `offset`, the byte position of its record header, if they are set.
+#[derive(Serialize)]
+pub struct NdjsonLine<'a, T: Serialize> {
+    #[serde(flatten)]
+    pub message: &'a T,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<usize>,
+}
+
+impl<T: Serialize> NdjsonLine<'_, T> {
+    /// writes the line including the line break, fails if the writer does
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        serde_json::to_writer(&mut *writer, self)?;
+        writer.write_all(b"\n")
+    }
+}
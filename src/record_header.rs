@@ -0,0 +1,75 @@
+/// Field number of the `timestamp` field which is shared by all messages
+pub const TIMESTAMP_FIELD_NUMBER: u8 = 253;
+
+/// The one byte header in front of every definition or data message
+pub struct RecordHeader {
+    pub definition_message: bool,
+    pub developer_data: bool,
+    pub local_message_number: u8,
+    /// time offset in seconds, only set for compressed timestamp headers
+    pub time_offset: Option<u8>,
+}
+
+impl RecordHeader {
+    pub fn parse(header: u8) -> RecordHeader {
+        let compressed_timestamp_header = header >> 7 & 1 == 1;
+        if compressed_timestamp_header {
+            // compressed timestamp headers are always data messages
+            // bit 5-6: local message type, bit 0-4: time offset
+            RecordHeader {
+                definition_message: false,
+                developer_data: false,
+                local_message_number: header >> 5 & 0x03,
+                time_offset: Some(header & 0x1F),
+            }
+        } else {
+            RecordHeader {
+                definition_message: header >> 6 & 1 == 1,
+                developer_data: header >> 5 & 1 == 1,
+                local_message_number: header & 0x0F,
+                time_offset: None,
+            }
+        }
+    }
+}
+
+/// Rebuilds the full timestamp from the last full timestamp and the 5 bit offset of
+/// a compressed timestamp header. A rollover is assumed if the offset is smaller than
+/// the lower 5 bits of the last timestamp.
+pub fn resolve_compressed_timestamp(last_timestamp: u32, time_offset: u8) -> u32 {
+    let time_offset = u32::from(time_offset);
+    let base = last_timestamp & !0x1F;
+    if time_offset >= last_timestamp & 0x1F {
+        base + time_offset
+    } else {
+        base + time_offset + 0x20
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_compressed_timestamp_header() {
+        // 1 | local message type 2 | time offset 23
+        let header = RecordHeader::parse(0b1101_0111);
+        assert!(!header.definition_message);
+        assert!(!header.developer_data);
+        assert_eq!(header.local_message_number, 2);
+        assert_eq!(header.time_offset, Some(23));
+    }
+
+    #[test]
+    fn adds_offset_within_the_same_32_seconds() {
+        // last timestamp has 0x05 in the lower 5 bits
+        assert_eq!(resolve_compressed_timestamp(0x1000_0005, 0x05), 0x1000_0005);
+        assert_eq!(resolve_compressed_timestamp(0x1000_0005, 0x1F), 0x1000_001F);
+    }
+
+    #[test]
+    fn rolls_over_if_offset_is_smaller_than_last_offset() {
+        assert_eq!(resolve_compressed_timestamp(0x1000_001E, 0x02), 0x1000_0022);
+        assert_eq!(resolve_compressed_timestamp(0x1000_0005, 0x04), 0x1000_0024);
+    }
+}
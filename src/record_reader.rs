@@ -0,0 +1,435 @@
+use std::collections::HashMap;
+use std::io::{ErrorKind, Read};
+use std::sync::Arc;
+
+use crate::crc::{crc16_update, CrcCheck, CrcReport};
+use crate::data_types::{BaseType, Value};
+use crate::error::FitError;
+use crate::fit_file::fields::{DeveloperDataId, DeveloperField};
+use crate::fit_file_raw::{DefinitionRecordRaw, FieldDefinitionRaw};
+use crate::record_header::{resolve_compressed_timestamp, RecordHeader, TIMESTAMP_FIELD_NUMBER};
+
+const FIELD_DESCRIPTION: u16 = 206;
+const DEVELOPER_DATA_ID: u16 = 207;
+
+/// A definition or data record as read by [RecordReader]
+pub(crate) enum Record {
+    /// the definition that the following data records of its local message number are read with
+    Definition(Arc<DefinitionRecordRaw>),
+    Data(DataRecord),
+}
+
+/// The bytes of a data record with the definition it was read with
+pub(crate) struct DataRecord {
+    pub record_header: u8,
+    /// absolute position of the record header
+    pub record_position: usize,
+    pub definition: Arc<DefinitionRecordRaw>,
+    /// the values of all fields as stored, in the order of the field definitions
+    pub data: Vec<u8>,
+    /// timestamp of a compressed timestamp header, it replaces the timestamp field of the message
+    pub compressed_timestamp: Option<u32>,
+}
+
+impl DataRecord {
+    /// absolute position of the data after the record header
+    pub fn data_position(&self) -> usize {
+        self.record_position + 1
+    }
+
+    /// local message number of the record header
+    pub fn local_message_number(&self) -> u8 {
+        RecordHeader::parse(self.record_header).local_message_number
+    }
+}
+
+/// Reads the headers, records and CRCs of chained FIT files from any [Read], the shared core of
+/// [FitDecoder](crate::fit_file::FitDecoder), [FitDecoderRaw](crate::fit_file_raw::FitDecoderRaw)
+/// and [FitFileRecordsRaw](crate::fit_file_raw::FitFileRecordsRaw) which only convert the records.
+///
+/// Keeps the state of the current FIT file: the running CRC, the definitions of the local message numbers,
+/// the last full timestamp for compressed timestamp headers and the developer fields.
+pub(crate) struct RecordReader<R: Read> {
+    reader: R,
+    /// absolute position in the stream
+    position: usize,
+    /// absolute position of the record header of the last record
+    record_position: usize,
+    /// `true` after the header of the first FIT file was read
+    started: bool,
+    /// absolute position of the current file's CRC
+    content_end: usize,
+    /// running CRC of the current FIT file
+    crc_value: u16,
+    crc: CrcReport,
+    /// `true` if all records of the current FIT file are read
+    file_finished: bool,
+    definitions: HashMap<u8, Arc<DefinitionRecordRaw>>,
+    /// developer fields described by the `field_description` messages of the current FIT file
+    developer_fields: Vec<DeveloperField>,
+    /// applications of the `developer_data_id` messages of the current FIT file
+    developer_data_ids: Vec<DeveloperDataId>,
+    /// last full timestamp as reference for compressed timestamp headers
+    last_timestamp: u32,
+    /// the decoder stops after the first error, see [next_chained_message]
+    failed: bool,
+}
+
+impl<R: Read> RecordReader<R> {
+    pub fn new(reader: R) -> RecordReader<R> {
+        RecordReader {
+            reader,
+            position: 0,
+            record_position: 0,
+            started: false,
+            content_end: 0,
+            crc_value: 0,
+            crc: CrcReport::default(),
+            file_finished: true,
+            definitions: HashMap::new(),
+            developer_fields: vec![],
+            developer_data_ids: vec![],
+            last_timestamp: 0,
+            failed: false,
+        }
+    }
+
+    /// CRC results of the current FIT file, the file CRC is available after its last record
+    pub fn crc(&self) -> &CrcReport {
+        &self.crc
+    }
+
+    pub fn developer_fields(&self) -> &[DeveloperField] {
+        &self.developer_fields
+    }
+
+    /// absolute byte position in the stream
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// absolute byte position of the record header of the last record that was read
+    pub fn record_position(&self) -> usize {
+        self.record_position
+    }
+
+    /// Skips the rest of the current FIT file and reads the header of the next one.
+    /// `read_header` checks the header bytes and returns the header with the size of header and records.
+    /// Returns `None` if the stream ends before another FIT file starts.
+    pub fn next_file<H>(
+        &mut self,
+        read_header: impl FnOnce(&[u8]) -> Result<(H, usize), FitError>,
+    ) -> Result<Option<H>, FitError> {
+        if self.started && !self.file_finished {
+            self.skip_file()?;
+        }
+        let file_start = self.position;
+        let mut length = [0u8; 1];
+        loop {
+            match self.reader.read(&mut length) {
+                Ok(0) => return Ok(None),
+                Ok(_) => break,
+                Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                Err(error) => {
+                    return Err(FitError::Io {
+                        offset: self.position,
+                        error,
+                    })
+                }
+            }
+        }
+        self.position += 1;
+        let mut header_info = vec![length[0]];
+        // a shorter header is reported by `read_header`
+        if header_info[0] >= 12 {
+            header_info.extend(self.read_raw_bytes(header_info[0] as usize - 1, "header")?);
+        }
+        let (header, content_size) =
+            read_header(&header_info).map_err(|error| error.shifted(file_start))?;
+        self.crc = CrcReport {
+            header: CrcCheck::header(&header_info),
+            file: None,
+        };
+        self.crc_value = crc16_update(0, &header_info);
+        self.content_end = file_start + content_size;
+        self.started = true;
+        self.file_finished = false;
+        self.definitions.clear();
+        self.developer_fields.clear();
+        self.developer_data_ids.clear();
+        self.last_timestamp = 0;
+        Ok(Some(header))
+    }
+
+    /// Reads the next definition or data record of the current FIT file.
+    /// Returns `None` after the last record, when the file CRC was read.
+    pub fn read_record(&mut self) -> Result<Option<Record>, FitError> {
+        if self.file_finished {
+            return Ok(None);
+        }
+        if self.position >= self.content_end {
+            self.read_file_crc()?;
+            return Ok(None);
+        }
+        // start: record header
+        let record_position = self.position;
+        let record_header_value = self.read_bytes(1, "record header")?[0];
+        let record_header = RecordHeader::parse(record_header_value);
+        let local_message_number = record_header.local_message_number;
+        // end: record header
+        self.record_position = record_position;
+
+        if record_header.definition_message {
+            let definition = Arc::new(self.read_definition(record_header_value)?);
+            self.definitions
+                .insert(local_message_number, Arc::clone(&definition));
+            return Ok(Some(Record::Definition(definition)));
+        }
+        let Some(definition) = self.definitions.get(&local_message_number).cloned() else {
+            return Err(FitError::UndefinedLocalMessage {
+                offset: record_position,
+                local_message_number,
+            });
+        };
+        let message_size = definition
+            .fields
+            .iter()
+            .map(|field| field.size as usize)
+            .sum();
+        let data = self.read_bytes(message_size, "data message")?;
+        let compressed_timestamp = match record_header.time_offset {
+            Some(time_offset) => {
+                let timestamp = resolve_compressed_timestamp(self.last_timestamp, time_offset);
+                self.last_timestamp = timestamp;
+                Some(timestamp)
+            }
+            None => {
+                if let Some(timestamp) = stored_timestamp(&definition, &data) {
+                    self.last_timestamp = timestamp;
+                }
+                None
+            }
+        };
+        Ok(Some(Record::Data(DataRecord {
+            record_header: record_header_value,
+            record_position,
+            definition,
+            data,
+            compressed_timestamp,
+        })))
+    }
+
+    /// Keeps the developer fields of `field_description` and the applications of `developer_data_id` messages,
+    /// the decoders call it after they converted the record.
+    pub fn update_developer_data(&mut self, record: &DataRecord) -> Result<(), FitError> {
+        let message_number = record.definition.message_number;
+        if message_number != FIELD_DESCRIPTION && message_number != DEVELOPER_DATA_ID {
+            return Ok(());
+        }
+        let (message, _) = record
+            .definition
+            .read_message(
+                &0,
+                &record.data,
+                &record.definition.architecture,
+                &self.developer_fields,
+            )
+            .map_err(|error| error.shifted(record.data_position()))?;
+        if message_number == FIELD_DESCRIPTION {
+            // we can be sure that the dev fields are provided before they are referenced
+            let mut developer_field = DeveloperField::from_raw(&message).map_err(|context| {
+                FitError::InvalidFieldDescription {
+                    offset: record.record_position,
+                    context,
+                }
+            })?;
+            developer_field.developer_data_id = self
+                .developer_data_ids
+                .iter()
+                .find(|developer_data_id| {
+                    developer_data_id.developer_data_index == developer_field.developer_data_index
+                })
+                .cloned();
+            // a later description of the same field replaces the former one
+            self.developer_fields.retain(|dev_field| {
+                dev_field.developer_data_index != developer_field.developer_data_index
+                    || dev_field.field_definition_number != developer_field.field_definition_number
+            });
+            self.developer_fields.push(developer_field);
+        } else if let Ok(developer_data_id) = DeveloperDataId::from_raw(&message) {
+            // the field descriptions that follow belong to this application,
+            // a new developer with the same index starts without field descriptions
+            let developer_data_index = developer_data_id.developer_data_index;
+            self.developer_fields
+                .retain(|dev_field| dev_field.developer_data_index != developer_data_index);
+            self.developer_data_ids
+                .retain(|id| id.developer_data_index != developer_data_index);
+            self.developer_data_ids.push(developer_data_id);
+        }
+        Ok(())
+    }
+
+    fn read_file_crc(&mut self) -> Result<(), FitError> {
+        let crc_bytes = self.read_raw_bytes(2, "file CRC")?;
+        self.crc.file = Some(CrcCheck {
+            expected: u16::from_le_bytes([crc_bytes[0], crc_bytes[1]]),
+            computed: self.crc_value,
+        });
+        self.file_finished = true;
+        Ok(())
+    }
+
+    fn skip_file(&mut self) -> Result<(), FitError> {
+        while self.position < self.content_end {
+            let length = (self.content_end - self.position).min(4096);
+            self.read_bytes(length, "data records")?;
+        }
+        self.read_file_crc()
+    }
+
+    fn read_definition(
+        &mut self,
+        record_header_value: u8,
+    ) -> Result<DefinitionRecordRaw, FitError> {
+        let record_header = RecordHeader::parse(record_header_value);
+        let definition_header = self.read_bytes(5, "definition message header")?;
+        let architecture = definition_header[1];
+        let type_fields: [u8; 2] = [definition_header[2], definition_header[3]];
+        let message_number: u16 = if architecture == 0 {
+            u16::from_le_bytes(type_fields)
+        } else {
+            u16::from_be_bytes(type_fields)
+        };
+        let number_of_fields = definition_header[4] as usize;
+
+        let field_definitions = self.read_bytes(number_of_fields * 3, "field definitions")?;
+        let mut fields: Vec<FieldDefinitionRaw> = field_definitions
+            .chunks(3)
+            .map(|field_definition| FieldDefinitionRaw {
+                number: field_definition[0],
+                size: field_definition[1],
+                base_type_value_or_dev_index: field_definition[2],
+                is_dev_field: false,
+            })
+            .collect();
+        if record_header.developer_data {
+            let number_of_developer_fields =
+                self.read_bytes(1, "number of developer fields")?[0] as usize;
+            let developer_field_definitions = self.read_bytes(
+                number_of_developer_fields * 3,
+                "developer field definitions",
+            )?;
+            fields.extend(developer_field_definitions.chunks(3).map(
+                |developer_field_definition| FieldDefinitionRaw {
+                    number: developer_field_definition[0],
+                    size: developer_field_definition[1],
+                    base_type_value_or_dev_index: developer_field_definition[2],
+                    is_dev_field: true,
+                },
+            ));
+        }
+        Ok(DefinitionRecordRaw {
+            record_header: record_header_value,
+            reserved: definition_header[0],
+            architecture,
+            message_number,
+            fields,
+        })
+    }
+
+    /// reads bytes of the current FIT file and adds them to its CRC
+    fn read_bytes(&mut self, length: usize, context: &str) -> Result<Vec<u8>, FitError> {
+        let data = self.read_raw_bytes(length, context)?;
+        self.crc_value = crc16_update(self.crc_value, &data);
+        Ok(data)
+    }
+
+    fn read_raw_bytes(&mut self, length: usize, context: &str) -> Result<Vec<u8>, FitError> {
+        let mut data = vec![0u8; length];
+        self.reader
+            .read_exact(&mut data)
+            .map_err(|error| match error.kind() {
+                ErrorKind::UnexpectedEof => FitError::UnexpectedEndOfFile {
+                    offset: self.position,
+                    context: context.to_string(),
+                },
+                _ => FitError::Io {
+                    offset: self.position,
+                    error,
+                },
+            })?;
+        self.position += length;
+        Ok(data)
+    }
+}
+
+/// the `timestamp` field of a data record, if its definition contains one that is valid
+fn stored_timestamp(definition: &DefinitionRecordRaw, data: &[u8]) -> Option<u32> {
+    let mut position = 0;
+    for field_definition in &definition.fields {
+        if field_definition.number == TIMESTAMP_FIELD_NUMBER
+            && field_definition.size == 4
+            && !field_definition.is_dev_field
+        {
+            let base_type = BaseType::UINT32;
+            let data = data.get(position..position + 4)?;
+            return match (base_type.read)(&base_type, data, &definition.architecture) {
+                Value::NumberValueU32(timestamp) => Some(timestamp),
+                _ => None,
+            };
+        }
+        position += field_definition.size as usize;
+    }
+    None
+}
+
+/// A decoder that converts the records of a [RecordReader] into messages
+pub(crate) trait RecordDecoder {
+    type Reader: Read;
+    type Message;
+
+    fn record_reader(&mut self) -> &mut RecordReader<Self::Reader>;
+
+    /// reads the header of the next FIT file, `false` at the end of the stream
+    fn next_file(&mut self) -> Result<bool, FitError>;
+
+    /// the next message of the current FIT file, `None` after its last message
+    fn read_message(&mut self) -> Result<Option<Self::Message>, FitError>;
+}
+
+/// The next message of all chained FIT files in the stream, the `Iterator` of the decoders.
+/// The decoder stops after the first error.
+pub(crate) fn next_chained_message<D: RecordDecoder>(
+    decoder: &mut D,
+) -> Option<Result<D::Message, FitError>> {
+    if decoder.record_reader().failed {
+        return None;
+    }
+    let result = loop {
+        let reader = decoder.record_reader();
+        if !reader.started || reader.file_finished {
+            let started = reader.started;
+            match decoder.next_file() {
+                Ok(true) => {}
+                Ok(false) if started => return None,
+                // an empty stream is not a FIT file
+                Ok(false) => {
+                    break Err(FitError::UnexpectedEndOfFile {
+                        offset: decoder.record_reader().position,
+                        context: "header".to_string(),
+                    })
+                }
+                Err(error) => break Err(error),
+            }
+        }
+        match decoder.read_message() {
+            Ok(Some(message)) => break Ok(message),
+            Ok(None) => continue,
+            Err(error) => break Err(error),
+        }
+    };
+    if result.is_err() {
+        decoder.record_reader().failed = true;
+    }
+    Some(result)
+}
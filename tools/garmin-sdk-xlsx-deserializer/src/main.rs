@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io::Write;
 
@@ -14,6 +14,12 @@ fn main() {
     target.write("use std::fmt;\nuse std::fmt::{Display, Formatter};\n".as_bytes()).unwrap();
     let mut prev_value = Data::Empty;
     let mut type_numbers = HashMap::new();
+    // values of every type by name, to resolve the reference values of subfields
+    let mut type_values: HashMap<String, HashMap<String, String>> = HashMap::new();
+    let mut current_type = String::new();
+    // value cells of the current type, to recognize bitfields
+    let mut current_values: Vec<String> = vec![];
+    let mut bitfield_types: HashSet<String> = HashSet::new();
     let mut mesg_num_parsing = false;
     if let Ok(r) = workbook.worksheet_range("Types") {
         for row in r.rows() {
@@ -26,38 +32,73 @@ fn main() {
                     if mesg_num_parsing {
                         type_numbers.insert(row[2].as_string().unwrap(), row[3].as_string().unwrap());
                     }
-                    buffer.push_str(format!("\t\t{} = {},\n", make_nice(row[2].to_string()), row[3]).as_str())
+                    type_values
+                        .entry(current_type.clone())
+                        .or_default()
+                        .insert(row[2].to_string(), row[3].to_string());
+                    current_values.push(row[3].to_string());
+                    buffer.push_str(
+                        format!(
+                            "\t\t{} = {}{},\n",
+                            make_nice(row[2].to_string()),
+                            row[3],
+                            profile_name(row[2].to_string())
+                        )
+                        .as_str(),
+                    )
                 }
                 prev_value = row[3].clone();
             } else {
-                if !buffer.is_empty() {
-                    buffer.push_str("\t}\n}\n");
-                    target.write(buffer.as_bytes()).unwrap();
+                if !current_type.is_empty() {
+                    if is_bitfield(&current_type, &current_values) {
+                        bitfield_types.insert(current_type.clone());
+                    }
+                    target
+                        .write(
+                            type_enum(
+                                &current_type,
+                                &buffer,
+                                bitfield_types.contains(&current_type),
+                            )
+                            .as_bytes(),
+                        )
+                        .unwrap();
                     buffer.clear();
+                    current_values.clear();
                     prev_value = Data::Empty;
                     mesg_num_parsing = false;
                 }
-                buffer.push_str("crate::key_value_enum! {\n");
-                buffer.push_str("\tpub enum ");
-                if "mesg_num".to_string().eq(&row[0].as_string().unwrap()) {
+                current_type = row[0].as_string().unwrap();
+                if "mesg_num".to_string().eq(&current_type) {
                     mesg_num_parsing = true;
                 }
-                buffer.push_str(make_nice(row[0].as_string().unwrap()).as_str());
-                buffer.push_str(" { \n");
             }
         }
-        if !buffer.is_empty() {
-            buffer.push_str("\t}\n}\n");
-            target.write(buffer.as_bytes()).unwrap();
+        if !current_type.is_empty() {
+            if is_bitfield(&current_type, &current_values) {
+                bitfield_types.insert(current_type.clone());
+            }
+            target
+                .write(
+                    type_enum(
+                        &current_type,
+                        &buffer,
+                        bitfield_types.contains(&current_type),
+                    )
+                    .as_bytes(),
+                )
+                .unwrap();
             buffer.clear();
-            prev_value = Data::Empty;
-            mesg_num_parsing = false;
         }
     }
     first_row = true;
     buffer.clear();
     let mut buffer2 = String::new();
+    let mut bitfields = String::new();
+    let mut strings = String::new();
     let mut current_mesg_num = "";
+    // rows of every message, subfields and components can reference fields that follow them
+    let mut messages: Vec<(String, Vec<Vec<Data>>)> = vec![];
 
     if let Ok(r) = workbook.worksheet_range("Messages") {
         for messages_row in r.rows() {
@@ -67,10 +108,41 @@ fn main() {
             }
             if ToCellDeserializer::is_empty(&messages_row[0]) {
                 if !ToCellDeserializer::is_empty(&messages_row[2]) {
+                    if let Some((_, rows)) = messages.last_mut() {
+                        rows.push(messages_row.to_vec());
+                    }
                     let string = messages_row[1].to_string();
                     if !string.is_empty() {
                         if primitive_type(messages_row[3].as_string().unwrap()) {
-                            buffer.push_str(format!("{},{},\"{}\"\n", current_mesg_num, string, messages_row[2].to_string()).as_str());
+                            buffer.push_str(
+                                format!(
+                                    "{},{},\"{}\"{}\n",
+                                    current_mesg_num,
+                                    string,
+                                    messages_row[2].to_string(),
+                                    scale_offset_units(messages_row)
+                                )
+                                .as_str(),
+                            );
+                            if messages_row[3].to_string().eq("string") {
+                                strings.push_str(
+                                    format!("{},{}\n", current_mesg_num, string).as_str(),
+                                );
+                            }
+                        } else if bitfield_types.contains(&messages_row[3].to_string()) {
+                            bitfields.push_str(
+                                format!(
+                                    "{},{},\"{}\",{}\n",
+                                    current_mesg_num,
+                                    string,
+                                    messages_row[2].to_string(),
+                                    bitfield_array_types(
+                                        &messages_row[3].to_string(),
+                                        &bitfield_types
+                                    )
+                                )
+                                .as_str(),
+                            );
                         } else {
                             buffer2.push_str(format!("{},{},\"{}\",{}\n", current_mesg_num, string, messages_row[2].to_string(), make_nice(messages_row[3].as_string().unwrap())).as_str());
                         }
@@ -83,6 +155,7 @@ fn main() {
                 // }
                 // create entry and start over
                 current_mesg_num = type_numbers.get(&messages_row[0].as_string().unwrap()).unwrap().as_str();
+                messages.push((current_mesg_num.to_string(), vec![]));
             }
         }
         println!("{}", "crate::expand_fields! {");
@@ -91,7 +164,243 @@ fn main() {
         println!("{}", "crate::expand_fields! {");
         println!("{}", buffer2);
         println!("{}", "}");
+        println!("{}", "crate::expand_bitfields! {");
+        println!("{}", bitfields);
+        println!("{}", "}");
+        println!("{}", "crate::expand_string_fields! {");
+        println!("{}", strings);
+        println!("{}", "}");
+    }
+
+    let mut subfields = String::new();
+    let mut enum_subfields = String::new();
+    let mut components = String::new();
+    for (mesg_num, rows) in &messages {
+        // number and type of the fields by name
+        let fields: HashMap<String, (String, String)> = rows
+            .iter()
+            .filter(|row| !row[1].to_string().is_empty())
+            .map(|row| (row[2].to_string(), (row[1].to_string(), row[3].to_string())))
+            .collect();
+        let mut field_number = String::new();
+        for row in rows {
+            if !row[1].to_string().is_empty() {
+                // field
+                field_number = row[1].to_string();
+                components.push_str(&component_rows(mesg_num, &field_number, row, &fields, None));
+                continue;
+            }
+            // subfield of the last field, one row for every reference field and value, with its components
+            for (reference_field, reference_value) in list(&row[11]).iter().zip(list(&row[12])) {
+                let (reference_number, reference_type) = fields.get(reference_field).unwrap();
+                let reference_value = type_values
+                    .get(reference_type)
+                    .and_then(|values| values.get(&reference_value))
+                    .cloned()
+                    .unwrap_or(reference_value);
+                let reference = format!("{},{}", reference_number, reference_value);
+                if primitive_type(row[3].to_string()) {
+                    subfields.push_str(
+                        format!(
+                            "{},{},\"{}\",{}{}\n",
+                            mesg_num,
+                            field_number,
+                            row[2],
+                            reference,
+                            scale_offset_units(row)
+                        )
+                        .as_str(),
+                    );
+                } else {
+                    enum_subfields.push_str(
+                        format!(
+                            "{},{},\"{}\",{},{}\n",
+                            mesg_num,
+                            field_number,
+                            row[2],
+                            reference,
+                            make_nice(row[3].to_string())
+                        )
+                        .as_str(),
+                    );
+                }
+                components.push_str(&component_rows(
+                    mesg_num,
+                    &field_number,
+                    row,
+                    &fields,
+                    Some(&reference),
+                ));
+            }
+        }
+    }
+    println!("{}", "crate::expand_subfields! {");
+    println!("{}", subfields);
+    println!("{}", "}");
+    println!("{}", "crate::expand_subfields! {");
+    println!("{}", enum_subfields);
+    println!("{}", "}");
+    println!("{}", "crate::expand_components! {");
+    println!("{}", components);
+    println!("{}", "}");
+}
+
+// columns 5 and 9-10 of the messages sheet: components, bits and accumulate, with scale and offset of columns 6-7
+// every component is a row of message, field, target field, bits, scale, offset, accumulate [, reference field, reference value]
+fn component_rows(
+    mesg_num: &str,
+    field_number: &str,
+    row: &[Data],
+    fields: &HashMap<String, (String, String)>,
+    reference: Option<&str>,
+) -> String {
+    let mut rows = String::new();
+    let scales = list(&row[6]);
+    let offsets = list(&row[7]);
+    let bits = list(&row[9]);
+    let accumulate = list(&row[10]);
+    for (i, component) in list(&row[5]).iter().enumerate() {
+        let (target_number, _) = fields.get(component).unwrap();
+        let scale = scales
+            .get(i)
+            .filter(|scale| !scale.is_empty())
+            .cloned()
+            .unwrap_or("1".to_string());
+        let offset = offsets
+            .get(i)
+            .filter(|offset| !offset.is_empty())
+            .cloned()
+            .unwrap_or("0".to_string());
+        let accumulate = accumulate
+            .get(i)
+            .is_some_and(|accumulate| accumulate == "1");
+        rows.push_str(
+            format!(
+                "{},{},{},{},{},{},{}",
+                mesg_num, field_number, target_number, bits[i], scale, offset, accumulate
+            )
+            .as_str(),
+        );
+        if let Some(reference) = reference {
+            rows.push_str(format!(",{}", reference).as_str());
+        }
+        rows.push('\n');
     }
+    rows
+}
+
+// cells with several values separate them by commas, e.g. the components of a field
+fn list(cell: &Data) -> Vec<String> {
+    let value = cell.to_string();
+    if value.trim().is_empty() {
+        return vec![];
+    }
+    value
+        .split(',')
+        .map(|value| value.trim().to_string())
+        .collect()
+}
+
+// columns 6-8 of the messages sheet: scale, offset and units
+// fields with components list one scale per component, those are not scaled themselves
+fn scale_offset_units(messages_row: &[Data]) -> String {
+    let scale = messages_row[6].to_string();
+    let offset = messages_row[7].to_string();
+    let units = messages_row[8].to_string();
+    if scale.contains(',') || (scale.is_empty() && offset.is_empty() && units.is_empty()) {
+        return String::new();
+    }
+    let scale = if scale.is_empty() {
+        "1".to_string()
+    } else {
+        scale
+    };
+    let offset = if offset.is_empty() {
+        "0".to_string()
+    } else {
+        offset
+    };
+    format!(",{},{},\"{}\"", scale, offset, units)
+}
+
+// the name of the profile if it can't be derived from the name of the enum value, like `edge_530` from `EdgeFivethree0`
+fn profile_name(value_name: String) -> String {
+    if snake_case(&make_nice(value_name.clone())) == value_name {
+        String::new()
+    } else {
+        format!(" => \"{}\"", value_name)
+    }
+}
+
+// same as `garmin_fit::fit_file::key_value_enum::snake_case`
+fn snake_case(name: &str) -> String {
+    let characters = name.chars().collect::<Vec<char>>();
+    let mut snake_case = String::with_capacity(name.len() + 4);
+    for (i, character) in characters.iter().enumerate() {
+        if character.is_ascii_uppercase() && i > 0 {
+            let previous = characters[i - 1];
+            let next_lowercase = characters
+                .get(i + 1)
+                .is_some_and(|next| next.is_ascii_lowercase());
+            if previous.is_ascii_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_ascii_uppercase() && next_lowercase)
+            {
+                snake_case.push('_');
+            }
+        }
+        snake_case.push(character.to_ascii_lowercase());
+    }
+    snake_case
+}
+
+// the enum of a type with its values, bitfields are marked to translate their flags
+fn type_enum(type_name: &str, values: &str, bitfield: bool) -> String {
+    let marker = if bitfield { "\t#[bitfield]\n" } else { "" };
+    format!(
+        "crate::key_value_enum! {{\n{}\tpub enum {} {{ \n{}\t}}\n}}\n",
+        marker,
+        make_nice(type_name.to_string()),
+        values
+    )
+}
+
+// the profile has no column for bitfields, their values are hex numbers of single bits like `0x00000004`,
+// several flags or an element of a bitfield array like `sport_bits_6` which can have a single flag
+fn is_bitfield(type_name: &str, values: &[String]) -> bool {
+    let flags: Option<Vec<u64>> = values
+        .iter()
+        .map(|value| {
+            value
+                .strip_prefix("0x")
+                .and_then(|hex| u64::from_str_radix(hex, 16).ok())
+        })
+        .collect();
+    let Some(flags) = flags else {
+        return false;
+    };
+    let array_element = array_element_index(type_name).is_some();
+    flags.iter().all(|flag| flag.count_ones() <= 1)
+        && (array_element || flags.iter().filter(|flag| **flag != 0).count() > 1)
+}
+
+// bitfield arrays like `sport_bits_0` have one type for every element: `sport_bits_0`, `sport_bits_1`, ...
+fn bitfield_array_types(type_name: &str, bitfield_types: &HashSet<String>) -> String {
+    match array_element_index(type_name) {
+        Some((prefix, "0")) => (0..)
+            .map(|index| format!("{}_bits_{}", prefix, index))
+            .take_while(|element_type| bitfield_types.contains(element_type))
+            .map(make_nice)
+            .collect::<Vec<_>>()
+            .join(","),
+        _ => make_nice(type_name.to_string()),
+    }
+}
+
+fn array_element_index(type_name: &str) -> Option<(&str, &str)> {
+    type_name
+        .rsplit_once("_bits_")
+        .filter(|(_, index)| index.chars().all(|c| c.is_ascii_digit()))
 }
 
 fn primitive_type(input: String) -> bool {
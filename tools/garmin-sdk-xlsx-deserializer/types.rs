@@ -115,7 +115,7 @@ crate::key_value_enum! {
 		DiveAlarm = 262,
 		ExerciseTitle = 264,
 		DiveSummary = 268,
-		SpotwoData = 269,
+		SpotwoData = 269 => "spo2_data",
 		SleepLevel = 275,
 		Jump = 285,
 		AadAccelFeatures = 289,
@@ -123,7 +123,7 @@ crate::key_value_enum! {
 		RespirationRate = 297,
 		HsaAccelerometerData = 302,
 		HsaStepData = 304,
-		HsaSpotwoData = 305,
+		HsaSpotwoData = 305 => "hsa_spo2_data",
 		HsaStressData = 306,
 		HsaRespirationData = 307,
 		HsaHeartRateData = 308,
@@ -156,6 +156,7 @@ crate::key_value_enum! {
 	}
 }
 crate::key_value_enum! {
+	#[bitfield]
 	pub enum FileFlags { 
 		Read = 0x02,
 		Write = 0x04,
@@ -241,6 +242,7 @@ crate::key_value_enum! {
 	}
 }
 crate::key_value_enum! {
+	#[bitfield]
 	pub enum LanguageBits0 { 
 		English = 0x01,
 		French = 0x02,
@@ -253,6 +255,7 @@ crate::key_value_enum! {
 	}
 }
 crate::key_value_enum! {
+	#[bitfield]
 	pub enum LanguageBitsOne { 
 		Dutch = 0x01,
 		Finnish = 0x02,
@@ -265,6 +268,7 @@ crate::key_value_enum! {
 	}
 }
 crate::key_value_enum! {
+	#[bitfield]
 	pub enum LanguageBitsTwo { 
 		Slovenian = 0x01,
 		Swedish = 0x02,
@@ -277,6 +281,7 @@ crate::key_value_enum! {
 	}
 }
 crate::key_value_enum! {
+	#[bitfield]
 	pub enum LanguageBitsThree { 
 		Bulgarian = 0x01,
 		Romanian = 0x02,
@@ -289,6 +294,7 @@ crate::key_value_enum! {
 	}
 }
 crate::key_value_enum! {
+	#[bitfield]
 	pub enum LanguageBitsFour { 
 		BrazilianPortuguese = 0x01,
 		Indonesian = 0x02,
@@ -443,7 +449,7 @@ crate::key_value_enum! {
 		IndonesianEquatorial = 10,
 		IndonesianIrian = 11,
 		IndonesianSouthern = 12,
-		IndiaZone0 = 13,
+		IndiaZone0 = 13 => "india_zone_0",
 		IndiaZoneIa = 14,
 		IndiaZoneIb = 15,
 		IndiaZoneIia = 16,
@@ -471,7 +477,7 @@ crate::key_value_enum! {
 		BorneoRso = 38,
 		EstonianGrid = 39,
 		LatvianGrid = 40,
-		SwedishRefNinenineGrid = 41,
+		SwedishRefNinenineGrid = 41 => "swedish_ref_99_grid",
 	}
 }
 crate::key_value_enum! {
@@ -555,6 +561,7 @@ crate::key_value_enum! {
 	}
 }
 crate::key_value_enum! {
+	#[bitfield]
 	pub enum SportBits0 { 
 		Generic = 0x01,
 		Running = 0x02,
@@ -567,6 +574,7 @@ crate::key_value_enum! {
 	}
 }
 crate::key_value_enum! {
+	#[bitfield]
 	pub enum SportBitsOne { 
 		Tennis = 0x01,
 		AmericanFootball = 0x02,
@@ -579,6 +587,7 @@ crate::key_value_enum! {
 	}
 }
 crate::key_value_enum! {
+	#[bitfield]
 	pub enum SportBitsTwo { 
 		Mountaineering = 0x01,
 		Hiking = 0x02,
@@ -591,6 +600,7 @@ crate::key_value_enum! {
 	}
 }
 crate::key_value_enum! {
+	#[bitfield]
 	pub enum SportBitsThree { 
 		Driving = 0x01,
 		Golf = 0x02,
@@ -603,6 +613,7 @@ crate::key_value_enum! {
 	}
 }
 crate::key_value_enum! {
+	#[bitfield]
 	pub enum SportBitsFour { 
 		Sailing = 0x01,
 		IceSkating = 0x02,
@@ -615,6 +626,7 @@ crate::key_value_enum! {
 	}
 }
 crate::key_value_enum! {
+	#[bitfield]
 	pub enum SportBitsFive { 
 		WaterSkiing = 0x01,
 		Kayaking = 0x02,
@@ -627,6 +639,7 @@ crate::key_value_enum! {
 	}
 }
 crate::key_value_enum! {
+	#[bitfield]
 	pub enum SportBitsSix { 
 		FloorClimbing = 0x01,
 	}
@@ -788,11 +801,11 @@ crate::key_value_enum! {
 }
 crate::key_value_enum! {
 	pub enum TimeMode { 
-		Houronetwo = 0,
-		Hourtwofour = 1,
+		Houronetwo = 0 => "hour12",
+		Hourtwofour = 1 => "hour24",
 		Military = 2,
-		HourOnetwoWithSeconds = 3,
-		HourTwofourWithSeconds = 4,
+		HourOnetwoWithSeconds = 3 => "hour_12_with_seconds",
+		HourTwofourWithSeconds = 4 => "hour_24_with_seconds",
 		Utc = 5,
 	}
 }
@@ -955,12 +968,12 @@ crate::key_value_enum! {
 		TrainingPeaksTss = 16,
 		RepeatUntilPowerLastLapLessThan = 17,
 		RepeatUntilMaxPowerLastLapLessThan = 18,
-		PowerThreesLessThan = 19,
-		PowerOne0SLessThan = 20,
-		PowerThree0SLessThan = 21,
-		PowerThreesGreaterThan = 22,
-		PowerOne0SGreaterThan = 23,
-		PowerThree0SGreaterThan = 24,
+		PowerThreesLessThan = 19 => "power_3s_less_than",
+		PowerOne0SLessThan = 20 => "power_10s_less_than",
+		PowerThree0SLessThan = 21 => "power_30s_less_than",
+		PowerThreesGreaterThan = 22 => "power_3s_greater_than",
+		PowerOne0SGreaterThan = 23 => "power_10s_greater_than",
+		PowerThree0SGreaterThan = 24 => "power_30s_greater_than",
 		PowerLapLessThan = 25,
 		PowerLapGreaterThan = 26,
 		RepeatUntilTrainingPeaksTss = 27,
@@ -978,9 +991,9 @@ crate::key_value_enum! {
 		Power = 4,
 		Grade = 5,
 		Resistance = 6,
-		PowerThrees = 7,
-		PowerOne0S = 8,
-		PowerThree0S = 9,
+		PowerThrees = 7 => "power_3s",
+		PowerOne0S = 8 => "power_10s",
+		PowerThree0S = 9 => "power_30s",
 		PowerLap = 10,
 		SwimStroke = 11,
 		SpeedLap = 12,
@@ -1081,7 +1094,7 @@ crate::key_value_enum! {
 crate::key_value_enum! {
 	pub enum Manufacturer { 
 		Garmin = 1,
-		GarminFrfour0FiveAntfs = 2,
+		GarminFrfour0FiveAntfs = 2 => "garmin_fr405_antfs",
 		Zephyr = 3,
 		Dayton = 4,
 		Idt = 5,
@@ -1119,18 +1132,18 @@ crate::key_value_enum! {
 		Magellan = 37,
 		Osynce = 38,
 		Holux = 39,
-		Concepttwo = 40,
+		Concepttwo = 40 => "concept2",
 		Shimano = 41,
 		OneGiantLeap = 42,
 		AceSensor = 43,
 		BrimBrothers = 44,
 		Xplova = 45,
 		PerceptionDigital = 46,
-		Bfonesystems = 47,
+		Bfonesystems = 47 => "bf1systems",
 		Pioneer = 48,
 		Spantec = 49,
 		Metalogics = 50,
-		Fouriiiis = 51,
+		Fouriiiis = 51 => "4iiiis",
 		SeikoEpson = 52,
 		SeikoEpsonOem = 53,
 		IforPowell = 54,
@@ -1169,7 +1182,7 @@ crate::key_value_enum! {
 		Tacx = 89,
 		DirectionTechnology = 90,
 		Magtonic = 91,
-		Onepartcarbon = 92,
+		Onepartcarbon = 92 => "1partcarbon",
 		InsideRideTechnologies = 93,
 		SoundOfMotion = 94,
 		Stryd = 95,
@@ -1274,7 +1287,7 @@ crate::key_value_enum! {
 		MahleEbikemotion = 299,
 		Nurvv = 300,
 		Microprogram = 301,
-		Zonefivecloud = 302,
+		Zonefivecloud = 302 => "zone5cloud",
 		Greenteg = 303,
 		YamahaMotors = 304,
 		Whoop = 305,
@@ -1306,154 +1319,154 @@ crate::key_value_enum! {
 }
 crate::key_value_enum! {
 	pub enum GarminProduct { 
-		Hrmone = 1,
-		Axh0One = 2,
-		Axb0One = 3,
-		Axb0Two = 4,
-		Hrmtwoss = 5,
-		DsiAlf0Two = 6,
-		Hrmthreess = 7,
+		Hrmone = 1 => "hrm1",
+		Axh0One = 2 => "axh01",
+		Axb0One = 3 => "axb01",
+		Axb0Two = 4 => "axb02",
+		Hrmtwoss = 5 => "hrm2ss",
+		DsiAlf0Two = 6 => "dsi_alf02",
+		Hrmthreess = 7 => "hrm3ss",
 		HrmRunSingleByteProductId = 8,
 		Bsm = 9,
 		Bcm = 10,
-		Axs0One = 11,
+		Axs0One = 11 => "axs01",
 		HrmTriSingleByteProductId = 12,
-		HrmfourRunSingleByteProductId = 13,
-		FrtwotwofiveSingleByteProductId = 14,
-		GenthreeBsmSingleByteProductId = 15,
-		GenthreeBcmSingleByteProductId = 16,
+		HrmfourRunSingleByteProductId = 13 => "hrm4_run_single_byte_product_id",
+		FrtwotwofiveSingleByteProductId = 14 => "fr225_single_byte_product_id",
+		GenthreeBsmSingleByteProductId = 15 => "gen3_bsm_single_byte_product_id",
+		GenthreeBcmSingleByteProductId = 16 => "gen3_bcm_single_byte_product_id",
 		HrmFitSingleByteProductId = 22,
 		Ohr = 255,
-		Frthree0OneChina = 473,
-		Frthree0OneJapan = 474,
-		Frthree0OneKorea = 475,
-		Frthree0OneTaiwan = 494,
-		Frfour0Five = 717,
-		Frfive0 = 782,
-		Frfour0FiveJapan = 987,
-		Frsix0 = 988,
-		DsiAlf0One = 1011,
-		Frthreeone0Xt = 1018,
-		Edgefive00 = 1036,
-		Froneone0 = 1124,
-		Edgeeight00 = 1169,
-		Edgefive00Taiwan = 1199,
-		Edgefive00Japan = 1213,
+		Frthree0OneChina = 473 => "fr301_china",
+		Frthree0OneJapan = 474 => "fr301_japan",
+		Frthree0OneKorea = 475 => "fr301_korea",
+		Frthree0OneTaiwan = 494 => "fr301_taiwan",
+		Frfour0Five = 717 => "fr405",
+		Frfive0 = 782 => "fr50",
+		Frfour0FiveJapan = 987 => "fr405_japan",
+		Frsix0 = 988 => "fr60",
+		DsiAlf0One = 1011 => "dsi_alf01",
+		Frthreeone0Xt = 1018 => "fr310xt",
+		Edgefive00 = 1036 => "edge500",
+		Froneone0 = 1124 => "fr110",
+		Edgeeight00 = 1169 => "edge800",
+		Edgefive00Taiwan = 1199 => "edge500_taiwan",
+		Edgefive00Japan = 1213 => "edge500_japan",
 		Chirp = 1253,
-		Froneone0Japan = 1274,
-		Edgetwo00 = 1325,
-		Frnineone0Xt = 1328,
-		Edgeeight00Taiwan = 1333,
-		Edgeeight00Japan = 1334,
-		Alf0Four = 1341,
-		Frsixone0 = 1345,
-		Frtwoone0Japan = 1360,
+		Froneone0Japan = 1274 => "fr110_japan",
+		Edgetwo00 = 1325 => "edge200",
+		Frnineone0Xt = 1328 => "fr910xt",
+		Edgeeight00Taiwan = 1333 => "edge800_taiwan",
+		Edgeeight00Japan = 1334 => "edge800_japan",
+		Alf0Four = 1341 => "alf04",
+		Frsixone0 = 1345 => "fr610",
+		Frtwoone0Japan = 1360 => "fr210_japan",
 		VectorSs = 1380,
 		VectorCp = 1381,
-		Edgeeight00China = 1386,
-		Edgefive00China = 1387,
-		ApproachGone0 = 1405,
-		Frsixone0Japan = 1410,
-		Edgefive00Korea = 1422,
-		Frseven0 = 1436,
-		Frthreeone0XtFourt = 1446,
+		Edgeeight00China = 1386 => "edge800_china",
+		Edgefive00China = 1387 => "edge500_china",
+		ApproachGone0 = 1405 => "approach_g10",
+		Frsixone0Japan = 1410 => "fr610_japan",
+		Edgefive00Korea = 1422 => "edge500_korea",
+		Frseven0 = 1436 => "fr70",
+		Frthreeone0XtFourt = 1446 => "fr310xt_4t",
 		Amx = 1461,
-		Frone0 = 1482,
-		Edgeeight00Korea = 1497,
+		Frone0 = 1482 => "fr10",
+		Edgeeight00Korea = 1497 => "edge800_korea",
 		Swim = 1499,
-		Frnineone0XtChina = 1537,
+		Frnineone0XtChina = 1537 => "fr910xt_china",
 		Fenix = 1551,
-		Edgetwo00Taiwan = 1555,
-		Edgefiveone0 = 1561,
-		Edgeeightone0 = 1567,
+		Edgetwo00Taiwan = 1555 => "edge200_taiwan",
+		Edgefiveone0 = 1561 => "edge510",
+		Edgeeightone0 = 1567 => "edge810",
 		Tempe = 1570,
-		Frnineone0XtJapan = 1600,
-		Frsixtwo0 = 1623,
-		Frtwotwo0 = 1632,
-		Frnineone0XtKorea = 1664,
-		Frone0Japan = 1688,
-		Edgeeightone0Japan = 1721,
+		Frnineone0XtJapan = 1600 => "fr910xt_japan",
+		Frsixtwo0 = 1623 => "fr620",
+		Frtwotwo0 = 1632 => "fr220",
+		Frnineone0XtKorea = 1664 => "fr910xt_korea",
+		Frone0Japan = 1688 => "fr10_japan",
+		Edgeeightone0Japan = 1721 => "edge810_japan",
 		VirbElite = 1735,
 		EdgeTouring = 1736,
-		Edgefiveone0Japan = 1742,
+		Edgefiveone0Japan = 1742 => "edge510_japan",
 		HrmTri = 1743,
 		HrmRun = 1752,
-		Frninetwo0Xt = 1765,
-		Edgefiveone0Asia = 1821,
-		Edgeeightone0China = 1822,
-		Edgeeightone0Taiwan = 1823,
-		Edgeone000 = 1836,
+		Frninetwo0Xt = 1765 => "fr920xt",
+		Edgefiveone0Asia = 1821 => "edge510_asia",
+		Edgeeightone0China = 1822 => "edge810_china",
+		Edgeeightone0Taiwan = 1823 => "edge810_taiwan",
+		Edgeone000 = 1836 => "edge1000",
 		VivoFit = 1837,
 		VirbRemote = 1853,
 		VivoKi = 1885,
-		Fronefive = 1903,
+		Fronefive = 1903 => "fr15",
 		VivoActive = 1907,
-		Edgefiveone0Korea = 1918,
-		Frsixtwo0Japan = 1928,
-		Frsixtwo0China = 1929,
-		Frtwotwo0Japan = 1930,
-		Frtwotwo0China = 1931,
-		ApproachSsix = 1936,
+		Edgefiveone0Korea = 1918 => "edge510_korea",
+		Frsixtwo0Japan = 1928 => "fr620_japan",
+		Frsixtwo0China = 1929 => "fr620_china",
+		Frtwotwo0Japan = 1930 => "fr220_japan",
+		Frtwotwo0China = 1931 => "fr220_china",
+		ApproachSsix = 1936 => "approach_s6",
 		VivoSmart = 1956,
-		Fenixtwo = 1967,
+		Fenixtwo = 1967 => "fenix2",
 		Epix = 1988,
-		Fenixthree = 2050,
-		Edgeone000Taiwan = 2052,
-		Edgeone000Japan = 2053,
-		FronefiveJapan = 2061,
-		Edgefivetwo0 = 2067,
-		Edgeone000China = 2070,
-		Frsixtwo0Russia = 2072,
-		Frtwotwo0Russia = 2073,
+		Fenixthree = 2050 => "fenix3",
+		Edgeone000Taiwan = 2052 => "edge1000_taiwan",
+		Edgeone000Japan = 2053 => "edge1000_japan",
+		FronefiveJapan = 2061 => "fr15_japan",
+		Edgefivetwo0 = 2067 => "edge520",
+		Edgeone000China = 2070 => "edge1000_china",
+		Frsixtwo0Russia = 2072 => "fr620_russia",
+		Frtwotwo0Russia = 2073 => "fr220_russia",
 		VectorS = 2079,
-		Edgeone000Korea = 2100,
-		Frninetwo0XtTaiwan = 2130,
-		Frninetwo0XtChina = 2131,
-		Frninetwo0XtJapan = 2132,
+		Edgeone000Korea = 2100 => "edge1000_korea",
+		Frninetwo0XtTaiwan = 2130 => "fr920xt_taiwan",
+		Frninetwo0XtChina = 2131 => "fr920xt_china",
+		Frninetwo0XtJapan = 2132 => "fr920xt_japan",
 		Virbx = 2134,
 		VivoSmartApac = 2135,
 		EtrexTouch = 2140,
-		Edgetwofive = 2147,
-		Frtwofive = 2148,
-		VivoFittwo = 2150,
-		Frtwotwofive = 2153,
-		Frsixthree0 = 2156,
-		Frtwothree0 = 2157,
-		Frseventhreefivext = 2158,
+		Edgetwofive = 2147 => "edge25",
+		Frtwofive = 2148 => "fr25",
+		VivoFittwo = 2150 => "vivo_fit2",
+		Frtwotwofive = 2153 => "fr225",
+		Frsixthree0 = 2156 => "fr630",
+		Frtwothree0 = 2157 => "fr230",
+		Frseventhreefivext = 2158 => "fr735xt",
 		VivoActiveApac = 2160,
-		VectorTwo = 2161,
-		VectorTwos = 2162,
+		VectorTwo = 2161 => "vector_2",
+		VectorTwos = 2162 => "vector_2s",
 		Virbxe = 2172,
-		Frsixtwo0Taiwan = 2173,
-		Frtwotwo0Taiwan = 2174,
+		Frsixtwo0Taiwan = 2173 => "fr620_taiwan",
+		Frtwotwo0Taiwan = 2174 => "fr220_taiwan",
 		Truswing = 2175,
-		Dtwoairvenu = 2187,
-		FenixthreeChina = 2188,
-		FenixthreeTwn = 2189,
+		Dtwoairvenu = 2187 => "d2airvenu",
+		FenixthreeChina = 2188 => "fenix3_china",
+		FenixthreeTwn = 2189 => "fenix3_twn",
 		VariaHeadlight = 2192,
 		VariaTaillightOld = 2193,
-		EdgeExploreOne000 = 2204,
-		FrtwotwofiveAsia = 2219,
+		EdgeExploreOne000 = 2204 => "edge_explore_1000",
+		FrtwotwofiveAsia = 2219 => "fr225_asia",
 		VariaRadarTaillight = 2225,
 		VariaRadarDisplay = 2226,
-		Edgetwo0 = 2238,
-		Edgefivetwo0Asia = 2260,
-		Edgefivetwo0Japan = 2261,
-		DtwoBravo = 2262,
-		ApproachStwo0 = 2266,
-		VivoSmarttwo = 2271,
-		Edgeone000Thai = 2274,
+		Edgetwo0 = 2238 => "edge20",
+		Edgefivetwo0Asia = 2260 => "edge520_asia",
+		Edgefivetwo0Japan = 2261 => "edge520_japan",
+		DtwoBravo = 2262 => "d2_bravo",
+		ApproachStwo0 = 2266 => "approach_s20",
+		VivoSmarttwo = 2271 => "vivo_smart2",
+		Edgeone000Thai = 2274 => "edge1000_thai",
 		VariaRemote = 2276,
-		EdgetwofiveAsia = 2288,
-		EdgetwofiveJpn = 2289,
-		Edgetwo0Asia = 2290,
-		ApproachXfour0 = 2292,
-		FenixthreeJapan = 2293,
+		EdgetwofiveAsia = 2288 => "edge25_asia",
+		EdgetwofiveJpn = 2289 => "edge25_jpn",
+		Edgetwo0Asia = 2290 => "edge20_asia",
+		ApproachXfour0 = 2292 => "approach_x40",
+		FenixthreeJapan = 2293 => "fenix3_japan",
 		VivoSmartEmea = 2294,
-		Frsixthree0Asia = 2310,
-		Frsixthree0Jpn = 2311,
-		Frtwothree0Jpn = 2313,
-		HrmfourRun = 2327,
+		Frsixthree0Asia = 2310 => "fr630_asia",
+		Frsixthree0Jpn = 2311 => "fr630_jpn",
+		Frtwothree0Jpn = 2313 => "fr230_jpn",
+		HrmfourRun = 2327 => "hrm4_run",
 		EpixJapan = 2332,
 		VivoActiveHr = 2337,
 		VivoSmartGpsHr = 2347,
@@ -1462,111 +1475,111 @@ crate::key_value_enum! {
 		VivoSmartGpsHrAsia = 2362,
 		VivoMove = 2368,
 		VariaTaillight = 2379,
-		FrtwothreefiveAsia = 2396,
-		FrtwothreefiveJapan = 2397,
+		FrtwothreefiveAsia = 2396 => "fr235_asia",
+		FrtwothreefiveJapan = 2397 => "fr235_japan",
 		VariaVision = 2398,
-		VivoFitthree = 2406,
-		FenixthreeKorea = 2407,
-		FenixthreeSea = 2408,
-		FenixthreeHr = 2413,
-		VirbUltraThree0 = 2417,
+		VivoFitthree = 2406 => "vivo_fit3",
+		FenixthreeKorea = 2407 => "fenix3_korea",
+		FenixthreeSea = 2408 => "fenix3_sea",
+		FenixthreeHr = 2413 => "fenix3_hr",
+		VirbUltraThree0 = 2417 => "virb_ultra_30",
 		IndexSmartScale = 2429,
-		Frtwothreefive = 2431,
-		FenixthreeChronos = 2432,
-		Oregonsevenxx = 2441,
-		Rinosevenxx = 2444,
+		Frtwothreefive = 2431 => "fr235",
+		FenixthreeChronos = 2432 => "fenix3_chronos",
+		Oregonsevenxx = 2441 => "oregon7xx",
+		Rinosevenxx = 2444 => "rino7xx",
 		EpixKorea = 2457,
-		FenixthreeHrChn = 2473,
-		FenixthreeHrTwn = 2474,
-		FenixthreeHrJpn = 2475,
-		FenixthreeHrSea = 2476,
-		FenixthreeHrKor = 2477,
+		FenixthreeHrChn = 2473 => "fenix3_hr_chn",
+		FenixthreeHrTwn = 2474 => "fenix3_hr_twn",
+		FenixthreeHrJpn = 2475 => "fenix3_hr_jpn",
+		FenixthreeHrSea = 2476 => "fenix3_hr_sea",
+		FenixthreeHrKor = 2477 => "fenix3_hr_kor",
 		Nautix = 2496,
 		VivoActiveHrApac = 2497,
-		Frthreefive = 2503,
-		OregonsevenxxWw = 2512,
-		EdgeEighttwo0 = 2530,
-		EdgeExploreEighttwo0 = 2531,
-		FrseventhreefivextApac = 2533,
-		FrseventhreefivextJapan = 2534,
-		Fenixfives = 2544,
-		DtwoBravoTitanium = 2547,
-		VariaUteight00 = 2567,
+		Frthreefive = 2503 => "fr35",
+		OregonsevenxxWw = 2512 => "oregon7xx_ww",
+		EdgeEighttwo0 = 2530 => "edge_820",
+		EdgeExploreEighttwo0 = 2531 => "edge_explore_820",
+		FrseventhreefivextApac = 2533 => "fr735xt_apac",
+		FrseventhreefivextJapan = 2534 => "fr735xt_japan",
+		Fenixfives = 2544 => "fenix5s",
+		DtwoBravoTitanium = 2547 => "d2_bravo_titanium",
+		VariaUteight00 = 2567 => "varia_ut800",
 		RunningDynamicsPod = 2593,
-		EdgeEighttwo0China = 2599,
-		EdgeEighttwo0Japan = 2600,
-		Fenixfivex = 2604,
+		EdgeEighttwo0China = 2599 => "edge_820_china",
+		EdgeEighttwo0Japan = 2600 => "edge_820_japan",
+		Fenixfivex = 2604 => "fenix5x",
 		VivoFitJr = 2606,
-		VivoSmartthree = 2622,
+		VivoSmartthree = 2622 => "vivo_smart3",
 		VivoSport = 2623,
-		EdgeEighttwo0Taiwan = 2628,
-		EdgeEighttwo0Korea = 2629,
-		EdgeEighttwo0Sea = 2630,
-		FrthreefiveHebrew = 2650,
-		ApproachSsix0 = 2656,
-		FrthreefiveApac = 2667,
-		FrthreefiveJapan = 2668,
-		FenixthreeChronosAsia = 2675,
-		VirbThreesix0 = 2687,
-		Frninethreefive = 2691,
-		Fenixfive = 2697,
-		Vivoactivethree = 2700,
-		FrtwothreefiveChinaNfc = 2733,
-		ForetrexSix0OneSeven0One = 2769,
+		EdgeEighttwo0Taiwan = 2628 => "edge_820_taiwan",
+		EdgeEighttwo0Korea = 2629 => "edge_820_korea",
+		EdgeEighttwo0Sea = 2630 => "edge_820_sea",
+		FrthreefiveHebrew = 2650 => "fr35_hebrew",
+		ApproachSsix0 = 2656 => "approach_s60",
+		FrthreefiveApac = 2667 => "fr35_apac",
+		FrthreefiveJapan = 2668 => "fr35_japan",
+		FenixthreeChronosAsia = 2675 => "fenix3_chronos_asia",
+		VirbThreesix0 = 2687 => "virb_360",
+		Frninethreefive = 2691 => "fr935",
+		Fenixfive = 2697 => "fenix5",
+		Vivoactivethree = 2700 => "vivoactive3",
+		FrtwothreefiveChinaNfc = 2733 => "fr235_china_nfc",
+		ForetrexSix0OneSeven0One = 2769 => "foretrex_601_701",
 		VivoMoveHr = 2772,
-		EdgeOne0Three0 = 2713,
-		FrthreefiveSea = 2727,
-		VectorThree = 2787,
-		FenixfiveAsia = 2796,
-		FenixfivesAsia = 2797,
-		FenixfivexAsia = 2798,
-		ApproachZeight0 = 2806,
-		FrthreefiveKorea = 2814,
-		Dtwocharlie = 2819,
-		VivoSmartthreeApac = 2831,
+		EdgeOne0Three0 = 2713 => "edge_1030",
+		FrthreefiveSea = 2727 => "fr35_sea",
+		VectorThree = 2787 => "vector_3",
+		FenixfiveAsia = 2796 => "fenix5_asia",
+		FenixfivesAsia = 2797 => "fenix5s_asia",
+		FenixfivexAsia = 2798 => "fenix5x_asia",
+		ApproachZeight0 = 2806 => "approach_z80",
+		FrthreefiveKorea = 2814 => "fr35_korea",
+		Dtwocharlie = 2819 => "d2charlie",
+		VivoSmartthreeApac = 2831 => "vivo_smart3_apac",
 		VivoSportApac = 2832,
-		FrninethreefiveAsia = 2833,
+		FrninethreefiveAsia = 2833 => "fr935_asia",
 		Descent = 2859,
-		VivoFitfour = 2878,
-		Frsixfourfive = 2886,
-		Frsixfourfivem = 2888,
-		Frthree0 = 2891,
-		FenixfivesPlus = 2900,
-		EdgeOnethree0 = 2909,
-		EdgeOne0Three0Asia = 2924,
-		VivosmartFour = 2927,
+		VivoFitfour = 2878 => "vivo_fit4",
+		Frsixfourfive = 2886 => "fr645",
+		Frsixfourfivem = 2888 => "fr645m",
+		Frthree0 = 2891 => "fr30",
+		FenixfivesPlus = 2900 => "fenix5s_plus",
+		EdgeOnethree0 = 2909 => "edge_130",
+		EdgeOne0Three0Asia = 2924 => "edge_1030_asia",
+		VivosmartFour = 2927 => "vivosmart_4",
 		VivoMoveHrAsia = 2945,
-		ApproachXone0 = 2962,
-		Frthree0Asia = 2977,
-		VivoactivethreemW = 2988,
-		FrsixfourfiveAsia = 3003,
-		FrsixfourfivemAsia = 3004,
+		ApproachXone0 = 2962 => "approach_x10",
+		Frthree0Asia = 2977 => "fr30_asia",
+		VivoactivethreemW = 2988 => "vivoactive3m_w",
+		FrsixfourfiveAsia = 3003 => "fr645_asia",
+		FrsixfourfivemAsia = 3004 => "fr645m_asia",
 		EdgeExplore = 3011,
-		Gpsmapsixsix = 3028,
-		ApproachSone0 = 3049,
-		VivoactivethreemL = 3066,
-		ApproachGeight0 = 3085,
-		EdgeOnethree0Asia = 3092,
-		EdgeOne0Three0Bontrager = 3095,
-		FenixfivePlus = 3110,
-		FenixfivexPlus = 3111,
-		EdgeFivetwo0Plus = 3112,
-		Frninefourfive = 3113,
-		EdgeFivethree0 = 3121,
-		EdgeEightthree0 = 3122,
+		Gpsmapsixsix = 3028 => "gpsmap66",
+		ApproachSone0 = 3049 => "approach_s10",
+		VivoactivethreemL = 3066 => "vivoactive3m_l",
+		ApproachGeight0 = 3085 => "approach_g80",
+		EdgeOnethree0Asia = 3092 => "edge_130_asia",
+		EdgeOne0Three0Bontrager = 3095 => "edge_1030_bontrager",
+		FenixfivePlus = 3110 => "fenix5_plus",
+		FenixfivexPlus = 3111 => "fenix5x_plus",
+		EdgeFivetwo0Plus = 3112 => "edge_520_plus",
+		Frninefourfive = 3113 => "fr945",
+		EdgeFivethree0 = 3121 => "edge_530",
+		EdgeEightthree0 = 3122 => "edge_830",
 		InstinctEsports = 3126,
-		FenixfivesPlusApac = 3134,
-		FenixfivexPlusApac = 3135,
-		EdgeFivetwo0PlusApac = 3142,
-		DescentTone = 3143,
-		FrtwothreefivelAsia = 3144,
-		FrtwofourfiveAsia = 3145,
-		VivoActivethreemApac = 3163,
-		GenthreeBsm = 3192,
-		GenthreeBcm = 3193,
-		VivoSmartfourAsia = 3218,
-		VivoactivefourSmall = 3224,
-		VivoactivefourLarge = 3225,
+		FenixfivesPlusApac = 3134 => "fenix5s_plus_apac",
+		FenixfivexPlusApac = 3135 => "fenix5x_plus_apac",
+		EdgeFivetwo0PlusApac = 3142 => "edge_520_plus_apac",
+		DescentTone = 3143 => "descent_t1",
+		FrtwothreefivelAsia = 3144 => "fr235l_asia",
+		FrtwofourfiveAsia = 3145 => "fr245_asia",
+		VivoActivethreemApac = 3163 => "vivo_active3m_apac",
+		GenthreeBsm = 3192 => "gen3_bsm",
+		GenthreeBcm = 3193 => "gen3_bcm",
+		VivoSmartfourAsia = 3218 => "vivo_smart4_asia",
+		VivoactivefourSmall = 3224 => "vivoactive4_small",
+		VivoactivefourLarge = 3225 => "vivoactive4_large",
 		Venu = 3226,
 		MarqDriver = 3246,
 		MarqAviator = 3247,
@@ -1574,133 +1587,133 @@ crate::key_value_enum! {
 		MarqCommander = 3249,
 		MarqExpedition = 3250,
 		MarqAthlete = 3251,
-		DescentMktwo = 3258,
-		Gpsmapsixsixi = 3284,
-		FenixsixSSport = 3287,
-		FenixsixS = 3288,
-		FenixsixSport = 3289,
-		Fenixsix = 3290,
-		Fenixsixx = 3291,
+		DescentMktwo = 3258 => "descent_mk2",
+		Gpsmapsixsixi = 3284 => "gpsmap66i",
+		FenixsixSSport = 3287 => "fenix6_s_sport",
+		FenixsixS = 3288 => "fenix6_s",
+		FenixsixSport = 3289 => "fenix6_sport",
+		Fenixsix = 3290 => "fenix6",
+		Fenixsixx = 3291 => "fenix6x",
 		HrmDual = 3299,
 		HrmPro = 3300,
-		VivoMovethreePremium = 3308,
-		ApproachSfour0 = 3314,
-		FrtwofourfivemAsia = 3321,
-		EdgeFivethree0Apac = 3349,
-		EdgeEightthree0Apac = 3350,
-		VivoMovethree = 3378,
-		VivoActivefourSmallAsia = 3387,
-		VivoActivefourLargeAsia = 3388,
-		VivoActivefourOledAsia = 3389,
-		Swimtwo = 3405,
+		VivoMovethreePremium = 3308 => "vivo_move3_premium",
+		ApproachSfour0 = 3314 => "approach_s40",
+		FrtwofourfivemAsia = 3321 => "fr245m_asia",
+		EdgeFivethree0Apac = 3349 => "edge_530_apac",
+		EdgeEightthree0Apac = 3350 => "edge_830_apac",
+		VivoMovethree = 3378 => "vivo_move3",
+		VivoActivefourSmallAsia = 3387 => "vivo_active4_small_asia",
+		VivoActivefourLargeAsia = 3388 => "vivo_active4_large_asia",
+		VivoActivefourOledAsia = 3389 => "vivo_active4_oled_asia",
+		Swimtwo = 3405 => "swim2",
 		MarqDriverAsia = 3420,
 		MarqAviatorAsia = 3421,
-		VivoMovethreeAsia = 3422,
-		FrninefourfiveAsia = 3441,
-		VivoActivethreetChn = 3446,
+		VivoMovethreeAsia = 3422 => "vivo_move3_asia",
+		FrninefourfiveAsia = 3441 => "fr945_asia",
+		VivoActivethreetChn = 3446 => "vivo_active3t_chn",
 		MarqCaptainAsia = 3448,
 		MarqCommanderAsia = 3449,
 		MarqExpeditionAsia = 3450,
 		MarqAthleteAsia = 3451,
 		InstinctSolar = 3466,
-		FrfourfiveAsia = 3469,
-		VivoactivethreeDaimler = 3473,
+		FrfourfiveAsia = 3469 => "fr45_asia",
+		VivoactivethreeDaimler = 3473 => "vivoactive3_daimler",
 		LegacyRey = 3498,
 		LegacyDarthVader = 3499,
 		LegacyCaptainMarvel = 3500,
 		LegacyFirstAvenger = 3501,
-		FenixsixsSportAsia = 3512,
-		FenixsixsAsia = 3513,
-		FenixsixSportAsia = 3514,
-		FenixsixAsia = 3515,
-		FenixsixxAsia = 3516,
+		FenixsixsSportAsia = 3512 => "fenix6s_sport_asia",
+		FenixsixsAsia = 3513 => "fenix6s_asia",
+		FenixsixSportAsia = 3514 => "fenix6_sport_asia",
+		FenixsixAsia = 3515 => "fenix6_asia",
+		FenixsixxAsia = 3516 => "fenix6x_asia",
 		LegacyCaptainMarvelAsia = 3535,
 		LegacyFirstAvengerAsia = 3536,
 		LegacyReyAsia = 3537,
 		LegacyDarthVaderAsia = 3538,
-		DescentMktwos = 3542,
-		EdgeOnethree0Plus = 3558,
-		EdgeOne0Three0Plus = 3570,
-		RallyTwo00 = 3578,
-		Frsevenfourfive = 3589,
+		DescentMktwos = 3542 => "descent_mk2s",
+		EdgeOnethree0Plus = 3558 => "edge_130_plus",
+		EdgeOne0Three0Plus = 3570 => "edge_1030_plus",
+		RallyTwo00 = 3578 => "rally_200",
+		Frsevenfourfive = 3589 => "fr745",
 		Venusq = 3600,
 		Lily = 3615,
 		MarqAdventurer = 3624,
 		Enduro = 3638,
-		SwimtwoApac = 3639,
+		SwimtwoApac = 3639 => "swim2_apac",
 		MarqAdventurerAsia = 3648,
-		FrninefourfiveLte = 3652,
-		DescentMktwoAsia = 3702,
-		Venutwo = 3703,
-		Venutwos = 3704,
+		FrninefourfiveLte = 3652 => "fr945_lte",
+		DescentMktwoAsia = 3702 => "descent_mk2_asia",
+		Venutwo = 3703 => "venu2",
+		Venutwos = 3704 => "venu2s",
 		VenuDaimlerAsia = 3737,
 		MarqGolfer = 3739,
 		VenuDaimler = 3740,
-		FrsevenfourfiveAsia = 3794,
-		VariaRctsevenonefive = 3808,
+		FrsevenfourfiveAsia = 3794 => "fr745_asia",
+		VariaRctsevenonefive = 3808 => "varia_rct715",
 		LilyAsia = 3809,
-		EdgeOne0Three0PlusAsia = 3812,
-		EdgeOnethree0PlusAsia = 3813,
-		ApproachSonetwo = 3823,
+		EdgeOne0Three0PlusAsia = 3812 => "edge_1030_plus_asia",
+		EdgeOnethree0PlusAsia = 3813 => "edge_130_plus_asia",
+		ApproachSonetwo = 3823 => "approach_s12",
 		EnduroAsia = 3872,
 		VenusqAsia = 3837,
-		EdgeOne0Four0 = 3843,
+		EdgeOne0Four0 = 3843 => "edge_1040",
 		MarqGolferAsia = 3850,
-		VenutwoPlus = 3851,
+		VenutwoPlus = 3851 => "venu2_plus",
 		Gnss = 3865,
-		Frfivefive = 3869,
-		InstinctTwo = 3888,
-		Fenixsevens = 3905,
-		Fenixseven = 3906,
-		Fenixsevenx = 3907,
-		FenixsevensApac = 3908,
-		FenixsevenApac = 3909,
-		FenixsevenxApac = 3910,
+		Frfivefive = 3869 => "fr55",
+		InstinctTwo = 3888 => "instinct_2",
+		Fenixsevens = 3905 => "fenix7s",
+		Fenixseven = 3906 => "fenix7",
+		Fenixsevenx = 3907 => "fenix7x",
+		FenixsevensApac = 3908 => "fenix7s_apac",
+		FenixsevenApac = 3909 => "fenix7_apac",
+		FenixsevenxApac = 3910 => "fenix7x_apac",
 		ApproachGonetwo =  3927,
-		DescentMktwosAsia = 3930,
-		ApproachSfourtwo = 3934,
-		EpixGentwo = 3943,
-		EpixGentwoApac = 3944,
-		VenutwosAsia = 3949,
-		VenutwoAsia = 3950,
-		FrninefourfiveLteAsia = 3978,
+		DescentMktwosAsia = 3930 => "descent_mk2s_asia",
+		ApproachSfourtwo = 3934 => "approach_s42",
+		EpixGentwo = 3943 => "epix_gen2",
+		EpixGentwoApac = 3944 => "epix_gen2_apac",
+		VenutwosAsia = 3949 => "venu2s_asia",
+		VenutwoAsia = 3950 => "venu2_asia",
+		FrninefourfiveLteAsia = 3978 => "fr945_lte_asia",
 		VivoMoveSport = 3982,
 		VivomoveTrend = 3983,
-		ApproachSonetwoAsia = 3986,
-		FrtwofivefiveMusic = 3990,
-		FrtwofivefiveSmallMusic = 3991,
-		Frtwofivefive = 3992,
-		FrtwofivefiveSmall = 3993,
+		ApproachSonetwoAsia = 3986 => "approach_s12_asia",
+		FrtwofivefiveMusic = 3990 => "fr255_music",
+		FrtwofivefiveSmallMusic = 3991 => "fr255_small_music",
+		Frtwofivefive = 3992 => "fr255",
+		FrtwofivefiveSmall = 3993 => "fr255_small",
 		ApproachGonetwoAsia =  4001,
-		ApproachSfourtwoAsia = 4002,
-		DescentGone = 4005,
-		VenutwoPlusAsia = 4017,
-		Frninefivefive = 4024,
-		FrfivefiveAsia = 4033,
-		EdgeFivefour0 = 4061,
-		EdgeEightfour0 = 4062,
-		VivosmartFive = 4063,
-		InstinctTwoAsia = 4071,
-		MarqGentwo = 4105,
-		Venusqtwo = 4115,
-		Venusqtwomusic = 4116,
-		MarqGentwoAviator = 4124,
-		DtwoAirXone0 = 4125,
+		ApproachSfourtwoAsia = 4002 => "approach_s42_asia",
+		DescentGone = 4005 => "descent_g1",
+		VenutwoPlusAsia = 4017 => "venu2_plus_asia",
+		Frninefivefive = 4024 => "fr955",
+		FrfivefiveAsia = 4033 => "fr55_asia",
+		EdgeFivefour0 = 4061 => "edge_540",
+		EdgeEightfour0 = 4062 => "edge_840",
+		VivosmartFive = 4063 => "vivosmart_5",
+		InstinctTwoAsia = 4071 => "instinct_2_asia",
+		MarqGentwo = 4105 => "marq_gen2",
+		Venusqtwo = 4115 => "venusq2",
+		Venusqtwomusic = 4116 => "venusq2music",
+		MarqGentwoAviator = 4124 => "marq_gen2_aviator",
+		DtwoAirXone0 = 4125 => "d2_air_x10",
 		HrmProPlus = 4130,
-		DescentGoneAsia = 4132,
-		Tactixseven = 4135,
+		DescentGoneAsia = 4132 => "descent_g1_asia",
+		Tactixseven = 4135 => "tactix7",
 		InstinctCrossover = 4155,
-		EdgeExploretwo = 4169,
-		DescentMkthree = 4222,
-		DescentMkthreei = 4223,
-		ApproachSseven0 = 4233,
-		FrtwosixfiveLarge = 4257,
-		FrtwosixfiveSmall = 4258,
-		Venuthree = 4260,
-		Venuthrees = 4261,
+		EdgeExploretwo = 4169 => "edge_explore2",
+		DescentMkthree = 4222 => "descent_mk3",
+		DescentMkthreei = 4223 => "descent_mk3i",
+		ApproachSseven0 = 4233 => "approach_s70",
+		FrtwosixfiveLarge = 4257 => "fr265_large",
+		FrtwosixfiveSmall = 4258 => "fr265_small",
+		Venuthree = 4260 => "venu3",
+		Venuthrees = 4261 => "venu3s",
 		TacxNeoSmart = 4265,
-		TacxNeotwoSmart = 4266,
-		TacxNeotwoTSmart = 4267,
+		TacxNeotwoSmart = 4266 => "tacx_neo2_smart",
+		TacxNeotwoTSmart = 4267 => "tacx_neo2_t_smart",
 		TacxNeoSmartBike = 4268,
 		TacxSatoriSmart = 4269,
 		TacxFlowSmart = 4270,
@@ -1708,25 +1721,25 @@ crate::key_value_enum! {
 		TacxBushidoSmart = 4272,
 		TacxGeniusSmart = 4273,
 		TacxFluxFluxSSmart = 4274,
-		TacxFluxtwoSmart = 4275,
+		TacxFluxtwoSmart = 4275 => "tacx_flux2_smart",
 		TacxMagnum = 4276,
-		EdgeOne0Four0Asia = 4305,
-		EpixGentwoProFourtwo = 4312,
-		EpixGentwoProFourseven = 4313,
-		EpixGentwoProFiveone = 4314,
-		Frninesixfive = 4315,
-		Endurotwo = 4341,
-		FenixsevensProSolar = 4374,
-		FenixsevenProSolar = 4375,
-		FenixsevenxProSolar = 4376,
-		Lilytwo = 4380,
-		InstinctTwox = 4394,
-		Vivoactivefive = 4426,
-		DescentTtwo = 4442,
+		EdgeOne0Four0Asia = 4305 => "edge_1040_asia",
+		EpixGentwoProFourtwo = 4312 => "epix_gen2_pro_42",
+		EpixGentwoProFourseven = 4313 => "epix_gen2_pro_47",
+		EpixGentwoProFiveone = 4314 => "epix_gen2_pro_51",
+		Frninesixfive = 4315 => "fr965",
+		Endurotwo = 4341 => "enduro2",
+		FenixsevensProSolar = 4374 => "fenix7s_pro_solar",
+		FenixsevenProSolar = 4375 => "fenix7_pro_solar",
+		FenixsevenxProSolar = 4376 => "fenix7x_pro_solar",
+		Lilytwo = 4380 => "lily2",
+		InstinctTwox = 4394 => "instinct_2x",
+		Vivoactivefive = 4426 => "vivoactive5",
+		DescentTtwo = 4442 => "descent_t2",
 		HrmFit = 4446,
-		MarqGentwoCommander = 4472,
-		DtwoMachonePro = 4556,
-		Sdmfour = 10007,
+		MarqGentwoCommander = 4472 => "marq_gen2_commander",
+		DtwoMachonePro = 4556 => "d2_mach1_pro",
+		Sdmfour = 10007 => "sdm4",
 		EdgeRemote = 10014,
 		TacxTrainingAppWin = 20533,
 		TacxTrainingAppMac = 20534,
@@ -1778,6 +1791,7 @@ crate::key_value_enum! {
 	}
 }
 crate::key_value_enum! {
+	#[bitfield]
 	pub enum WorkoutCapabilities { 
 		Interval = 0x00000001,
 		Custom = 0x00000002,
@@ -1813,6 +1827,7 @@ crate::key_value_enum! {
 	}
 }
 crate::key_value_enum! {
+	#[bitfield]
 	pub enum CourseCapabilities { 
 		Processed = 0x00000001,
 		Valid = 0x00000002,
@@ -1953,6 +1968,7 @@ crate::key_value_enum! {
 	}
 }
 crate::key_value_enum! {
+	#[bitfield]
 	pub enum ConnectivityCapabilities { 
 		Bluetooth = 0x00000001,
 		BluetoothLe = 0x00000002,
@@ -2356,10 +2372,10 @@ crate::key_value_enum! {
 }
 crate::key_value_enum! {
 	pub enum CameraOrientationType { 
-		CameraOrientation0 = 0,
-		CameraOrientationNine0 = 1,
-		CameraOrientationOneeight0 = 2,
-		CameraOrientationTwoseven0 = 3,
+		CameraOrientation0 = 0 => "camera_orientation_0",
+		CameraOrientationNine0 = 1 => "camera_orientation_90",
+		CameraOrientationOneeight0 = 2 => "camera_orientation_180",
+		CameraOrientationTwoseven0 = 3 => "camera_orientation_270",
 	}
 }
 crate::key_value_enum! {
@@ -2371,6 +2387,7 @@ crate::key_value_enum! {
 	}
 }
 crate::key_value_enum! {
+	#[bitfield]
 	pub enum AttitudeValidity { 
 		TrackAngleHeadingValid = 0x0001,
 		PitchValid = 0x0002,
@@ -2504,8 +2521,8 @@ crate::key_value_enum! {
 		Sunrise = 21,
 		Sunset = 22,
 		ComparedToVirtualPartner = 23,
-		MaximumTwofourh = 24,
-		MinimumTwofourh = 25,
+		MaximumTwofourh = 24 => "maximum_24h",
+		MinimumTwofourh = 25 => "minimum_24h",
 		Minimum = 26,
 		First = 27,
 		Second = 28,
@@ -2515,15 +2532,15 @@ crate::key_value_enum! {
 		Moving = 32,
 		Stopped = 33,
 		EstimatedTotal = 34,
-		ZoneNine = 242,
-		ZoneEight = 243,
-		ZoneSeven = 244,
-		ZoneSix = 245,
-		ZoneFive = 246,
-		ZoneFour = 247,
-		ZoneThree = 248,
-		ZoneTwo = 249,
-		ZoneOne = 250,
+		ZoneNine = 242 => "zone_9",
+		ZoneEight = 243 => "zone_8",
+		ZoneSeven = 244 => "zone_7",
+		ZoneSix = 245 => "zone_6",
+		ZoneFive = 246 => "zone_5",
+		ZoneFour = 247 => "zone_4",
+		ZoneThree = 248 => "zone_3",
+		ZoneTwo = 249 => "zone_2",
+		ZoneOne = 250 => "zone_1",
 	}
 }
 crate::key_value_enum! {
@@ -2548,7 +2565,7 @@ crate::key_value_enum! {
 		Ascent = 17,
 		Descent = 18,
 		VerticalSpeed = 19,
-		DitwoBatteryLevel = 20,
+		DitwoBatteryLevel = 20 => "di2_battery_level",
 		FrontGear = 21,
 		RearGear = 22,
 		GearRatio = 23,
@@ -2628,6 +2645,7 @@ crate::key_value_enum! {
 	}
 }
 crate::key_value_enum! {
+	#[bitfield]
 	pub enum AutoActivityDetect { 
 		None = 0x00000000,
 		Running = 0x00000001,
@@ -2639,6 +2657,7 @@ crate::key_value_enum! {
 	}
 }
 crate::key_value_enum! {
+	#[bitfield]
 	pub enum SupportedExdScreenLayouts { 
 		FullScreen = 0x00000001,
 		HalfVertical = 0x00000002,
@@ -2653,22 +2672,22 @@ crate::key_value_enum! {
 crate::key_value_enum! {
 	pub enum FitBaseType { 
 		Enum = 0,
-		NumberValueS8 = 1,
-		NumberValueU8 = 2,
-		NumberValueS16 = 131,
-		NumberValueU16 = 132,
-		NumberValueS32 = 133,
-		NumberValueU32 = 134,
-		StringValue = 7,
-		NumberValueU64 = 136,
-		NumberValueU64 = 137,
-		NumberValueU8 = 10,
-		NumberValueU16 = 139,
-		NumberValueU32 = 140,
-		NumberValueU8 = 13,
-		NumberValueS64 = 142,
-		NumberValueU64 = 143,
-		NumberValueU64 = 144,
+		NumberValueS8 = 1 => "sint8",
+		NumberValueU8 = 2 => "uint8",
+		NumberValueS16 = 131 => "sint16",
+		NumberValueU16 = 132 => "uint16",
+		NumberValueS32 = 133 => "sint32",
+		NumberValueU32 = 134 => "uint32",
+		StringValue = 7 => "string",
+		NumberValueU64 = 136 => "float32",
+		NumberValueU64 = 137 => "float64",
+		NumberValueU8 = 10 => "uint8z",
+		NumberValueU16 = 139 => "uint16z",
+		NumberValueU32 = 140 => "uint32z",
+		NumberValueU8 = 13 => "byte",
+		NumberValueS64 = 142 => "sint64",
+		NumberValueU64 = 143 => "uint64",
+		NumberValueU64 = 144 => "uint64z",
 	}
 }
 crate::key_value_enum! {
@@ -2685,22 +2704,22 @@ crate::key_value_enum! {
 		ExitLeftIdx = 9,
 		ExitRightIdx = 10,
 		FerryIdx = 11,
-		RoundaboutFourfiveIdx = 12,
-		RoundaboutNine0Idx = 13,
-		RoundaboutOnethreefiveIdx = 14,
-		RoundaboutOneeight0Idx = 15,
-		RoundaboutTwotwofiveIdx = 16,
-		RoundaboutTwoseven0Idx = 17,
-		RoundaboutThreeonefiveIdx = 18,
-		RoundaboutThreesix0Idx = 19,
-		RoundaboutNegFourfiveIdx = 20,
-		RoundaboutNegNine0Idx = 21,
-		RoundaboutNegOnethreefiveIdx = 22,
-		RoundaboutNegOneeight0Idx = 23,
-		RoundaboutNegTwotwofiveIdx = 24,
-		RoundaboutNegTwoseven0Idx = 25,
-		RoundaboutNegThreeonefiveIdx = 26,
-		RoundaboutNegThreesix0Idx = 27,
+		RoundaboutFourfiveIdx = 12 => "roundabout_45_idx",
+		RoundaboutNine0Idx = 13 => "roundabout_90_idx",
+		RoundaboutOnethreefiveIdx = 14 => "roundabout_135_idx",
+		RoundaboutOneeight0Idx = 15 => "roundabout_180_idx",
+		RoundaboutTwotwofiveIdx = 16 => "roundabout_225_idx",
+		RoundaboutTwoseven0Idx = 17 => "roundabout_270_idx",
+		RoundaboutThreeonefiveIdx = 18 => "roundabout_315_idx",
+		RoundaboutThreesix0Idx = 19 => "roundabout_360_idx",
+		RoundaboutNegFourfiveIdx = 20 => "roundabout_neg_45_idx",
+		RoundaboutNegNine0Idx = 21 => "roundabout_neg_90_idx",
+		RoundaboutNegOnethreefiveIdx = 22 => "roundabout_neg_135_idx",
+		RoundaboutNegOneeight0Idx = 23 => "roundabout_neg_180_idx",
+		RoundaboutNegTwotwofiveIdx = 24 => "roundabout_neg_225_idx",
+		RoundaboutNegTwoseven0Idx = 25 => "roundabout_neg_270_idx",
+		RoundaboutNegThreeonefiveIdx = 26 => "roundabout_neg_315_idx",
+		RoundaboutNegThreesix0Idx = 27 => "roundabout_neg_360_idx",
 		RoundaboutGenericIdx = 28,
 		RoundaboutNegGenericIdx = 29,
 		SharpTurnLeftIdx = 30,
@@ -3290,7 +3309,7 @@ crate::key_value_enum! {
 }
 crate::key_value_enum! {
 	pub enum LateralRaiseExerciseName { 
-		FourfiveDegreeCableExternalRotation = 0,
+		FourfiveDegreeCableExternalRotation = 0 => "45_degree_cable_external_rotation",
 		AlternatingLateralRaiseWithStaticHold = 1,
 		BarMuscleUp = 2,
 		BentOverLateralRaise = 3,
@@ -3480,10 +3499,10 @@ crate::key_value_enum! {
 }
 crate::key_value_enum! {
 	pub enum PlankExerciseName { 
-		FourfiveDegreePlank = 0,
-		WeightedFourfiveDegreePlank = 1,
-		Nine0DegreeStaticHold = 2,
-		WeightedNine0DegreeStaticHold = 3,
+		FourfiveDegreePlank = 0 => "45_degree_plank",
+		WeightedFourfiveDegreePlank = 1 => "weighted_45_degree_plank",
+		Nine0DegreeStaticHold = 2 => "90_degree_static_hold",
+		WeightedNine0DegreeStaticHold = 3 => "weighted_90_degree_static_hold",
 		BearCrawl = 4,
 		WeightedBearCrawl = 5,
 		CrossBodyMountainClimber = 6,
@@ -3657,7 +3676,7 @@ crate::key_value_enum! {
 crate::key_value_enum! {
 	pub enum PullUpExerciseName { 
 		BandedPullUps = 0,
-		Three0DegreeLatPulldown = 1,
+		Three0DegreeLatPulldown = 1 => "30_degree_lat_pulldown",
 		BandAssistedChinUp = 2,
 		CloseGripChinUp = 3,
 		WeightedCloseGripChinUp = 4,
@@ -3848,7 +3867,7 @@ crate::key_value_enum! {
 }
 crate::key_value_enum! {
 	pub enum ShoulderStabilityExerciseName { 
-		Nine0DegreeCableExternalRotation = 0,
+		Nine0DegreeCableExternalRotation = 0 => "90_degree_cable_external_rotation",
 		BandExternalRotation = 1,
 		BandInternalRotation = 2,
 		BentArmLateralRaiseAndExternalRotation = 3,
@@ -4151,13 +4170,13 @@ crate::key_value_enum! {
 	pub enum WaterType { 
 		Fresh = 0,
 		Salt = 1,
-		Enonethreethreeonenine = 2,
+		Enonethreethreeonenine = 2 => "en13319",
 		Custom = 3,
 	}
 }
 crate::key_value_enum! {
 	pub enum TissueModelType { 
-		ZhlOnesixc = 0,
+		ZhlOnesixc = 0 => "zhl_16c",
 	}
 }
 crate::key_value_enum! {
@@ -4173,9 +4192,9 @@ crate::key_value_enum! {
 		GasSwitchPrompted = 1,
 		NearSurface = 2,
 		ApproachingNdl = 3,
-		PotwoWarn = 4,
-		PotwoCritHigh = 5,
-		PotwoCritLow = 6,
+		PotwoWarn = 4 => "po2_warn",
+		PotwoCritHigh = 5 => "po2_crit_high",
+		PotwoCritLow = 6 => "po2_crit_low",
 		TimeAlert = 7,
 		DepthAlert = 8,
 		DecoCeilingBroken = 9,
@@ -4201,7 +4220,7 @@ crate::key_value_enum! {
 		SwitchedToOpenCircuit = 29,
 		SwitchedToClosedCircuit = 30,
 		TankBatteryLow = 32,
-		PotwoCcrDilLow = 33,
+		PotwoCcrDilLow = 33 => "po2_ccr_dil_low",
 		DecoStopCleared = 34,
 		ApneaNeutralBuoyancy = 35,
 		ApneaTargetDepth = 36,
@@ -4347,6 +4366,6 @@ crate::key_value_enum! {
 crate::key_value_enum! {
 	pub enum NoFlyTimeMode { 
 		Standard = 0,
-		FlatTwofourHours = 1,
+		FlatTwofourHours = 1 => "flat_24_hours",
 	}
 }